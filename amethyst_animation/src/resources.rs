@@ -11,7 +11,7 @@ use amethyst_assets::{
 };
 use amethyst_core::{
     ecs::{CommandBuffer, Entity, EntityStore, SubWorld},
-    Transform,
+    NameIndex, Transform,
 };
 use derivative::Derivative;
 use fnv::FnvHashMap;
@@ -170,6 +170,24 @@ where
         }
     }
 
+    /// Create a hierarchy by resolving the `/` separated name path of each node relative to
+    /// `root`, see [`NameIndex::find_path_from`].
+    ///
+    /// Returns `None` if one of the paths does not resolve to an entity.
+    #[must_use]
+    pub fn from_paths<'p, W: EntityStore>(
+        index: &NameIndex,
+        world: &W,
+        root: Entity,
+        paths: impl IntoIterator<Item = (usize, &'p str)>,
+    ) -> Option<Self> {
+        let nodes = paths
+            .into_iter()
+            .map(|(node, path)| Some((node, index.find_path_from(world, root, path)?)))
+            .collect::<Option<_>>()?;
+        Some(Self::new_many(nodes))
+    }
+
     /// Create rest state for the hierarchy. Will copy the values from the base components for each
     /// entity in the hierarchy.
     pub fn rest_state(&self, world: &SubWorld<'_>, buffer: &mut CommandBuffer)
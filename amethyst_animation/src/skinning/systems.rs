@@ -6,7 +6,7 @@ use amethyst_core::{
         SystemBuilder,
     },
     math::{convert, Matrix4},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use amethyst_rendy::skinning::JointTransforms;
 use log::error;
@@ -29,12 +29,12 @@ impl System for VertexSkinningSystem {
         Box::new(
             SystemBuilder::new("VertexSkinningSystem")
                 .read_component::<Joint>()
-                .read_component::<Transform>()
+                .read_component::<GlobalTransform>()
                 .write_component::<Skin>()
                 .write_component::<JointTransforms>()
                 .with_query(
-                    <(Entity, Read<Transform>, Read<Joint>)>::query()
-                        .filter(maybe_changed::<Transform>()),
+                    <(Entity, Read<GlobalTransform>, Read<Joint>)>::query()
+                        .filter(maybe_changed::<GlobalTransform>()),
                 )
                 .build(move |_, world, _, global_transforms| {
                     #[cfg(feature = "profiler")]
@@ -50,7 +50,7 @@ impl System for VertexSkinningSystem {
                         }
                     });
 
-                    let mut q = <(Entity, &Transform, &mut JointTransforms)>::query();
+                    let mut q = <(Entity, &GlobalTransform, &mut JointTransforms)>::query();
                     let (mut left, mut right) = world.split_for_query(&q);
 
                     for entity in &updated_skins {
@@ -69,12 +69,12 @@ impl System for VertexSkinningSystem {
                                             {
                                                 Some((transform, inverse_bind_matrix))
                                             } else {
-                                                error!("Missing `Transform` Component for join entity {:?}",joint_entity );
+                                                error!("Missing `GlobalTransform` Component for join entity {:?}",joint_entity );
                                                 None
                                             }
                                         })
                                         .map(|(global, inverse_bind_matrix)| {
-                                            global.1.global_matrix()
+                                            global.1.matrix()
                                                 * inverse_bind_matrix
                                                 * bind_shape
                                         }),
@@ -84,7 +84,7 @@ impl System for VertexSkinningSystem {
                                 for (entity, mesh_global, matrix) in q.iter_mut(&mut left) {
                                     if skin.meshes.contains(entity) {
                                         if let Some(global_inverse) =
-                                            mesh_global.global_matrix().try_inverse()
+                                            mesh_global.matrix().try_inverse()
                                         {
                                             matrix.matrices.clear();
                                             matrix.matrices.extend(skin.joint_matrices.iter().map(
@@ -101,12 +101,12 @@ impl System for VertexSkinningSystem {
                         }
                     }
 
-                    let mut q = <(Entity, &Transform, &mut JointTransforms)>::query();
+                    let mut q = <(Entity, &GlobalTransform, &mut JointTransforms)>::query();
                     let (mut left, right) = world.split_for_query(&q);
 
                     for (entity, mesh_global, joint_transform) in q.iter_mut(&mut left) {
                         if updated.contains(entity) {
-                            if let Some(global_inverse) = mesh_global.global_matrix().try_inverse()
+                            if let Some(global_inverse) = mesh_global.matrix().try_inverse()
                             {
                                 if let Ok(skin) = <&Skin>::query().get(&right, joint_transform.skin)
                                 {
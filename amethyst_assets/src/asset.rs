@@ -25,6 +25,13 @@ pub trait Asset: Send + Sync + 'static {
 
     /// The `Data` type the asset can be created from.
     type Data: Send + Sync + 'static;
+
+    /// The memory used by this asset, accounted against the `AssetBudget` of its type.
+    ///
+    /// Assets returning `None`, the default, are not accounted.
+    fn size_in_bytes(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Defines a way to process asset's data into the asset. This allows
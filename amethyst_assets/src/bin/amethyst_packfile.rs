@@ -0,0 +1,9 @@
+//! Builds a packfile from asset directories, to ship an application without the asset daemon.
+
+use amethyst_assets::{build_packfile, PackfileArgs};
+use amethyst_error::Error;
+use structopt::StructOpt;
+
+fn main() -> Result<(), Error> {
+    build_packfile(PackfileArgs::from_args())
+}
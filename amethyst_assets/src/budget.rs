@@ -0,0 +1,282 @@
+//! Memory budgets per asset type, evicting the least recently used cached assets.
+
+use std::marker::PhantomData;
+
+use amethyst_core::{
+    dispatcher::System,
+    ecs::{systems::ParallelRunnable, SystemBuilder},
+    shrev::EventChannel,
+};
+use derivative::Derivative;
+use fnv::FnvHashMap;
+
+use crate::{
+    loader::{DefaultLoader, LoadHandle},
+    storage::AssetStorage,
+    Asset, AssetHandle, Handle,
+};
+
+/// Memory budget of the assets of type `A`, along with the cached assets it may evict.
+///
+/// Assets are normally unloaded as soon as their last handle is dropped. Cached assets are kept
+/// loaded by the budget, so they can be used again without reloading, until the memory used by
+/// the loaded assets of type `A` exceeds the budget. The least recently used cached assets that
+/// no one else holds a handle to are then evicted.
+///
+/// Assets loaded with `Loader::load_from_data`, and the other loads built on it, are unloaded by
+/// the budget itself when they are evicted, as they are otherwise never unloaded.
+///
+/// The memory of an asset is the one reported by `Asset::size_in_bytes`, or by
+/// `AssetStorage::set_size_in_bytes`. The budget is enforced by the `AssetBudgetSystem<A>`, which
+/// must be added to the dispatcher.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_assets::{Asset, AssetBudget, AssetBudgetSystem};
+/// use amethyst_core::ecs::{DispatcherBuilder, Resources};
+///
+/// struct Chunk {
+///     heights: Vec<f32>,
+/// }
+///
+/// impl Asset for Chunk {
+///     fn name() -> &'static str {
+///         "Chunk"
+///     }
+///     type Data = Vec<f32>;
+///
+///     fn size_in_bytes(&self) -> Option<usize> {
+///         Some(self.heights.len() * std::mem::size_of::<f32>())
+///     }
+/// }
+///
+/// let mut resources = Resources::default();
+/// resources.insert(AssetBudget::<Chunk>::new(256 * 1024 * 1024));
+/// let mut dispatcher = DispatcherBuilder::default();
+/// dispatcher.add_system(AssetBudgetSystem::<Chunk>::default());
+/// ```
+pub struct AssetBudget<A> {
+    budget_bytes: usize,
+    cached: FnvHashMap<LoadHandle, Handle<A>>,
+}
+
+impl<A> AssetBudget<A> {
+    /// Creates a budget of `budget_bytes` for the assets of type `A`.
+    #[must_use]
+    pub fn new(budget_bytes: usize) -> Self {
+        AssetBudget {
+            budget_bytes,
+            cached: FnvHashMap::default(),
+        }
+    }
+
+    /// The memory budget, in bytes.
+    #[must_use]
+    pub fn budget_bytes(&self) -> usize {
+        self.budget_bytes
+    }
+
+    /// Changes the memory budget, which takes effect the next time the budget is enforced.
+    pub fn set_budget_bytes(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+    }
+
+    /// Keeps the asset loaded after its other handles are dropped, until it is evicted.
+    pub fn cache(&mut self, handle: &Handle<A>) {
+        self.cached
+            .entry(handle.load_handle())
+            .or_insert_with(|| handle.clone());
+    }
+
+    /// Stops caching the asset, which is unloaded if no one else holds a handle to it.
+    pub fn uncache<H: AssetHandle>(&mut self, handle: &H) {
+        self.cached.remove(&handle.load_handle());
+    }
+
+    /// Returns true if the asset is cached.
+    #[must_use]
+    pub fn is_cached<H: AssetHandle>(&self, handle: &H) -> bool {
+        self.cached.contains_key(&handle.load_handle())
+    }
+
+    /// The number of cached assets.
+    #[must_use]
+    pub fn num_cached(&self) -> usize {
+        self.cached.len()
+    }
+}
+
+impl<A> std::fmt::Debug for AssetBudget<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssetBudget")
+            .field("budget_bytes", &self.budget_bytes)
+            .field("cached", &self.cached.len())
+            .finish()
+    }
+}
+
+/// Sent when the loaded assets of a type use more memory than their `AssetBudget`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetPressureEvent {
+    /// Name of the asset type, as returned by `Asset::name`.
+    pub asset_type: &'static str,
+    /// The memory budget of the asset type, in bytes.
+    pub budget_bytes: usize,
+    /// The memory used before evicting cached assets, in bytes.
+    pub used_bytes: usize,
+    /// The memory released by unloading the evicted assets, in bytes.
+    pub evicted_bytes: usize,
+    /// The number of evicted assets.
+    pub evicted: usize,
+}
+
+impl AssetPressureEvent {
+    /// Returns true if evicting all the cached assets that could be evicted was not enough to get
+    /// back within the budget.
+    #[must_use]
+    pub fn is_over_budget(&self) -> bool {
+        self.used_bytes - self.evicted_bytes > self.budget_bytes
+    }
+}
+
+/// An evictable asset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Candidate {
+    handle: LoadHandle,
+    ticks_since_use: u64,
+    size_in_bytes: usize,
+}
+
+/// Chooses the least recently used candidates to free at least `excess_bytes`.
+fn select_evictions(mut candidates: Vec<Candidate>, excess_bytes: usize) -> Vec<Candidate> {
+    candidates.sort_by(|a, b| b.ticks_since_use.cmp(&a.ticks_since_use));
+    let mut freed = 0;
+    candidates
+        .into_iter()
+        .take_while(|candidate| {
+            let needed = freed < excess_bytes;
+            freed += candidate.size_in_bytes;
+            needed
+        })
+        .collect()
+}
+
+/// Enforces the `AssetBudget<A>` resource, evicting cached assets and writing an
+/// `AssetPressureEvent` whenever the assets of type `A` use more memory than the budget.
+///
+/// The `AssetBudget<A>` resource must be inserted before the system runs. The
+/// `EventChannel<AssetPressureEvent>` is inserted by the `LoaderBundle`.
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct AssetBudgetSystem<A> {
+    _marker: PhantomData<A>,
+}
+
+impl<A> System for AssetBudgetSystem<A>
+where
+    A: Asset,
+{
+    fn build(self) -> Box<dyn ParallelRunnable> {
+        Box::new(
+            SystemBuilder::new(format!("Asset Budget: {}", A::name()))
+                .read_resource::<DefaultLoader>()
+                .write_resource::<AssetStorage<A>>()
+                .write_resource::<AssetBudget<A>>()
+                .write_resource::<EventChannel<AssetPressureEvent>>()
+                .build(|_, _, (loader, storage, budget, events), _| {
+                    storage.tick();
+                    let used_bytes = storage.memory_usage();
+                    if used_bytes <= budget.budget_bytes {
+                        return;
+                    }
+
+                    // Only the budget holds a handle to these assets, so they are unloaded when it
+                    // drops it.
+                    let candidates = budget
+                        .cached
+                        .keys()
+                        .filter(|handle| {
+                            storage.resolve(**handle).map_or(false, |handle| {
+                                matches!(loader.ref_count(handle), Some(refs) if refs <= 1)
+                            })
+                        })
+                        .filter_map(|handle| {
+                            Some(Candidate {
+                                handle: *handle,
+                                ticks_since_use: storage.ticks_since_use(*handle)?,
+                                size_in_bytes: storage.size_in_bytes(*handle)?,
+                            })
+                        })
+                        .collect();
+                    let evictions = select_evictions(candidates, used_bytes - budget.budget_bytes);
+                    for eviction in &evictions {
+                        budget.cached.remove(&eviction.handle);
+                        if let Some(handle) = storage.resolve(eviction.handle) {
+                            if loader.is_from_data(handle) {
+                                storage.unload(handle);
+                            }
+                        }
+                    }
+
+                    let event = AssetPressureEvent {
+                        asset_type: A::name(),
+                        budget_bytes: budget.budget_bytes,
+                        used_bytes,
+                        evicted_bytes: evictions.iter().map(|e| e.size_in_bytes).sum(),
+                        evicted: evictions.len(),
+                    };
+                    log::debug!("{:?}", event);
+                    events.single_write(event);
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(handle: u64, ticks_since_use: u64, size_in_bytes: usize) -> Candidate {
+        Candidate {
+            handle: LoadHandle(handle),
+            ticks_since_use,
+            size_in_bytes,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let candidates = vec![
+            candidate(1, 5, 100),
+            candidate(2, 50, 100),
+            candidate(3, 0, 100),
+            candidate(4, 20, 100),
+        ];
+
+        let evicted: Vec<_> = select_evictions(candidates.clone(), 150)
+            .iter()
+            .map(|c| c.handle)
+            .collect();
+        assert_eq!(evicted, vec![LoadHandle(2), LoadHandle(4)]);
+        assert!(select_evictions(candidates.clone(), 0).is_empty());
+        assert_eq!(select_evictions(candidates, 10_000).len(), 4);
+    }
+
+    #[test]
+    fn pressure_reports_remaining_excess() {
+        let event = AssetPressureEvent {
+            asset_type: "Texture",
+            budget_bytes: 100,
+            used_bytes: 300,
+            evicted_bytes: 150,
+            evicted: 2,
+        };
+        assert!(event.is_over_budget());
+        assert!(!AssetPressureEvent {
+            evicted_bytes: 200,
+            ..event
+        }
+        .is_over_budget());
+    }
+}
@@ -0,0 +1,290 @@
+//! Build steps, transforming asset data after it is imported and before it is stored.
+
+use std::any::Any;
+
+use amethyst_error::{format_err, Error, ResultExt};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use type_uuid::TypeUuid;
+
+/// A transformation of imported asset data, e.g. generating mipmaps, compressing textures into a
+/// GPU-ready format or deduplicating mesh vertices.
+///
+/// Build steps run when the `AssetDaemon` imports an asset, in the order they are listed in the
+/// `build_steps` of the importer options in the `.meta` file of the asset. Their output is what
+/// is stored in the asset database, so the steps only run again when the source file or the
+/// `.meta` file changes.
+///
+/// Steps are registered with the [`register_build_step!`](crate::register_build_step) macro.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_assets::{register_build_step, BuildStep};
+/// use amethyst_error::Error;
+/// use serde::Deserialize;
+///
+/// #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+/// pub struct HeightMap(pub Vec<f32>);
+///
+/// #[derive(Default, Deserialize)]
+/// pub struct ScaleSettings {
+///     factor: f32,
+/// }
+///
+/// /// Scales the heights of a height map.
+/// #[derive(Default)]
+/// pub struct Scale;
+///
+/// impl BuildStep for Scale {
+///     const NAME: &'static str = "scale";
+///     type Data = HeightMap;
+///     type Settings = ScaleSettings;
+///
+///     fn build(&self, data: HeightMap, settings: &ScaleSettings) -> Result<HeightMap, Error> {
+///         Ok(HeightMap(data.0.iter().map(|h| h * settings.factor).collect()))
+///     }
+/// }
+///
+/// register_build_step!(Scale);
+/// ```
+///
+/// The step is then enabled in the `.meta` file of a height map:
+///
+/// ```ron
+/// importer_options: (
+///     format: (),
+///     build_steps: [(step: "scale", settings: Some((factor: 2.0)))],
+/// ),
+/// ```
+pub trait BuildStep: Default + Send + Sync + 'static {
+    /// Name of the step in `.meta` files.
+    const NAME: &'static str;
+
+    /// The asset data the step transforms.
+    type Data: 'static;
+
+    /// Settings of the step, read from the `.meta` file. Missing settings are the default ones.
+    type Settings: DeserializeOwned + Default;
+
+    /// Transforms the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data can't be transformed, which fails the import of the asset.
+    fn build(&self, data: Self::Data, settings: &Self::Settings) -> Result<Self::Data, Error>;
+}
+
+/// A build step registered with [`register_build_step!`](crate::register_build_step).
+#[derive(Debug)]
+pub struct BuildStepRegistration {
+    /// Name of the step in `.meta` files.
+    pub name: &'static str,
+    /// Runs the step on data boxed in an `Option`.
+    pub run: fn(&mut dyn Any, Option<&ron::Value>) -> Result<(), Error>,
+}
+inventory::collect!(BuildStepRegistration);
+
+/// Runs `S` on `data`, which must be an `Option<S::Data>`.
+///
+/// This function is not intended to be called directly. Use the `register_build_step!` macro
+/// instead.
+///
+/// # Errors
+///
+/// Returns an error if the data or the settings have the wrong type, or if the step fails.
+pub fn run_build_step<S: BuildStep>(
+    data: &mut dyn Any,
+    settings: Option<&ron::Value>,
+) -> Result<(), Error> {
+    let data = data
+        .downcast_mut::<Option<S::Data>>()
+        .ok_or_else(|| format_err!("Build step {:?} can't transform this asset type", S::NAME))?;
+    let settings = match settings {
+        Some(settings) => settings
+            .clone()
+            .into_rust::<S::Settings>()
+            .with_context(|_| format_err!("Invalid settings for build step {:?}", S::NAME))?,
+        None => S::Settings::default(),
+    };
+    let input = data.take().expect("build step data already taken");
+    *data = Some(S::default().build(input, &settings)?);
+    Ok(())
+}
+
+/// A build step to run and its settings, as written in `.meta` files.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildStepConfig {
+    /// Name of the step.
+    pub step: String,
+    /// Settings of the step, the default settings if `None`.
+    #[serde(default)]
+    pub settings: Option<ron::Value>,
+}
+
+/// Options of the importers of `Format`s: the options of the format, followed by the build steps
+/// to run on the imported data.
+///
+/// `.meta` files written before build steps existed hold the options of the format alone, they
+/// are read as options without build steps.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ImportOptions<T> {
+    /// Options of the format.
+    pub format: T,
+    /// The build steps to run on the imported data, in order.
+    #[serde(default)]
+    pub build_steps: Vec<BuildStepConfig>,
+}
+
+impl<T> From<T> for ImportOptions<T> {
+    fn from(format: T) -> Self {
+        ImportOptions {
+            format,
+            build_steps: Vec::new(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ImportOptions<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Shape<T> {
+            WithBuildSteps {
+                format: T,
+                #[serde(default)]
+                build_steps: Vec<BuildStepConfig>,
+            },
+            FormatOnly(T),
+        }
+
+        Ok(match Shape::deserialize(deserializer)? {
+            Shape::WithBuildSteps {
+                format,
+                build_steps,
+            } => ImportOptions {
+                format,
+                build_steps,
+            },
+            Shape::FormatOnly(format) => format.into(),
+        })
+    }
+}
+
+impl<T: TypeUuid> TypeUuid for ImportOptions<T> {
+    const UUID: type_uuid::Bytes = T::UUID;
+}
+
+/// Runs the configured build steps on `data`.
+///
+/// Importers call this on the data of each asset they import. `SimpleImporter` runs the
+/// `build_steps` of its `ImportOptions`, the prefab and glTF importers the build steps of their
+/// own options.
+///
+/// # Errors
+///
+/// Returns an error if a step isn't registered, doesn't apply to `D` or fails.
+pub fn run_build_steps<D: 'static>(data: D, steps: &[BuildStepConfig]) -> Result<D, Error> {
+    let mut data = Some(data);
+    for config in steps {
+        let registration = inventory::iter::<BuildStepRegistration>
+            .into_iter()
+            .find(|registration| registration.name == config.step)
+            .ok_or_else(|| format_err!("Build step {:?} is not registered", config.step))?;
+        (registration.run)(&mut data, config.settings.as_ref())?;
+    }
+    Ok(data.expect("build step lost the data"))
+}
+
+/// Registers a [`BuildStep`], so it can be used in `.meta` files.
+///
+/// # Parameters
+///
+/// * `step`: Type that implements the `BuildStep` trait.
+#[macro_export]
+macro_rules! register_build_step {
+    ($step:ty) => {
+        $crate::register_build_step!(amethyst_assets; $step);
+    };
+    ($krate:ident; $step:ty) => {
+        $crate::inventory::submit!{
+            #![crate = $krate]
+            $crate::BuildStepRegistration {
+                name: <$step as $crate::BuildStep>::NAME,
+                run: $crate::run_build_step::<$step>,
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Double;
+
+    #[derive(Default, Deserialize)]
+    struct DoubleSettings {
+        #[serde(default)]
+        times: u32,
+    }
+
+    impl BuildStep for Double {
+        const NAME: &'static str = "test_double";
+        type Data = Vec<u32>;
+        type Settings = DoubleSettings;
+
+        fn build(&self, data: Vec<u32>, settings: &DoubleSettings) -> Result<Vec<u32>, Error> {
+            let factor = 2_u32.pow(settings.times.max(1));
+            Ok(data.into_iter().map(|value| value * factor).collect())
+        }
+    }
+
+    inventory::submit! {
+        BuildStepRegistration {
+            name: Double::NAME,
+            run: run_build_step::<Double>,
+        }
+    }
+
+    fn config(settings: Option<&str>) -> BuildStepConfig {
+        BuildStepConfig {
+            step: "test_double".to_string(),
+            settings: settings.map(|settings| ron::from_str(settings).unwrap()),
+        }
+    }
+
+    #[test]
+    fn runs_steps_in_order() {
+        let steps = vec![config(None), config(Some("(times: 2)"))];
+        assert_eq!(run_build_steps(vec![1, 2], &steps).unwrap(), vec![8, 16]);
+    }
+
+    #[test]
+    fn rejects_unknown_steps_and_other_data() {
+        let unknown = BuildStepConfig {
+            step: "missing".to_string(),
+            settings: None,
+        };
+        assert!(run_build_steps(vec![1_u32], &[unknown]).is_err());
+        assert!(run_build_steps(String::from("text"), &[config(None)]).is_err());
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ScaleOptions {
+        scale: u32,
+    }
+
+    #[test]
+    fn reads_import_options_with_and_without_build_steps() {
+        let options: ImportOptions<ScaleOptions> =
+            ron::from_str("(format: (scale: 2), build_steps: [(step: \"test_double\")])").unwrap();
+        assert_eq!(options.format, ScaleOptions { scale: 2 });
+        assert_eq!(options.build_steps, vec![config(None)]);
+
+        // The options of `.meta` files written before build steps existed.
+        let options: ImportOptions<ScaleOptions> = ron::from_str("(scale: 3)").unwrap();
+        assert_eq!(options.format, ScaleOptions { scale: 3 });
+        assert!(options.build_steps.is_empty());
+    }
+}
@@ -1,9 +1,14 @@
 use std::default::Default;
 
-use amethyst_core::ecs::{DispatcherBuilder, Resources, SystemBundle, World};
+use amethyst_core::{
+    ecs::{DispatcherBuilder, Resources, SystemBundle, World},
+    shrev::EventChannel,
+};
 use amethyst_error::Error;
 
-use crate::{prefab::ComponentRegistryBuilder, DefaultLoader, Loader};
+use crate::{
+    prefab::ComponentRegistryBuilder, AssetPressureEvent, DefaultLoader, Loader, LoaderMode,
+};
 
 fn asset_loading_tick(_: &mut World, resources: &mut Resources) {
     let mut loader = resources
@@ -16,6 +21,9 @@ fn asset_loading_tick(_: &mut World, resources: &mut Resources) {
 }
 
 /// Bundle that initializes Loader as well as related processing systems and resources
+///
+/// The loader reads assets according to the `LoaderMode` resource, from the asset daemon if there
+/// is none.
 pub struct LoaderBundle;
 
 impl SystemBundle for LoaderBundle {
@@ -29,10 +37,15 @@ impl SystemBundle for LoaderBundle {
             .auto_register_components()
             .build();
         resources.insert(component_registry);
-        let mut loader = DefaultLoader::default();
+        let mode = resources
+            .get::<LoaderMode>()
+            .map(|mode| mode.clone())
+            .unwrap_or_default();
+        let mut loader = mode.create_loader()?;
         loader.init_world(resources);
         loader.init_dispatcher(builder);
         resources.insert(loader);
+        resources.insert(EventChannel::<AssetPressureEvent>::default());
 
         builder.add_thread_local_fn(asset_loading_tick);
         builder.add_thread_local_fn(crate::prefab::system::prefab_spawning_tick);
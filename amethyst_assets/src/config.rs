@@ -0,0 +1,325 @@
+//! Config files imported as assets, kept in sync with a resource and reloaded when they change.
+
+use std::{
+    ffi::OsStr,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use amethyst_config::{Config, ConfigError, ConfigFormat};
+use amethyst_core::{
+    dispatcher::System,
+    ecs::{
+        systems::ParallelRunnable, DispatcherBuilder, Resources, SystemBuilder, SystemBundle, World,
+    },
+    shrev::{EventChannel, ReaderId},
+};
+use amethyst_error::Error;
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+
+use crate::{
+    self as amethyst_assets, Asset, AssetEvent, AssetEventKind, AssetProcessorSystem, AssetStorage,
+    DefaultLoader, Format, Handle, Loader,
+};
+
+/// The bytes of a config file, imported from `.config` files by the `AssetDaemon`.
+///
+/// Config files use their own extension because `.ron` files are imported as serialized assets,
+/// prefixed with the UUID of their type. The format of a file is given by the extension before
+/// `.config`: `difficulty.ron.config` and `difficulty.config` are RON, `difficulty.json.config` is
+/// JSON and `difficulty.bin.config` is bincode, with the `json` and `binary` features
+/// respectively.
+///
+/// The bytes are imported as they are, they are only parsed into a config type by
+/// `ConfigFile::read`.
+#[derive(Clone, Debug, Serialize, Deserialize, TypeUuid)]
+#[uuid = "34a03918-6bb4-4c68-8540-763291f23ca0"]
+pub struct ConfigFile(pub Vec<u8>);
+
+impl Asset for ConfigFile {
+    fn name() -> &'static str {
+        "ConfigFile"
+    }
+    type Data = Self;
+}
+
+crate::register_asset_type!(ConfigFile => ConfigFile; AssetProcessorSystem<ConfigFile>);
+
+impl ConfigFile {
+    /// Reads the config `C` from the file at `path`, in the format given by the extension before
+    /// `.config`. RON parse errors are reported as a `ConfigError::FileParser` for `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format isn't supported or the bytes are not a valid `C`.
+    pub fn read<C: Config>(&self, path: &str) -> Result<C, ConfigError> {
+        let path = Path::new(path);
+        C::load_bytes(format_path(path), &self.0).map_err(|err| {
+            match err {
+                ConfigError::FileParser(err, _) => ConfigError::FileParser(err, path.to_owned()),
+                _ => err,
+            }
+        })
+    }
+}
+
+// The path of a config file without its `.config` extension, which gives the format of the file.
+fn format_path(path: &Path) -> PathBuf {
+    let path = if path.extension() == Some(OsStr::new("config")) {
+        path.with_extension("")
+    } else {
+        path.to_owned()
+    };
+    if path.extension().is_some() {
+        path
+    } else {
+        path.with_extension("ron")
+    }
+}
+
+/// Imports the `ConfigFile`s of `.config` files.
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, TypeUuid)]
+#[uuid = "3c4117d2-084f-4be3-9626-1d44a526a2f6"]
+pub struct ConfigFileFormat;
+
+impl Format<ConfigFile> for ConfigFileFormat {
+    fn name(&self) -> &'static str {
+        "CONFIG"
+    }
+
+    fn import_simple(&self, bytes: Vec<u8>) -> Result<ConfigFile, Error> {
+        // Files are parsed when read, so that errors are reported by the `ConfigAsset`.
+        Ok(ConfigFile(bytes))
+    }
+}
+
+crate::register_importer!(".config", ConfigFileFormat);
+
+/// Resource tracking the config file the resource `C` is read from.
+///
+/// The `ConfigReloadSystem<C>` reads the file into the resource `C` each time the `AssetDaemon`
+/// imports it, so edits to the file are applied while the game runs. A file that fails to parse
+/// is reported by `last_error`, as a `ConfigError::FileParser` for RON files, the resource keeping
+/// its previous value.
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct ConfigAsset<C> {
+    path: String,
+    handle: Handle<ConfigFile>,
+    error: Option<ConfigError>,
+    #[derivative(Debug = "ignore")]
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C> ConfigAsset<C> {
+    /// Path of the config file, relative to the asset directories.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Handle of the config file.
+    #[must_use]
+    pub fn handle(&self) -> &Handle<ConfigFile> {
+        &self.handle
+    }
+
+    /// The error of the last attempt to read the file, if it failed.
+    #[must_use]
+    pub fn last_error(&self) -> Option<&ConfigError> {
+        self.error.as_ref()
+    }
+}
+
+impl<C: Config> ConfigAsset<C> {
+    /// Reads the config from a version of the file.
+    ///
+    /// Returns `None` if the file couldn't be read, in which case the error is available from
+    /// `last_error`.
+    pub fn read(&mut self, file: &ConfigFile) -> Option<C> {
+        match file.read(&self.path) {
+            Ok(config) => {
+                self.error = None;
+                Some(config)
+            }
+            Err(err) => {
+                log::error!("Failed to read config {}: {}", self.path, err);
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+}
+
+/// Swaps the config read by the `ConfigAsset<C>` into the resource `C` when its file is loaded
+/// or reloaded.
+#[derive(Debug)]
+pub struct ConfigReloadSystem<C> {
+    reader: ReaderId<AssetEvent<ConfigFile>>,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C> ConfigReloadSystem<C> {
+    /// Creates the system, reading the `AssetEvent`s of config files with `reader`.
+    #[must_use]
+    pub fn new(reader: ReaderId<AssetEvent<ConfigFile>>) -> Self {
+        ConfigReloadSystem {
+            reader,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C> System for ConfigReloadSystem<C>
+where
+    C: Config + Send + Sync + 'static,
+{
+    fn build(mut self) -> Box<dyn ParallelRunnable> {
+        Box::new(
+            SystemBuilder::new(format!("Config Reload: {}", std::any::type_name::<C>()))
+                .read_resource::<EventChannel<AssetEvent<ConfigFile>>>()
+                .read_resource::<AssetStorage<ConfigFile>>()
+                .write_resource::<ConfigAsset<C>>()
+                .write_resource::<C>()
+                .build(move |_, _, (events, storage, asset, config), _| {
+                    let changed = events.read(&mut self.reader).any(|event| {
+                        event.is_for(&asset.handle, storage)
+                            && matches!(
+                                event.kind,
+                                AssetEventKind::Loaded { .. } | AssetEventKind::Reloaded { .. }
+                            )
+                    });
+                    if !changed {
+                        return;
+                    }
+                    let file = storage.get(&asset.handle).cloned();
+                    if let Some(reloaded) = file.and_then(|file| asset.read(&file)) {
+                        log::info!("Loaded config {}", asset.path());
+                        **config = reloaded;
+                    }
+                }),
+        )
+    }
+}
+
+/// Loads the config `C` from a `.config` file of the asset directories into a resource, and adds
+/// the `ConfigReloadSystem<C>` keeping it up to date when the file changes.
+///
+/// The resource is the default `C` until the file is loaded. The bundle must be added after the
+/// `LoaderBundle`.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst_assets::{ConfigAssetBundle, LoaderBundle};
+/// use amethyst_core::ecs::DispatcherBuilder;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Deserialize, Serialize)]
+/// struct Difficulty {
+///     enemy_speed: f32,
+/// }
+///
+/// let mut dispatcher = DispatcherBuilder::default();
+/// dispatcher
+///     .add_bundle(LoaderBundle)
+///     .add_bundle(ConfigAssetBundle::<Difficulty>::new("config/difficulty.config"));
+/// ```
+#[derive(Debug)]
+pub struct ConfigAssetBundle<C> {
+    path: String,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C> ConfigAssetBundle<C> {
+    /// Loads the config from `path`, relative to the asset directories.
+    pub fn new<P: Into<String>>(path: P) -> Self {
+        ConfigAssetBundle {
+            path: path.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C> SystemBundle for ConfigAssetBundle<C>
+where
+    C: Config + Default + Send + Sync + 'static,
+{
+    fn load(
+        &mut self,
+        _: &mut World,
+        resources: &mut Resources,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), Error> {
+        let handle = resources
+            .get::<DefaultLoader>()
+            .ok_or_else(|| Error::from_string("ConfigAssetBundle requires the LoaderBundle"))?
+            .load(&self.path);
+        let reader = resources
+            .get_mut::<EventChannel<AssetEvent<ConfigFile>>>()
+            .ok_or_else(|| Error::from_string("ConfigAssetBundle requires the LoaderBundle"))?
+            .register_reader();
+        resources.insert(ConfigAsset::<C> {
+            path: self.path.clone(),
+            handle,
+            error: None,
+            _marker: PhantomData,
+        });
+        resources.get_or_insert_with(C::default);
+        builder.add_system(ConfigReloadSystem::<C>::new(reader));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Difficulty {
+        enemy_speed: u32,
+    }
+
+    #[test]
+    fn imports_files_without_parsing_them() {
+        let file = ConfigFileFormat.import_simple(b"(enemy_speed: ".to_vec());
+        assert_eq!(file.unwrap().0, b"(enemy_speed: ".to_vec());
+    }
+
+    #[test]
+    fn reports_parse_errors_with_the_path() {
+        let file = ConfigFile(b"(enemy_speed: 2)".to_vec());
+        let read = file.read::<Difficulty>("config/difficulty.config");
+        assert_eq!(read.unwrap(), Difficulty { enemy_speed: 2 });
+
+        let file = ConfigFile(b"(enemy_speed: \"fast\")".to_vec());
+        match file.read::<Difficulty>("config/difficulty.config") {
+            Err(ConfigError::FileParser(_, path)) => {
+                assert_eq!(path, PathBuf::from("config/difficulty.config"))
+            }
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn reads_the_format_before_the_config_extension() {
+        let file = ConfigFile(b"(enemy_speed: 3)".to_vec());
+        let read = file.read::<Difficulty>("config/difficulty.ron.config");
+        assert_eq!(read.unwrap(), Difficulty { enemy_speed: 3 });
+
+        assert!(matches!(
+            file.read::<Difficulty>("config/difficulty.txt.config"),
+            Err(ConfigError::Extension(_))
+        ));
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn reads_binary_config_files() {
+        let file = ConfigFile(bincode::serialize(&Difficulty { enemy_speed: 4 }).unwrap());
+        let read = file.read::<Difficulty>("config/difficulty.bin.config");
+        assert_eq!(read.unwrap(), Difficulty { enemy_speed: 4 });
+    }
+}
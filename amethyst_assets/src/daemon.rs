@@ -46,9 +46,13 @@ impl From<AssetDaemonArgs> for AssetDaemonOpt {
     }
 }
 
+/// Options of the asset daemon.
 pub struct AssetDaemonOpt {
+    /// Path to the asset metadata database directory.
     pub db_dir: PathBuf,
+    /// Socket address for the daemon to listen for connections.
     pub address: SocketAddr,
+    /// Directories to watch for assets.
     pub asset_dirs: Vec<PathBuf>,
 }
 
@@ -94,11 +98,14 @@ impl AssetDaemon {
     /// * `asset_dirs` - The directories to load assets from.
     #[must_use]
     pub fn new(asset_dirs: Vec<PathBuf>) -> Self {
-        let opt = AssetDaemonOpt {
-            db_dir: ".assets_db".into(),
-            address: "127.0.0.1:9999".parse().unwrap(),
+        Self::with_opt(AssetDaemonOpt {
             asset_dirs,
-        };
+            ..AssetDaemonOpt::default()
+        })
+    }
+    /// Returns an `AssetDaemon` initialized with the given options.
+    #[must_use]
+    pub fn with_opt(opt: AssetDaemonOpt) -> Self {
         AssetDaemon {
             state: AssetDaemonState::Initialized(InitializedDaemon { opt }),
         }
@@ -10,6 +10,8 @@ pub enum Error {
     Source,
     #[error(display = "Format {:?} could not load asset", _0)]
     Format(&'static str),
+    #[error(display = "No format is registered for extension {:?}", _0)]
+    NoFormat(String),
     #[error(display = "Asset was loaded but no handle to it was saved.")]
     UnusedHandle,
 }
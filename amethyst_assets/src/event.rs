@@ -0,0 +1,136 @@
+//! Events of the assets of a type being loaded, reloaded, failing or being unloaded.
+
+use std::marker::PhantomData;
+
+use derivative::Derivative;
+use distill::loader::{handle::AssetHandle, LoadHandle};
+
+use crate::storage::AssetStorage;
+
+/// What happened to an asset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetEventKind {
+    /// The asset was loaded for the first time.
+    Loaded {
+        /// Version of the asset.
+        version: u32,
+    },
+    /// A new version of the asset replaced the previous one, e.g. after its source file changed.
+    Reloaded {
+        /// Version of the asset.
+        version: u32,
+    },
+    /// The asset data could not be deserialized or processed into an asset.
+    ///
+    /// A previous version of the asset stays loaded.
+    Failed {
+        /// Description of the error.
+        error: String,
+    },
+    /// The asset was removed from the storage.
+    Unloaded,
+}
+
+/// Written to the `EventChannel<AssetEvent<A>>` when an asset of type `A` is loaded, reloaded,
+/// fails to load or is unloaded.
+///
+/// The channel is created along with the `AssetStorage<A>`, and the events are published by the
+/// `AssetProcessorSystem<A>`. Systems can use them to rebuild data derived from an asset only when
+/// the asset changes, instead of polling `AssetStorage::get_version`.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_assets::{AssetEvent, AssetEventKind, AssetStorage, Handle};
+/// use amethyst_core::shrev::{EventChannel, ReaderId};
+///
+/// struct NavMesh;
+/// struct TileMap;
+///
+/// fn rebuild_nav_mesh(
+///     events: &EventChannel<AssetEvent<TileMap>>,
+///     reader: &mut ReaderId<AssetEvent<TileMap>>,
+///     storage: &AssetStorage<TileMap>,
+///     map: &Handle<TileMap>,
+/// ) -> Option<NavMesh> {
+///     let changed = events.read(reader).any(|event| {
+///         event.is_for(map, storage)
+///             && matches!(
+///                 event.kind,
+///                 AssetEventKind::Loaded { .. } | AssetEventKind::Reloaded { .. }
+///             )
+///     });
+///     if changed {
+///         storage.get(map).map(|_| NavMesh)
+///     } else {
+///         None
+///     }
+/// }
+/// ```
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct AssetEvent<A> {
+    /// Direct load handle of the asset, as used by the `AssetStorage`.
+    pub handle: LoadHandle,
+    /// What happened to the asset.
+    pub kind: AssetEventKind,
+    #[derivative(Debug = "ignore")]
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A> AssetEvent<A> {
+    pub(crate) fn new(handle: LoadHandle, kind: AssetEventKind) -> Self {
+        AssetEvent {
+            handle,
+            kind,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns true if the event is about the asset of `handle`.
+    ///
+    /// Handles of assets loaded by path are indirect, so they are resolved through the storage.
+    #[must_use]
+    pub fn is_for<H: AssetHandle>(&self, handle: &H, storage: &AssetStorage<A>) -> bool {
+        storage.resolve(handle.load_handle()) == Some(self.handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultLoader;
+
+    #[test]
+    fn storage_records_the_asset_lifecycle() {
+        let handle = LoadHandle(1);
+        let loader = DefaultLoader::default();
+        let mut storage = AssetStorage::<u32>::new(loader.indirection_table.clone());
+
+        storage.update_asset(handle, 1, 0);
+        storage.commit_asset(handle, 0);
+        storage.update_asset(handle, 2, 1);
+        storage.commit_asset(handle, 1);
+        storage.failed(handle, "corrupted".to_string());
+        storage.remove_asset(handle, 1);
+
+        let kinds: Vec<_> = storage.drain_events().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AssetEventKind::Loaded { version: 0 },
+                AssetEventKind::Reloaded { version: 1 },
+                AssetEventKind::Failed {
+                    error: "corrupted".to_string()
+                },
+                AssetEventKind::Unloaded,
+            ]
+        );
+        assert_eq!(storage.drain_events().count(), 0);
+    }
+}
@@ -0,0 +1,386 @@
+//! Dependency graph of the assets loaded by the `DefaultLoader`, for load progress reporting.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use distill::core::AssetRef;
+use fnv::{FnvHashMap, FnvHashSet};
+
+use crate::loader::{AssetUuid, LoadHandle, LoadStatus};
+
+/// Load state of an asset of a `LoadReport`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetLoadState {
+    /// The asset is being fetched or processed.
+    Pending,
+    /// The asset is loaded.
+    Loaded,
+    /// The asset doesn't exist or failed to load.
+    Failed,
+}
+
+impl From<LoadStatus> for AssetLoadState {
+    fn from(status: LoadStatus) -> Self {
+        match status {
+            LoadStatus::Loaded => AssetLoadState::Loaded,
+            LoadStatus::DoesNotExist | LoadStatus::Error(_) => AssetLoadState::Failed,
+            LoadStatus::NotRequested
+            | LoadStatus::Unresolved
+            | LoadStatus::Loading
+            | LoadStatus::Unloading => AssetLoadState::Pending,
+        }
+    }
+}
+
+/// An asset of a `LoadReport`.
+#[derive(Clone, Debug)]
+pub struct AssetLoadNode {
+    /// Handle of the asset.
+    pub handle: LoadHandle,
+    /// Load state of the asset.
+    pub state: AssetLoadState,
+    /// Size of the asset data, once it was received.
+    pub size_in_bytes: Option<usize>,
+    /// Time from the request of the asset to its commit in the storage, once committed.
+    ///
+    /// Dependencies are usually requested by the loader itself, so their timing starts when the
+    /// `DefaultLoader` first sees them.
+    pub load_time: Option<Duration>,
+    /// Handles of the assets this asset needs to be loaded, the `load_deps` of its artifact, once
+    /// the loader received its metadata.
+    pub dependencies: Vec<LoadHandle>,
+}
+
+/// Load state of an asset and of every asset it depends on, e.g. the textures of a material or
+/// the meshes of a glTF scene, created with `DefaultLoader::load_report`.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst_assets::{DefaultLoader, Handle, Loader};
+///
+/// # fn report(loader: &DefaultLoader, handle: &Handle<()>) {
+/// let report = loader.load_report(handle);
+/// println!(
+///     "{:.0}% of {} bytes loaded",
+///     report.progress() * 100.0,
+///     report.total_bytes()
+/// );
+/// for failed in report.failed() {
+///     println!("{:?} failed", failed.handle);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LoadReport {
+    // Breadth first from the root.
+    nodes: Vec<AssetLoadNode>,
+}
+
+impl LoadReport {
+    /// The asset the report was created for.
+    #[must_use]
+    pub fn root(&self) -> &AssetLoadNode {
+        &self.nodes[0]
+    }
+
+    /// The root and all of its dependencies, each once, breadth first from the root.
+    #[must_use]
+    pub fn nodes(&self) -> &[AssetLoadNode] {
+        &self.nodes
+    }
+
+    /// Returns the node of the asset with the given handle, if it is part of the report.
+    #[must_use]
+    pub fn get(&self, handle: LoadHandle) -> Option<&AssetLoadNode> {
+        self.nodes.iter().find(|node| node.handle == handle)
+    }
+
+    /// The assets that are still loading.
+    pub fn pending(&self) -> impl Iterator<Item = &AssetLoadNode> {
+        self.in_state(AssetLoadState::Pending)
+    }
+
+    /// The assets that are loaded.
+    pub fn loaded(&self) -> impl Iterator<Item = &AssetLoadNode> {
+        self.in_state(AssetLoadState::Loaded)
+    }
+
+    /// The assets that failed to load.
+    pub fn failed(&self) -> impl Iterator<Item = &AssetLoadNode> {
+        self.in_state(AssetLoadState::Failed)
+    }
+
+    fn in_state(&self, state: AssetLoadState) -> impl Iterator<Item = &AssetLoadNode> {
+        self.nodes.iter().filter(move |node| node.state == state)
+    }
+
+    /// The fraction of the assets which are loaded, between 0 and 1.
+    ///
+    /// Dependencies are only known once the metadata of the asset needing them is received, so
+    /// the fraction may go down while loading.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn progress(&self) -> f32 {
+        self.loaded().count() as f32 / self.nodes.len() as f32
+    }
+
+    /// Returns true if every asset is loaded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.nodes
+            .iter()
+            .all(|node| node.state == AssetLoadState::Loaded)
+    }
+
+    /// The total size of the asset data received so far.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter_map(|node| node.size_in_bytes)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+struct LoadRecord {
+    requested: Instant,
+    committed: Option<Instant>,
+    // Latest version received, the older ones are freed when it replaces them on hot reload.
+    version: Option<u32>,
+    size_in_bytes: Option<usize>,
+}
+
+impl LoadRecord {
+    fn new() -> Self {
+        LoadRecord {
+            requested: Instant::now(),
+            committed: None,
+            version: None,
+            size_in_bytes: None,
+        }
+    }
+}
+
+/// How the `DefaultLoader` identifies its assets, provided by the distill loader.
+pub(crate) trait LoadLookup {
+    /// The load status of an asset.
+    fn status(&self, handle: LoadHandle) -> LoadStatus;
+
+    /// The asset of a handle, if the handle is known.
+    fn asset_id(&self, handle: LoadHandle) -> Option<AssetUuid>;
+
+    /// The handle of an asset, if it is requested.
+    fn load_handle(&self, id: AssetUuid) -> Option<LoadHandle>;
+}
+
+/// What the `DefaultLoader` has seen of the loads of its assets.
+#[derive(Debug, Default)]
+pub(crate) struct LoadGraph {
+    records: FnvHashMap<LoadHandle, LoadRecord>,
+    // The `load_deps` of the artifacts seen by the loader. They describe the assets rather than
+    // their loads, so they are kept once the assets are unloaded, and replaced on hot reload.
+    load_deps: FnvHashMap<AssetUuid, Vec<AssetUuid>>,
+}
+
+impl LoadGraph {
+    /// Records the request of an asset, unless it is already known.
+    pub(crate) fn requested(&mut self, handle: LoadHandle) {
+        self.records.entry(handle).or_insert_with(LoadRecord::new);
+    }
+
+    /// Records the data of a version of an asset.
+    pub(crate) fn received(&mut self, handle: LoadHandle, version: u32, size_in_bytes: usize) {
+        let record = self.records.entry(handle).or_insert_with(LoadRecord::new);
+        record.version = Some(version);
+        record.size_in_bytes = Some(size_in_bytes);
+    }
+
+    /// Records the `load_deps` of the artifact of an asset resolved by the loader. Dependencies
+    /// given by path are resolved on their own, so only the ones given by UUID are kept.
+    pub(crate) fn resolved(&mut self, id: AssetUuid, load_deps: &[AssetRef]) {
+        let load_deps = load_deps
+            .iter()
+            .filter_map(|dependency| match dependency {
+                AssetRef::Uuid(id) => Some(*id),
+                AssetRef::Path(_) => None,
+            })
+            .collect();
+        self.load_deps.insert(id, load_deps);
+    }
+
+    /// Records the commit of a version of an asset.
+    pub(crate) fn committed(&mut self, handle: LoadHandle) {
+        if let Some(record) = self.records.get_mut(&handle) {
+            record.committed.get_or_insert_with(Instant::now);
+        }
+    }
+
+    /// Forgets an asset once its last handle is dropped.
+    pub(crate) fn released(&mut self, handle: LoadHandle) {
+        self.records.remove(&handle);
+    }
+
+    /// Forgets the assets which failed to load, as they are never freed. Their failure is still
+    /// reported while they have handles.
+    pub(crate) fn forget_failed(&mut self, lookup: &dyn LoadLookup) {
+        self.records.retain(|handle, record| {
+            record.committed.is_some()
+                || AssetLoadState::from(lookup.status(*handle)) != AssetLoadState::Failed
+        });
+    }
+
+    /// Forgets an asset once its latest version is freed, which means it was unloaded.
+    ///
+    /// Freeing an older version only means it was replaced by a hot reload.
+    pub(crate) fn freed(&mut self, handle: LoadHandle, version: u32) {
+        let replaced = self
+            .records
+            .get(&handle)
+            .and_then(|record| record.version)
+            .map_or(false, |latest| latest > version);
+        if !replaced {
+            self.records.remove(&handle);
+        }
+    }
+
+    /// Creates the report of `root` and its dependencies.
+    pub(crate) fn report(&self, root: LoadHandle, lookup: &dyn LoadLookup) -> LoadReport {
+        let mut nodes = Vec::new();
+        let mut visited = FnvHashSet::default();
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        visited.insert(root);
+
+        while let Some(handle) = queue.pop_front() {
+            let record = self.records.get(&handle);
+            let dependencies: Vec<_> = lookup
+                .asset_id(handle)
+                .and_then(|id| self.load_deps.get(&id))
+                .map_or_else(Vec::new, |load_deps| {
+                    load_deps
+                        .iter()
+                        .filter_map(|id| lookup.load_handle(*id))
+                        .collect()
+                });
+            for dependency in &dependencies {
+                if visited.insert(*dependency) {
+                    queue.push_back(*dependency);
+                }
+            }
+            nodes.push(AssetLoadNode {
+                handle,
+                state: lookup.status(handle).into(),
+                size_in_bytes: record.and_then(|r| r.size_in_bytes),
+                load_time: record.and_then(|r| Some(r.committed? - r.requested)),
+                dependencies,
+            });
+        }
+
+        LoadReport { nodes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Asset `n` has the handle `n`, in the state given by `states` or pending.
+    struct Lookup(Vec<(LoadHandle, AssetLoadState)>);
+
+    impl LoadLookup for Lookup {
+        fn status(&self, handle: LoadHandle) -> LoadStatus {
+            match self.0.iter().find(|(h, _)| *h == handle) {
+                Some((_, AssetLoadState::Loaded)) => LoadStatus::Loaded,
+                Some((_, AssetLoadState::Failed)) => LoadStatus::DoesNotExist,
+                Some((_, AssetLoadState::Pending)) | None => LoadStatus::Loading,
+            }
+        }
+
+        fn asset_id(&self, handle: LoadHandle) -> Option<AssetUuid> {
+            Some(id(handle.0))
+        }
+
+        fn load_handle(&self, id: AssetUuid) -> Option<LoadHandle> {
+            let mut n = [0; 8];
+            n.copy_from_slice(&id.0[..8]);
+            Some(LoadHandle(u64::from_le_bytes(n)))
+        }
+    }
+
+    fn id(n: u64) -> AssetUuid {
+        let mut id = [0; 16];
+        id[..8].copy_from_slice(&n.to_le_bytes());
+        AssetUuid(id)
+    }
+
+    #[test]
+    fn report_walks_load_deps_once() {
+        let (material, albedo, normal) = (LoadHandle(1), LoadHandle(2), LoadHandle(3));
+        let mut graph = LoadGraph::default();
+        graph.requested(material);
+        graph.resolved(id(1), &[AssetRef::Uuid(id(2)), AssetRef::Uuid(id(3))]);
+        graph.resolved(id(2), &[AssetRef::Uuid(id(1))]);
+        graph.received(material, 1, 100);
+        graph.received(albedo, 1, 1000);
+        graph.committed(albedo);
+
+        let lookup = Lookup(vec![
+            (albedo, AssetLoadState::Loaded),
+            (normal, AssetLoadState::Failed),
+        ]);
+        let report = graph.report(material, &lookup);
+
+        let handles: Vec<_> = report.nodes().iter().map(|node| node.handle).collect();
+        assert_eq!(handles, vec![material, albedo, normal]);
+        assert_eq!(report.total_bytes(), 1100);
+        assert!((report.progress() - 1.0 / 3.0).abs() < f32::EPSILON);
+        assert_eq!(report.failed().next().unwrap().handle, normal);
+        assert!(report.get(albedo).unwrap().load_time.is_some());
+        assert!(report.root().load_time.is_none());
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn reload_keeps_the_record() {
+        let (material, albedo) = (LoadHandle(1), LoadHandle(2));
+        let mut graph = LoadGraph::default();
+        graph.requested(material);
+        graph.resolved(id(1), &[AssetRef::Uuid(id(2))]);
+        graph.received(material, 1, 100);
+        graph.committed(material);
+
+        // A hot reload commits the new version before freeing the old one.
+        graph.received(material, 2, 120);
+        graph.committed(material);
+        graph.freed(material, 1);
+
+        let loaded = Lookup(vec![(material, AssetLoadState::Loaded)]);
+        let report = graph.report(material, &loaded);
+        assert_eq!(report.root().size_in_bytes, Some(120));
+        assert_eq!(report.root().dependencies, vec![albedo]);
+        assert!(report.root().load_time.is_some());
+
+        graph.freed(material, 2);
+        let report = graph.report(material, &Lookup(Vec::new()));
+        assert_eq!(report.root().size_in_bytes, None);
+    }
+
+    #[test]
+    fn failed_and_released_loads_are_forgotten() {
+        let (missing, dropped, loading) = (LoadHandle(1), LoadHandle(2), LoadHandle(3));
+        let mut graph = LoadGraph::default();
+        for handle in &[missing, dropped, loading] {
+            graph.requested(*handle);
+        }
+
+        graph.forget_failed(&Lookup(vec![(missing, AssetLoadState::Failed)]));
+        graph.released(dropped);
+
+        let remaining: Vec<_> = graph.records.keys().copied().collect();
+        assert_eq!(remaining, vec![loading]);
+    }
+}
@@ -18,15 +18,22 @@
 pub use rayon::ThreadPool;
 
 mod asset;
+mod budget;
+mod build;
 mod bundle;
 mod cache;
+mod config;
 #[cfg(feature = "asset-daemon")]
 mod daemon;
 /// asset loading specific errors
 pub mod error;
+mod event;
+mod graph;
 #[cfg(feature = "json")]
 mod json;
 mod loader;
+#[cfg(feature = "packfile")]
+mod packfile;
 /// helpers for registering prefab components
 pub mod prefab;
 mod processor;
@@ -34,6 +41,9 @@ mod progress;
 mod simple_importer;
 mod source;
 mod storage;
+#[cfg(test)]
+mod test_util;
+mod validate;
 
 pub use distill::{
     importer as distill_importer,
@@ -47,21 +57,33 @@ pub use distill::{
 pub use type_uuid::TypeUuid;
 // used in macros. Private API otherwise.
 #[doc(hidden)]
-pub use {erased_serde, inventory, lazy_static};
+pub use {crate::build::run_build_step, erased_serde, inventory, lazy_static};
 
 #[cfg(feature = "asset-daemon")]
 /// internal `AssetDaemon` control
-pub use crate::daemon::AssetDaemon;
+pub use crate::daemon::{AssetDaemon, AssetDaemonOpt};
+#[cfg(feature = "packfile")]
+pub use crate::packfile::{build_packfile, PackfileArgs};
+#[cfg(feature = "zip")]
+pub use crate::source::ZipSource;
+#[cfg(feature = "validate")]
+pub use crate::validate::run_asset_validation;
 #[cfg(feature = "json")]
 pub use crate::json::JsonFormat;
 pub use crate::{
     asset::{Asset, Format, FormatValue, ProcessableAsset, SerializableFormat},
+    budget::{AssetBudget, AssetBudgetSystem, AssetPressureEvent},
+    build::{run_build_steps, BuildStep, BuildStepConfig, BuildStepRegistration, ImportOptions},
     bundle::LoaderBundle,
     cache::Cache,
-    loader::{create_asset_type, AssetUuid, DefaultLoader, LoadStatus, Loader},
+    config::{ConfigAsset, ConfigAssetBundle, ConfigFile, ConfigFileFormat, ConfigReloadSystem},
+    event::{AssetEvent, AssetEventKind},
+    graph::{AssetLoadNode, AssetLoadState, LoadReport},
+    loader::{create_asset_type, AssetUuid, DefaultLoader, LoadStatus, Loader, LoaderMode},
     processor::{AssetProcessorSystem, ProcessingQueue, ProcessingState},
     progress::{Completion, Progress, ProgressCounter, Tracker},
-    simple_importer::{SimpleImporter, SourceFileImporter},
-    source::{Directory, Source},
+    simple_importer::{BytesFormat, FormatRegistration, SimpleImporter, SourceFileImporter},
+    source::{Directory, InMemorySource, Overlay, Source, Sources, DEFAULT_SOURCE},
     storage::AssetStorage,
+    validate::{validate_assets, AssetProblem, ValidationReport},
 };
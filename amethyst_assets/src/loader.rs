@@ -1,8 +1,16 @@
-use std::{cell::RefCell, collections::HashMap, error::Error, fs::File, path::PathBuf, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use amethyst_core::{
     dispatcher::System,
     ecs::{DispatcherBuilder, Resources},
+    shrev::EventChannel,
 };
 use amethyst_error::Error as AmethystError;
 use distill::{
@@ -20,11 +28,21 @@ use distill_loader::{
     AssetTypeId, Loader as DistillLoader, PackfileReader, RpcIO,
 };
 pub use distill_loader::{storage::LoadStatus, AssetUuid};
+use fnv::FnvHashMap;
 use log::debug;
+use parking_lot::Mutex;
 use serde::de::Deserialize;
 
 use crate::{
-    loader, processor::ProcessingQueue, progress::Progress, storage::AssetStorage, Asset, TypeUuid,
+    event::AssetEvent,
+    graph::{LoadGraph, LoadLookup, LoadReport},
+    loader,
+    processor::ProcessingQueue,
+    progress::Progress,
+    simple_importer::BytesFormat,
+    source::Sources,
+    storage::AssetStorage,
+    Asset, Format, TypeUuid,
 };
 
 /// Manages asset loading and storage for an application.
@@ -129,6 +147,87 @@ pub trait Loader: Send + Sync {
         A: Asset,
         P: Progress;
 
+    /// Loads an asset from the source registered under the name `source`, and returns a handle.
+    ///
+    /// The bytes are loaded and imported with `format` right away, then processed like the data
+    /// passed to `load_from_data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source doesn't exist, doesn't have the asset, or if `format` can't
+    /// import it.
+    fn load_from<A, F, P>(
+        &self,
+        path: &str,
+        format: F,
+        source: &str,
+        sources: &Sources,
+        progress: P,
+        storage: &ProcessingQueue<A::Data>,
+    ) -> Result<Handle<A>, AmethystError>
+    where
+        A: Asset,
+        F: Format<A::Data>,
+        P: Progress,
+    {
+        let data = sources.load_data(source, path, &format)?;
+        Ok(self.load_from_data(data, progress, storage))
+    }
+
+    /// Imports an asset from bytes, such as a downloaded file or user-generated content, and
+    /// returns a handle.
+    ///
+    /// The bytes are imported right away, either with the `Format` registered with
+    /// `register_importer!` for a file extension such as `"png"`, or with a given `Format`, then
+    /// processed like the data passed to `load_from_data`.
+    ///
+    /// The bytes don't go through the `AssetDaemon`, so no `BuildStep` runs on the imported data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no format producing `A::Data` is registered for the extension, or if
+    /// the format can't import the bytes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use amethyst_assets::{BytesFormat, DefaultLoader, Format, Handle, Loader, ProcessingQueue};
+    /// # use amethyst_assets::Asset;
+    /// # use amethyst_error::Error;
+    /// # struct Text;
+    /// # impl Asset for Text {
+    /// #     fn name() -> &'static str { "Text" }
+    /// #     type Data = String;
+    /// # }
+    ///
+    /// # fn load(
+    /// #     loader: &DefaultLoader,
+    /// #     queue: &ProcessingQueue<String>,
+    /// #     format: Box<dyn Format<String>>,
+    /// # ) -> Result<(), Error> {
+    /// let bytes = b"Hello".to_vec();
+    /// let by_extension: Handle<Text> = loader.load_from_bytes(bytes.clone(), "txt", (), queue)?;
+    /// let by_format: Handle<Text> =
+    ///     loader.load_from_bytes(bytes, BytesFormat::Format(format), (), queue)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn load_from_bytes<A, S, P>(
+        &self,
+        bytes: Vec<u8>,
+        format: S,
+        progress: P,
+        storage: &ProcessingQueue<A::Data>,
+    ) -> Result<Handle<A>, AmethystError>
+    where
+        A: Asset,
+        S: Into<BytesFormat<A::Data>>,
+        P: Progress,
+    {
+        let data = format.into().import(bytes)?;
+        Ok(self.load_from_data(data, progress, storage))
+    }
+
     /// Creates the `AssetTypeStorage`'s resources in the `World`.
     fn init_world(&mut self, resources: &mut Resources);
 
@@ -147,36 +246,23 @@ pub struct DefaultLoader {
     ref_receiver: Receiver<RefOp>,
     handle_allocator: Arc<AtomicHandleAllocator>,
     pub(crate) indirection_table: IndirectionTable,
+    graph: Mutex<LoadGraph>,
+    // Reference counts of the handles of `load_from_data`, which distill doesn't know about.
+    data_refs: Mutex<FnvHashMap<LoadHandle, u32>>,
 }
 
 impl Default for DefaultLoader {
+    /// Creates a loader fetching assets from the asset daemon.
     fn default() -> Self {
-        Self::new(true, None, None)
+        log::info!("Using RpcIO");
+        Self::new(Box::new(RpcIO::default()))
     }
 }
 
 impl DefaultLoader {
-    fn new(
-        loader_io_use_rpc: bool,
-        connect_string: Option<String>,
-        packfile_path: Option<PathBuf>,
-    ) -> Self {
+    fn new(loader_io: Box<dyn LoaderIO>) -> Self {
         let (tx, rx) = unbounded();
         let handle_allocator = Arc::new(AtomicHandleAllocator::default());
-        let loader_io: Box<dyn LoaderIO> = if loader_io_use_rpc {
-            log::info!("Using RpcIO");
-            let rpc_io = connect_string
-                .and_then(|cs| RpcIO::new(cs).ok())
-                .unwrap_or_default();
-            Box::new(rpc_io)
-        } else {
-            log::info!("Using PackfileIO");
-            let packfile_io = packfile_path
-                .map(|pfp| File::open(pfp).expect("Could not open packfile"))
-                .and_then(|pf| PackfileReader::new(pf).ok())
-                .expect("packfile not found");
-            Box::new(packfile_io)
-        };
         let loader = DistillLoader::new_with_handle_allocator(loader_io, handle_allocator.clone());
         Self {
             indirection_table: loader.indirection_table(),
@@ -185,25 +271,121 @@ impl DefaultLoader {
             ref_sender: tx,
             ref_receiver: rx,
             handle_allocator,
+            graph: Mutex::default(),
+            data_refs: Mutex::default(),
+        }
+    }
+
+    /// Returns the load state of the asset of `handle` and of every asset it depends on.
+    ///
+    /// Dependencies are the `load_deps` of the imported asset, they are known once the loader
+    /// received its metadata.
+    pub fn load_report<H: AssetHandle>(&self, handle: &H) -> LoadReport {
+        self.graph.lock().report(handle.load_handle(), &self.loader)
+    }
+
+    /// Returns the number of handles to the asset, counting the reference operations processed
+    /// so far, or `None` if the handle is unknown.
+    pub(crate) fn ref_count(&self, handle: LoadHandle) -> Option<u32> {
+        if let Some(refs) = self.data_refs.lock().get(&handle) {
+            return Some(*refs);
+        }
+        self.loader.get_load_info(handle).map(|info| info.refs)
+    }
+
+    /// Returns true if the asset was loaded from data, in which case it isn't unloaded when its
+    /// last handle is dropped.
+    pub(crate) fn is_from_data(&self, handle: LoadHandle) -> bool {
+        self.data_refs.lock().contains_key(&handle)
+    }
+
+    fn requested(&self, handle: LoadHandle) -> LoadHandle {
+        self.graph.lock().requested(handle);
+        handle
+    }
+
+    /// Creates a loader reading every asset from a packfile, without connecting to the asset
+    /// daemon.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packfile can't be opened or isn't a valid packfile.
+    pub fn from_packfile<P: AsRef<Path>>(path: P) -> Result<Self, AmethystError> {
+        let path = path.as_ref();
+        log::info!("Using PackfileIO with {}", path.display());
+        let file = File::open(path).map_err(|err| {
+            AmethystError::from_string(format!(
+                "Could not open packfile {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        let packfile_io = PackfileReader::new(file).map_err(|err| {
+            AmethystError::from_string(format!(
+                "Could not read packfile {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        Ok(Self::new(Box::new(packfile_io)))
+    }
+}
+
+/// Where the `DefaultLoader` created by the `LoaderBundle` reads assets from.
+///
+/// Insert this resource before adding the `LoaderBundle` to change the mode, or use
+/// `ApplicationBuilder::with_packfile`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoaderMode {
+    /// Assets are imported by the asset daemon and fetched over RPC, and reloaded when they change.
+    Daemon,
+    /// Assets are read from a packfile built ahead of time, e.g. by `build_packfile`.
+    Packfile(PathBuf),
+}
+
+impl Default for LoaderMode {
+    fn default() -> Self {
+        LoaderMode::Daemon
+    }
+}
+
+impl LoaderMode {
+    /// Creates the loader for this mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packfile can't be read.
+    pub fn create_loader(&self) -> Result<DefaultLoader, AmethystError> {
+        match self {
+            LoaderMode::Daemon => Ok(DefaultLoader::default()),
+            LoaderMode::Packfile(path) => DefaultLoader::from_packfile(path),
         }
     }
 }
 
 impl Loader for DefaultLoader {
     fn load_asset_generic(&self, id: AssetUuid) -> GenericHandle {
-        GenericHandle::new(self.ref_sender.clone(), self.loader.add_ref(id))
+        GenericHandle::new(
+            self.ref_sender.clone(),
+            self.requested(self.loader.add_ref(id)),
+        )
     }
     fn load_asset<A: TypeUuid>(&self, id: AssetUuid) -> Handle<A> {
-        Handle::new(self.ref_sender.clone(), self.loader.add_ref(id))
+        Handle::new(
+            self.ref_sender.clone(),
+            self.requested(self.loader.add_ref(id)),
+        )
     }
     fn load<A: TypeUuid>(&self, path: &str) -> Handle<A> {
         Handle::new(
             self.ref_sender.clone(),
-            self.loader
-                .add_ref_indirect(IndirectIdentifier::PathWithType(
-                    path.to_string(),
-                    AssetTypeId(A::UUID),
-                )),
+            self.requested(
+                self.loader
+                    .add_ref_indirect(IndirectIdentifier::PathWithType(
+                        path.to_string(),
+                        AssetTypeId(A::UUID),
+                    )),
+            ),
         )
     }
     fn get_load(&self, id: AssetUuid) -> Option<WeakHandle> {
@@ -228,6 +410,7 @@ impl Loader for DefaultLoader {
         let tracker = progress.create_tracker();
         let tracker = Box::new(tracker);
         let handle = self.handle_allocator.alloc();
+        self.data_refs.lock().insert(handle, 1);
         let version = 0;
         processing_queue.enqueue_from_data(handle, data, tracker, version);
         Handle::<A>::new(self.ref_sender.clone(), handle)
@@ -250,23 +433,68 @@ impl Loader for DefaultLoader {
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => panic!("RefOp receiver disconnected"),
                 Ok(RefOp::Decrease(handle)) => {
-                    self.loader.remove_ref(handle);
+                    let mut data_refs = self.data_refs.lock();
+                    match data_refs.get_mut(&handle) {
+                        Some(refs) => {
+                            *refs -= 1;
+                            if *refs == 0 {
+                                data_refs.remove(&handle);
+                            }
+                        }
+                        None => {
+                            self.loader.remove_ref(handle);
+                            let released = self
+                                .loader
+                                .get_load_info(handle)
+                                .map_or(true, |info| info.refs == 0);
+                            if released {
+                                self.graph.lock().released(handle);
+                            }
+                        }
+                    }
                 }
                 Ok(RefOp::Increase(handle)) => {
-                    self.loader.add_ref_handle(handle);
+                    match self.data_refs.lock().get_mut(&handle) {
+                        Some(refs) => *refs += 1,
+                        None => {
+                            self.loader.add_ref_handle(handle);
+                        }
+                    }
                 }
                 Ok(RefOp::IncreaseUuid(uuid)) => {
                     self.loader.add_ref(uuid);
                 }
             }
         }
-        let storages = WorldStorages::new(resources, &self.storage_map, &self.ref_sender);
-        self.loader.process(&storages, &AssetIndirectionResolver)
+        let storages =
+            WorldStorages::new(resources, &self.storage_map, &self.ref_sender, &self.graph);
+        let resolver = AssetIndirectionResolver { graph: &self.graph };
+        let result = self.loader.process(&storages, &resolver);
+        self.graph.lock().forget_failed(&self.loader);
+        result
     }
 }
 
-pub struct AssetIndirectionResolver;
-impl IndirectionResolver for AssetIndirectionResolver {
+impl LoadLookup for DistillLoader {
+    fn status(&self, handle: LoadHandle) -> LoadStatus {
+        self.get_load_status(handle)
+    }
+
+    fn asset_id(&self, handle: LoadHandle) -> Option<AssetUuid> {
+        self.get_load_info(handle).map(|info| info.asset_id)
+    }
+
+    fn load_handle(&self, id: AssetUuid) -> Option<LoadHandle> {
+        self.get_load(id)
+    }
+}
+
+/// Resolves paths to assets, recording the `load_deps` of the resolved assets in the `LoadGraph`.
+pub struct AssetIndirectionResolver<'a> {
+    graph: &'a Mutex<LoadGraph>,
+}
+
+impl<'a> IndirectionResolver for AssetIndirectionResolver<'a> {
     fn resolve(
         &self,
         id: &IndirectIdentifier,
@@ -281,6 +509,7 @@ impl IndirectionResolver for AssetIndirectionResolver {
                         || candidate_assets_len == 1
                         || *id_type.unwrap() == artifact.type_id
                     {
+                        self.graph.lock().resolved(asset.id, &artifact.load_deps);
                         return Some(asset.id);
                     }
                 }
@@ -341,17 +570,11 @@ where
         load_op: AssetLoadOp,
         version: u32,
     ) -> Result<(), Box<dyn Error + Send>> {
-        match bincode::deserialize::<Intermediate>(data) {
-            Err(err) => {
-                let e = AmethystError::from_string(format!("{}", err));
-                load_op.error(err);
-                Err(e.into_error())
-            }
-            Ok(asset) => {
-                self.0.enqueue(handle, asset, load_op, version);
-                Ok(())
-            }
-        }
+        // Deserialization errors go through the queue, so they are reported as `AssetEvent`s.
+        let asset = bincode::deserialize::<Intermediate>(data)
+            .map_err(|err| AmethystError::from_string(format!("{}", err)));
+        self.0.enqueue(handle, asset, load_op, version);
+        Ok(())
     }
 
     fn commit_asset_version(&mut self, handle: LoadHandle, version: u32) {
@@ -407,6 +630,7 @@ struct WorldStorages<'a> {
     storage_map: &'a AssetStorageMap,
     ref_sender: &'a Sender<RefOp>,
     resources: &'a Resources,
+    graph: &'a Mutex<LoadGraph>,
 }
 
 impl<'a> WorldStorages<'a> {
@@ -414,11 +638,13 @@ impl<'a> WorldStorages<'a> {
         resources: &'a Resources,
         storage_map: &'a AssetStorageMap,
         ref_sender: &'a Sender<RefOp>,
+        graph: &'a Mutex<LoadGraph>,
     ) -> WorldStorages<'a> {
         WorldStorages {
             storage_map,
             ref_sender,
             resources,
+            graph,
         }
     }
 }
@@ -435,6 +661,7 @@ impl<'a> distill_loader::storage::AssetStorage for WorldStorages<'a> {
     ) -> Result<(), Box<dyn Error + Send>> {
         let moved_op = RefCell::new(Some(load_op));
         let mut result = None;
+        let size_in_bytes = data.len();
         if let Some(asset_type) = self.storage_map.storages_by_data_uuid.get(asset_type) {
             (asset_type.with_storage)(self.resources, &mut |storage: &mut dyn AssetTypeStorage| {
                 futures_executor::block_on(SerdeContext::with(
@@ -451,6 +678,9 @@ impl<'a> distill_loader::storage::AssetStorage for WorldStorages<'a> {
                 ));
             });
         }
+        self.graph
+            .lock()
+            .received(load_handle, version, size_in_bytes);
         result.unwrap()
     }
 
@@ -468,6 +698,7 @@ impl<'a> distill_loader::storage::AssetStorage for WorldStorages<'a> {
             .with_storage)(self.resources, &mut |storage: &mut dyn AssetTypeStorage| {
             storage.commit_asset_version(load_handle, version);
         });
+        self.graph.lock().committed(load_handle);
     }
 
     fn free(&self, asset_type: &AssetTypeId, load_handle: LoadHandle, version: u32) {
@@ -479,6 +710,7 @@ impl<'a> distill_loader::storage::AssetStorage for WorldStorages<'a> {
             .with_storage)(self.resources, &mut |storage: &mut dyn AssetTypeStorage| {
             storage.free(load_handle, version);
         });
+        self.graph.lock().freed(load_handle, version);
     }
 }
 
@@ -526,6 +758,7 @@ where
         create_storage: |res, indirection_table| {
             debug!("Creating storage for {:x?}", Asset::UUID);
             res.get_or_insert_with(|| AssetStorage::<Asset>::new(indirection_table.clone()));
+            res.get_or_insert_with(EventChannel::<AssetEvent<Asset>>::default);
             debug!("Creating queue for intermediate {:x?}", Intermediate::UUID);
             res.get_or_insert_with(ProcessingQueue::<Intermediate>::default);
         },
@@ -599,3 +832,14 @@ macro_rules! register_asset_type {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_packfile_is_an_error() {
+        let mode = LoaderMode::Packfile(PathBuf::from("does/not/exist.pack"));
+        assert!(mode.create_loader().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Offline packing of assets, to ship an application without the asset daemon.
+
+use std::{
+    net::{SocketAddr, TcpListener},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use amethyst_error::Error;
+use distill_cli::{create_context, CmdPack, Command};
+use structopt::StructOpt;
+use tokio::{runtime, task::LocalSet};
+
+use crate::{
+    daemon::{AssetDaemon, AssetDaemonOpt},
+    validate::pending_imports,
+};
+
+/// Delay between two checks of the imports, and between two attempts to reach the daemon.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Parameters to build a packfile.
+///
+/// # Examples
+///
+/// ```bash
+/// amethyst_packfile --db .assets_db --output assets.pack assets
+/// ```
+#[derive(StructOpt, Debug, Clone)]
+pub struct PackfileArgs {
+    /// Path to the asset metadata database directory.
+    #[structopt(name = "db", long, parse(from_os_str), default_value = ".assets_db")]
+    pub db_dir: PathBuf,
+    /// Path of the packfile to write.
+    #[structopt(short, long, parse(from_os_str), default_value = "assets.pack")]
+    pub output: PathBuf,
+    /// Seconds to wait for the daemon to import the assets.
+    #[structopt(long, default_value = "120")]
+    pub timeout: u64,
+    /// Directories to pack the assets of.
+    #[structopt(parse(from_os_str), default_value = "assets")]
+    pub asset_dirs: Vec<PathBuf>,
+}
+
+/// Imports the assets of `asset_dirs` with the importers of the `AssetDaemon`, and writes them to
+/// a packfile at `output`, which can then be loaded with `DefaultLoader::from_packfile`.
+///
+/// The daemon runs on a background thread, and the assets are packed once it has written a
+/// `.meta` file with the current importer version for every source file. The packing client of
+/// distill only connects to the default daemon address, so the daemon listens there, and no other
+/// asset daemon may be running.
+///
+/// # Errors
+///
+/// Returns an error if another process listens on the default daemon address, if the assets
+/// aren't imported within `timeout` seconds, or if the packfile can't be written.
+pub fn build_packfile(args: PackfileArgs) -> Result<(), Error> {
+    let opt = AssetDaemonOpt {
+        db_dir: args.db_dir,
+        asset_dirs: args.asset_dirs,
+        ..AssetDaemonOpt::default()
+    };
+    ensure_available(opt.address)?;
+    let asset_dirs = opt.asset_dirs.clone();
+    let deadline = Instant::now() + Duration::from_secs(args.timeout);
+
+    let mut daemon = AssetDaemon::with_opt(opt);
+    daemon.start_on_new_thread();
+    let result = wait_for_imports(&asset_dirs, deadline).and_then(|_| pack(&args.output, deadline));
+    daemon.stop_and_join();
+    result
+}
+
+/// Fails if `address` is taken, the assets of the daemon listening there would be packed.
+fn ensure_available(address: SocketAddr) -> Result<(), Error> {
+    TcpListener::bind(address).map(drop).map_err(|err| {
+        Error::from_string(format!(
+            "Can't listen on {}, is another asset daemon running? {}",
+            address, err
+        ))
+    })
+}
+
+/// Blocks until every source file of `asset_dirs` is imported with the current version of its
+/// importer.
+fn wait_for_imports(asset_dirs: &[PathBuf], deadline: Instant) -> Result<(), Error> {
+    loop {
+        let pending = pending_imports(asset_dirs)?;
+        match pending.first() {
+            None => return Ok(()),
+            Some(path) if Instant::now() >= deadline => {
+                return Err(Error::from_string(format!(
+                    "Timed out waiting for the import of {} files, such as {}",
+                    pending.len(),
+                    path.display()
+                )));
+            }
+            Some(_) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Writes the assets imported by the daemon to `output`, retrying until the daemon listens.
+fn pack(output: &Path, deadline: Instant) -> Result<(), Error> {
+    loop {
+        if write_packfile(output)? {
+            log::info!("Wrote packfile {}", output.display());
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(Error::from_string("Could not reach asset daemon"));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Writes the assets currently known to the daemon to `output`, returning `false` if the daemon
+/// can't be reached.
+fn write_packfile(output: &Path) -> Result<bool, Error> {
+    let output = output
+        .to_str()
+        .ok_or_else(|| Error::from_string("Packfile path is not valid UTF-8"))?;
+    let runtime = runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(Error::new)?;
+    let local = LocalSet::new();
+    local.block_on(&runtime, async {
+        let context = match create_context(&local).await {
+            Ok(context) => context,
+            Err(err) => {
+                log::debug!("Could not reach asset daemon: {}", err);
+                return Ok(false);
+            }
+        };
+        CmdPack
+            .run(&context, vec![output])
+            .await
+            .map(|_| true)
+            .map_err(|err| Error::from_string(format!("Could not write packfile: {}", err)))
+    })
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use amethyst_core::ecs::World;
 use distill::importer as distill_importer;
 use distill_importer::{typetag, SerdeImportable};
@@ -11,8 +13,9 @@ use crate::{asset::Asset, prefab, Handle, WeakHandle};
 #[derive(TypeUuid, Serialize, Deserialize, SerdeImportable)]
 #[uuid = "5e751ea4-e63b-4192-a008-f5bf8674e45b"]
 pub struct Prefab {
-    /// contains Legion World and Entity Mappings
-    pub(crate) cooked: Option<legion_prefab::CookedPrefab>,
+    /// contains Legion World and Entity Mappings, shared with the instances spawned from it
+    #[serde(skip)]
+    pub(crate) cooked: Option<Arc<legion_prefab::CookedPrefab>>,
 
     /// Contains World to cook and references to other prefabs
     pub(crate) raw: legion_prefab::Prefab,
@@ -2,7 +2,7 @@ use std::{collections::HashMap, ops::Range};
 
 use amethyst_core::ecs::{
     storage::{Archetype, Component, ComponentTypeId, ComponentWriter, Components},
-    world::EntityHasher,
+    world::{EntityHasher, Entry, EntryRef},
     Entity, Resources,
 };
 use fnv::{FnvBuildHasher, FnvHashMap};
@@ -10,6 +10,9 @@ use legion_prefab::{
     ComponentRegistration, CopyCloneImpl, SpawnCloneImpl, SpawnCloneImplHandlerSet, SpawnInto,
 };
 use prefab_format::ComponentTypeUuid;
+use serde_diff::SerdeDiff;
+
+use crate::prefab::hot_reload::{patch_component, ComponentPatcher};
 
 /// registers prefab components that can be created by prefab
 /// use `register_component_type!` macro to add new components
@@ -18,6 +21,7 @@ pub struct ComponentRegistryBuilder {
     components: FnvHashMap<ComponentTypeId, ComponentRegistration>,
     components_by_uuid: FnvHashMap<ComponentTypeUuid, ComponentRegistration>,
     spawn_handler_set: SpawnCloneImplHandlerSet,
+    patchers: FnvHashMap<ComponentTypeId, ComponentPatcher>,
 }
 
 impl ComponentRegistryBuilder {
@@ -81,6 +85,16 @@ impl ComponentRegistryBuilder {
             .add_mapping_closure::<FromT, _, _>(clone_fn);
     }
 
+    /// registers a component which is patched on the entities spawned from a prefab when the
+    /// prefab is reloaded, keeping the fields the game changed since it was spawned.
+    /// Other components keep their live values.
+    #[must_use]
+    pub fn add_hot_reload<T: Component + Clone + SerdeDiff>(mut self) -> Self {
+        self.patchers
+            .insert(ComponentTypeId::of::<T>(), patch_component::<T>);
+        self
+    }
+
     /// builds the component registry with spawn mappings
     #[must_use]
     pub fn build(self) -> ComponentRegistry {
@@ -88,6 +102,7 @@ impl ComponentRegistryBuilder {
             components: self.components,
             components_by_uuid: self.components_by_uuid,
             spawn_handler_set: self.spawn_handler_set,
+            patchers: self.patchers,
         }
     }
 }
@@ -97,6 +112,7 @@ pub struct ComponentRegistry {
     components: FnvHashMap<ComponentTypeId, ComponentRegistration>,
     components_by_uuid: FnvHashMap<ComponentTypeUuid, ComponentRegistration>,
     spawn_handler_set: SpawnCloneImplHandlerSet,
+    patchers: FnvHashMap<ComponentTypeId, ComponentPatcher>,
 }
 
 impl ComponentRegistry {
@@ -132,4 +148,18 @@ impl ComponentRegistry {
             entity_map,
         )
     }
+
+    /// registers a component which is patched on the entities spawned from a prefab when the
+    /// prefab is reloaded, see `ComponentRegistryBuilder::add_hot_reload`
+    pub fn add_hot_reload<T: Component + Clone + SerdeDiff>(&mut self) {
+        self.patchers
+            .insert(ComponentTypeId::of::<T>(), patch_component::<T>);
+    }
+
+    /// patches the hot reloadable components of `live` with the changes from `base` to `new`
+    pub(crate) fn patch(&self, base: &EntryRef<'_>, new: &EntryRef<'_>, live: &mut Entry<'_>) {
+        for patcher in self.patchers.values() {
+            patcher(base, new, live);
+        }
+    }
 }
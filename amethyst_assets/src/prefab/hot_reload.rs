@@ -0,0 +1,200 @@
+use amethyst_core::ecs::{
+    storage::Component,
+    world::{Entry, EntryRef},
+    DispatcherBuilder, Resources, SystemBundle, World,
+};
+use amethyst_error::Error;
+use bincode::Options;
+use derivative::Derivative;
+use serde_diff::{Apply, Diff, SerdeDiff};
+
+use crate::prefab::ComponentRegistry;
+
+/// Bundle patching the components of the entities spawned from a prefab when the prefab is
+/// reloaded, keeping the fields the game changed since they were spawned.
+///
+/// Only the components registered with [`PrefabHotReloadBundle::with_component`] are patched,
+/// the others keep their live values. The bundle must be added after the `LoaderBundle`.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst_assets::{prefab::PrefabHotReloadBundle, LoaderBundle};
+/// use amethyst_core::{ecs::DispatcherBuilder, transform::Transform};
+///
+/// let mut dispatcher = DispatcherBuilder::default();
+/// dispatcher
+///     .add_bundle(LoaderBundle)
+///     .add_bundle(PrefabHotReloadBundle::default().with_component::<Transform>());
+/// ```
+#[derive(Derivative, Default)]
+#[derivative(Debug)]
+pub struct PrefabHotReloadBundle {
+    #[derivative(Debug = "ignore")]
+    components: Vec<fn(&mut ComponentRegistry)>,
+}
+
+impl PrefabHotReloadBundle {
+    /// Patches the `C` components of the entities spawned from a prefab when it is reloaded.
+    #[must_use]
+    pub fn with_component<C: Component + Clone + SerdeDiff>(mut self) -> Self {
+        self.components.push(ComponentRegistry::add_hot_reload::<C>);
+        self
+    }
+}
+
+impl SystemBundle for PrefabHotReloadBundle {
+    fn load(
+        &mut self,
+        _: &mut World,
+        resources: &mut Resources,
+        _: &mut DispatcherBuilder,
+    ) -> Result<(), Error> {
+        let mut registry = resources
+            .get_mut::<ComponentRegistry>()
+            .ok_or_else(|| Error::from_string("PrefabHotReloadBundle requires the LoaderBundle"))?;
+        for add_hot_reload in &self.components {
+            add_hot_reload(&mut registry);
+        }
+        Ok(())
+    }
+}
+
+/// Patches a live component after the prefab it was spawned from changed.
+///
+/// Receives the entity as it was in the previous version of the prefab, as it is in the new
+/// version, and the live entity spawned from it.
+pub(crate) type ComponentPatcher = fn(&EntryRef<'_>, &EntryRef<'_>, &mut Entry<'_>);
+
+/// Applies the changes between two versions of a prefab to a live component, keeping the fields
+/// the game changed since the component was spawned.
+pub(crate) fn patch_component<T>(base: &EntryRef<'_>, new: &EntryRef<'_>, live: &mut Entry<'_>)
+where
+    T: Component + Clone + SerdeDiff,
+{
+    match (base.get_component::<T>(), new.get_component::<T>()) {
+        (Ok(base), Ok(new)) => {
+            // A component removed by the game stays removed.
+            if let Ok(current) = live.get_component_mut::<T>() {
+                match merge(base, new, current) {
+                    Ok(merged) => *current = merged,
+                    Err(err) => log::error!(
+                        "Failed to patch {} from the reloaded prefab: {}",
+                        std::any::type_name::<T>(),
+                        err
+                    ),
+                }
+            }
+        }
+        (Err(_), Ok(new)) => {
+            if live.get_component::<T>().is_err() {
+                live.add_component(new.clone());
+            }
+        }
+        (Ok(_), Err(_)) => live.remove_component::<T>(),
+        (Err(_), Err(_)) => {}
+    }
+}
+
+/// Three-way merge: the fields in which `live` differs from `base` are applied on top of `new`.
+fn merge<T: Clone + SerdeDiff>(base: &T, new: &T, live: &T) -> Result<T, bincode::Error> {
+    let local_changes = bincode::options().serialize(&Diff::serializable(base, live))?;
+    let mut merged = new.clone();
+    bincode::options().deserialize_seed(Apply::deserializable(&mut merged), &local_changes)?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::prefab::ComponentRegistryBuilder;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerdeDiff)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    #[test]
+    fn merge_keeps_local_changes() {
+        let base = Health {
+            current: 10,
+            max: 10,
+        };
+        let damaged = Health {
+            current: 5,
+            max: 10,
+        };
+        let tweaked = Health {
+            current: 20,
+            max: 20,
+        };
+
+        assert_eq!(
+            merge(&base, &tweaked, &damaged).unwrap(),
+            Health {
+                current: 5,
+                max: 20
+            }
+        );
+        assert_eq!(merge(&base, &tweaked, &base).unwrap(), tweaked);
+    }
+
+    #[test]
+    fn patch_adds_and_removes_components() {
+        let mut prefabs = World::default();
+        let base = prefabs.push((Health { current: 1, max: 1 },));
+        let new = prefabs.push((7_u8,));
+
+        let mut world = World::default();
+        let live = world.push((Health { current: 1, max: 1 }, 3_u8));
+        {
+            let mut live = world.entry(live).unwrap();
+            let base = prefabs.entry_ref(base).unwrap();
+            let new = prefabs.entry_ref(new).unwrap();
+            patch_component::<Health>(&base, &new, &mut live);
+            patch_component::<u8>(&base, &new, &mut live);
+        }
+
+        let live = world.entry_ref(live).unwrap();
+        assert!(live.get_component::<Health>().is_err());
+        // The live value was added by the game, so the prefab doesn't override it.
+        assert_eq!(live.get_component::<u8>().unwrap(), &3);
+    }
+
+    #[test]
+    fn bundle_registers_only_its_components() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(ComponentRegistryBuilder::default().build());
+        PrefabHotReloadBundle::default()
+            .with_component::<Health>()
+            .load(
+                &mut world,
+                &mut resources,
+                &mut DispatcherBuilder::default(),
+            )
+            .unwrap();
+
+        let mut prefabs = World::default();
+        let base = prefabs.push((Health { current: 1, max: 1 }, 1_u8));
+        let new = prefabs.push((Health { current: 2, max: 2 }, 2_u8));
+        let live = world.push((Health { current: 1, max: 1 }, 1_u8));
+        {
+            let mut live = world.entry(live).unwrap();
+            let base = prefabs.entry_ref(base).unwrap();
+            let new = prefabs.entry_ref(new).unwrap();
+            let registry = resources.get::<ComponentRegistry>().unwrap();
+            registry.patch(&base, &new, &mut live);
+        }
+
+        let live = world.entry_ref(live).unwrap();
+        assert_eq!(
+            live.get_component::<Health>().unwrap(),
+            &Health { current: 2, max: 2 }
+        );
+        assert_eq!(live.get_component::<u8>().unwrap(), &1);
+    }
+}
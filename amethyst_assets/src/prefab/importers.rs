@@ -1,7 +1,7 @@
 use std::{collections::HashMap, io::Read};
 
 use distill::{
-    core::AssetUuid,
+    core::{AssetRef, AssetUuid},
     importer::{self as distill_importer, ImportOp, ImportedAsset, Importer, ImporterValue},
 };
 use legion_prefab::ComponentRegistration;
@@ -9,11 +9,16 @@ use prefab_format::ComponentTypeUuid;
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
 
-use crate::{prefab, prefab::Prefab};
+use crate::{build::run_build_steps, prefab, prefab::Prefab, BuildStepConfig};
 
-#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+/// Options of the importer for '.prefab' files.
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone)]
 #[uuid = "80583980-24d4-4034-8394-ea749b43f55d"]
-pub struct PrefabImporterOptions {}
+pub struct PrefabImporterOptions {
+    /// The build steps to run on the imported `Prefab`, in order.
+    #[serde(default)]
+    pub build_steps: Vec<BuildStepConfig>,
+}
 
 /// A simple state for Importer to retain the same UUID between imports
 /// for all single-asset source files
@@ -30,7 +35,7 @@ pub struct PrefabImporter {}
 
 impl Importer for PrefabImporter {
     fn version_static() -> u32 {
-        1
+        2
     }
     fn version(&self) -> u32 {
         Self::version_static()
@@ -44,7 +49,7 @@ impl Importer for PrefabImporter {
         &self,
         _op: &mut ImportOp,
         source: &mut dyn Read,
-        _: &Self::Options,
+        options: &Self::Options,
         state: &mut Self::State,
     ) -> distill_importer::Result<ImporterValue> {
         log::info!("Importing prefab");
@@ -81,10 +86,21 @@ impl Importer for PrefabImporter {
         }
         let raw_prefab = prefab_deser.prefab();
 
+        // Referenced prefabs are loaded along with this one, so they are ready to be cooked
+        // into it.
+        let load_deps = raw_prefab
+            .prefab_meta
+            .prefab_refs
+            .keys()
+            .map(|prefab_id| AssetRef::Uuid(AssetUuid(*prefab_id)))
+            .collect();
+
         let prefab_asset = Prefab {
             raw: raw_prefab,
             ..prefab::assets::Prefab::default()
         };
+        let prefab_asset = run_build_steps(prefab_asset, &options.build_steps)
+            .map_err(|e| distill_importer::Error::Boxed(e.into_error()))?;
 
         // STEP 3: Now we need to save it into an asset
 
@@ -97,7 +113,7 @@ impl Importer for PrefabImporter {
                 id: state.id.expect("AssetUuid not generated"),
                 search_tags: Vec::new(),
                 build_deps: Vec::new(),
-                load_deps: Vec::new(),
+                load_deps,
                 asset_data: Box::new(prefab_asset),
                 build_pipeline: None,
             }],
@@ -13,8 +13,17 @@ pub use component_registry::{ComponentRegistry, ComponentRegistryBuilder};
 pub use legion_prefab::{self, register_component_type, ComponentRegistration};
 pub use serde_diff::{self, SerdeDiff};
 
+mod hot_reload;
+pub use hot_reload::PrefabHotReloadBundle;
+
 mod processor;
 
+mod overrides;
+pub use overrides::{PrefabCommands, PrefabOverrides};
+
+mod snapshot;
+pub use snapshot::{WorldSnapshot, SNAPSHOT_VERSION};
+
 // register core components
 register_component_type!(amethyst_core::transform::Transform);
 register_component_type!(amethyst_core::transform::TransformValues);
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use amethyst_core::ecs::{systems::CommandBuffer, world::Entry, Entity, World};
+use bincode::Options;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_diff::{Apply, Diff, SerdeDiff};
+
+use crate::{prefab::Prefab, Handle};
+
+/// Changes made to a single component of the root entity of a prefab instance.
+trait ComponentOverride: Send + Sync {
+    fn apply(&self, entry: &mut Entry<'_>);
+}
+
+/// Replaces the component with a copy of the value.
+struct Replace<T>(T);
+
+impl<T> ComponentOverride for Replace<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn apply(&self, entry: &mut Entry<'_>) {
+        entry.add_component(self.0.clone());
+    }
+}
+
+/// Applies a `serde_diff` diff, serialized with bincode, to the component.
+struct ApplyDiff<T> {
+    diff: Vec<u8>,
+    phantom: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T> ComponentOverride for ApplyDiff<T>
+where
+    T: SerdeDiff + DeserializeOwned + Send + Sync + 'static,
+{
+    fn apply(&self, entry: &mut Entry<'_>) {
+        match entry.get_component_mut::<T>() {
+            Ok(component) => {
+                if let Err(err) = bincode::options()
+                    .deserialize_seed(Apply::deserializable(component), &self.diff)
+                {
+                    log::error!(
+                        "Failed to apply prefab override of {}: {}",
+                        std::any::type_name::<T>(),
+                        err
+                    );
+                }
+            }
+            Err(_) => log::warn!(
+                "Prefab override of {} targets a component the prefab doesn't have",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+}
+
+/// Per-instance changes to the components of the root entity of a prefab.
+///
+/// Overrides are stored on the instance and applied every time the prefab is spawned into it, so
+/// they also survive the prefab being reloaded.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_assets::prefab::PrefabOverrides;
+/// use amethyst_core::transform::Transform;
+///
+/// let mut moved = Transform::default();
+/// moved.set_translation_x(10.0);
+///
+/// // Only the fields that differ from the base are overridden.
+/// let overrides = PrefabOverrides::default().with_diff(&Transform::default(), &moved);
+/// ```
+#[derive(Clone, Default)]
+pub struct PrefabOverrides {
+    overrides: Vec<Arc<dyn ComponentOverride>>,
+}
+
+impl PrefabOverrides {
+    /// Replaces the whole component, or adds it if the prefab doesn't have one, e.g. to use
+    /// another material handle.
+    #[must_use]
+    pub fn with<T>(mut self, component: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.overrides.push(Arc::new(Replace(component)));
+        self
+    }
+
+    /// Overrides only the fields of the component in which `target` differs from `base`, leaving
+    /// the other fields to the prefab.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the diff can't be serialized.
+    #[must_use]
+    pub fn with_diff<T>(mut self, base: &T, target: &T) -> Self
+    where
+        T: SerdeDiff + Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        let diff = bincode::options()
+            .serialize(&Diff::serializable(base, target))
+            .expect("failed to serialize prefab override");
+        self.overrides.push(Arc::new(ApplyDiff::<T> {
+            diff,
+            phantom: std::marker::PhantomData,
+        }));
+        self
+    }
+
+    /// Returns true if nothing is overridden.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Applies the overrides, in the order they were added.
+    pub(crate) fn apply(&self, entry: &mut Entry<'_>) {
+        for component_override in &self.overrides {
+            component_override.apply(entry);
+        }
+    }
+}
+
+impl std::fmt::Debug for PrefabOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrefabOverrides")
+            .field("overrides", &self.overrides.len())
+            .finish()
+    }
+}
+
+/// Spawns instances of prefabs.
+pub trait PrefabCommands {
+    /// Creates an entity which becomes the root of a new instance of the prefab, with the given
+    /// overrides, as soon as the prefab is loaded.
+    ///
+    /// Prefabs referenced by the prefab are spawned along with it.
+    fn spawn_prefab(&mut self, handle: &Handle<Prefab>, overrides: PrefabOverrides) -> Entity;
+}
+
+impl PrefabCommands for CommandBuffer {
+    fn spawn_prefab(&mut self, handle: &Handle<Prefab>, overrides: PrefabOverrides) -> Entity {
+        self.push((handle.clone(), overrides))
+    }
+}
+
+impl PrefabCommands for World {
+    fn spawn_prefab(&mut self, handle: &Handle<Prefab>, overrides: PrefabOverrides) -> Entity {
+        self.push((handle.clone(), overrides))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use amethyst_core::transform::Transform;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Material(u32);
+
+    #[test]
+    fn overrides_apply_in_order() {
+        let mut world = World::default();
+        let mut prefab_transform = Transform::default();
+        prefab_transform.set_scale([2.0, 2.0, 2.0].into());
+        let entity = world.push((prefab_transform, Material(1)));
+
+        let mut moved = Transform::default();
+        moved.set_translation_x(5.0);
+        let overrides = PrefabOverrides::default()
+            .with(Material(2))
+            .with_diff(&Transform::default(), &moved);
+
+        overrides.apply(&mut world.entry(entity).unwrap());
+
+        let entry = world.entry_ref(entity).unwrap();
+        assert_eq!(entry.get_component::<Material>().unwrap(), &Material(2));
+        let transform = entry.get_component::<Transform>().unwrap();
+        assert!((transform.translation().x - 5.0).abs() < f32::EPSILON);
+        assert!((transform.scale().x - 2.0).abs() < f32::EPSILON);
+    }
+}
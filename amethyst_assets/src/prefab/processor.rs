@@ -1,13 +1,17 @@
 use amethyst_core::{
     dispatcher::System,
     ecs::{systems::ParallelRunnable, SystemBuilder},
+    shrev::EventChannel,
 };
+use std::sync::Arc;
+
 use distill::core::AssetUuid;
 use fnv::{FnvHashMap, FnvHashSet};
 use prefab_format::PrefabUuid;
 
 use crate::{
     self as amethyst_assets,
+    event::AssetEvent,
     loader::{DefaultLoader, Loader},
     prefab::{ComponentRegistry, Prefab},
     storage::{AssetStorage, MutateAssetInStorage},
@@ -75,14 +79,19 @@ impl System for PrefabProcessorSystem {
                 .write_resource::<ProcessingQueue<Prefab>>()
                 .write_resource::<AssetStorage<Prefab>>()
                 .write_resource::<DefaultLoader>()
+                .write_resource::<EventChannel<AssetEvent<Prefab>>>()
                 .build(
-                    move |_, _, (component_registry, processing_queue, storage, loader), _| {
+                    move |_,
+                          _,
+                          (component_registry, processing_queue, storage, loader, events),
+                          _| {
                         prefab_asset_processor(
                             component_registry,
                             processing_queue,
                             storage,
                             loader,
                         );
+                        events.iter_write(storage.drain_events());
                     },
                 ),
         )
@@ -123,7 +132,7 @@ fn prefab_asset_processor(
 
         for (handle, cooked_prefab) in updates {
             storage.mutate_asset_in_storage(&handle, move |prefab| {
-                prefab.cooked = Some(cooked_prefab);
+                prefab.cooked = Some(Arc::new(cooked_prefab));
                 prefab.version += 1;
             });
         }
@@ -152,7 +161,11 @@ fn prefab_asset_processor(
                 .iter()
                 .all(|handle| storage.contains(handle.load_handle()))
             {
-                prefab.cooked = Some(Prefab::cook_prefab(&prefab, storage, component_registry));
+                prefab.cooked = Some(Arc::new(Prefab::cook_prefab(
+                    &prefab,
+                    storage,
+                    component_registry,
+                )));
                 prefab.version += storage
                     .get_for_load_handle(*handle)
                     .map_or(1, |Prefab { version, .. }| *version + 1);
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, path::Path};
+
+use amethyst_config::{Config, ConfigFormat};
+use amethyst_core::{
+    ecs::{
+        query::{self, LayoutFilter},
+        world::EntityHasher,
+        Entity, IntoQuery, Resources, World,
+    },
+    transform::Parent,
+};
+use amethyst_error::{format_err, Error};
+use prefab_format::EntityUuid;
+use serde::{Deserialize, Serialize};
+
+use crate::prefab::ComponentRegistry;
+
+/// Version of the snapshots written by this version of Amethyst.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A copy of the entities of a `World`, which can be saved to a file and restored later, e.g. to
+/// implement save games.
+///
+/// Only the components of the `ComponentRegistry` are captured, and they are restored through
+/// its spawn mappings, just like the components of a `Prefab`. `Parent` references survive the
+/// round trip, as long as the parent is captured too.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst_assets::prefab::{ComponentRegistry, WorldSnapshot};
+/// use amethyst_config::ConfigFormat;
+/// use amethyst_core::ecs::{Resources, World};
+/// use amethyst_error::Error;
+///
+/// # fn save_and_load(world: &mut World, resources: &Resources) -> Result<(), Error> {
+/// let registry = resources.get::<ComponentRegistry>().unwrap();
+/// WorldSnapshot::capture(world, &registry).save("save.ron", ConfigFormat::Ron)?;
+///
+/// let snapshot = WorldSnapshot::load("save.ron")?;
+/// world.clear();
+/// snapshot.restore(world, resources, &registry);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    version: u32,
+    prefab: legion_prefab::Prefab,
+    // `Parent` doesn't survive prefab serialization, so the hierarchy is kept by entity uuid.
+    parents: Vec<(EntityUuid, EntityUuid)>,
+}
+
+impl WorldSnapshot {
+    /// Captures every entity of `world`.
+    #[must_use]
+    pub fn capture(world: &World, registry: &ComponentRegistry) -> Self {
+        Self::capture_filtered(world, &query::any(), registry)
+    }
+
+    /// Captures the entities of `world` matching `filter`, e.g. `component::<Persistent>()`.
+    ///
+    /// Captured entities whose parent doesn't match the filter lose their `Parent`.
+    #[must_use]
+    pub fn capture_filtered<F: LayoutFilter>(
+        world: &World,
+        filter: &F,
+        registry: &ComponentRegistry,
+    ) -> Self {
+        let mut captured = World::default();
+        let entity_map = captured.clone_from(world, filter, &mut registry.copy_clone_impl());
+
+        let hierarchy: Vec<(Entity, Option<Entity>)> = <(Entity, &Parent)>::query()
+            .iter(&captured)
+            .map(|(child, parent)| (*child, entity_map.get(&parent.0).copied()))
+            .collect();
+        for (child, _) in &hierarchy {
+            if let Some(mut entry) = captured.entry(*child) {
+                entry.remove_component::<Parent>();
+            }
+        }
+
+        let prefab = legion_prefab::Prefab::new(captured);
+        let uuids: HashMap<Entity, EntityUuid, EntityHasher> = prefab
+            .prefab_meta
+            .entities
+            .iter()
+            .map(|(uuid, entity)| (*entity, *uuid))
+            .collect();
+        let parents = hierarchy
+            .into_iter()
+            .filter_map(|(child, parent)| Some((uuids[&child], uuids[&parent?])))
+            .collect();
+
+        WorldSnapshot {
+            version: SNAPSHOT_VERSION,
+            prefab,
+            parents,
+        }
+    }
+
+    /// The version of Amethyst's snapshot format this snapshot was written with.
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The number of captured entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prefab.prefab_meta.entities.len()
+    }
+
+    /// Returns true if no entity was captured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Spawns the captured entities into `world`, and returns them.
+    ///
+    /// Entities are always spawned anew, so references to them held outside of the snapshot
+    /// don't point to the restored entities.
+    pub fn restore(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+        registry: &ComponentRegistry,
+    ) -> Vec<Entity> {
+        let entity_map = world.clone_from(
+            &self.prefab.world,
+            &query::any(),
+            &mut registry.spawn_clone_impl(resources, &HashMap::default()),
+        );
+
+        let restored = |uuid: &EntityUuid| {
+            self.prefab
+                .prefab_meta
+                .entities
+                .get(uuid)
+                .and_then(|entity| entity_map.get(entity))
+                .copied()
+        };
+        for (child, parent) in &self.parents {
+            match (restored(child), restored(parent)) {
+                (Some(child), Some(parent)) => {
+                    if let Some(mut entry) = world.entry(child) {
+                        entry.add_component(Parent(parent));
+                    }
+                }
+                _ => log::warn!("Snapshot hierarchy references an entity that was not captured"),
+            }
+        }
+
+        entity_map.values().copied().collect()
+    }
+
+    /// Saves the snapshot to `path` in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot can't be serialized or written.
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: ConfigFormat) -> Result<(), Error> {
+        self.write_format(format, path)?;
+        Ok(())
+    }
+
+    /// Loads a snapshot from `path`, in the format matching its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or deserialized, or if it was written by a newer
+    /// version of Amethyst.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        <Self as Config>::load(path)?.checked()
+    }
+
+    /// Loads a snapshot from bytes in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes can't be deserialized, or if they were written by a newer
+    /// version of Amethyst.
+    pub fn load_bytes(format: ConfigFormat, bytes: &[u8]) -> Result<Self, Error> {
+        <Self as Config>::load_bytes_format(format, bytes)?.checked()
+    }
+
+    fn checked(self) -> Result<Self, Error> {
+        if self.version > SNAPSHOT_VERSION {
+            Err(format_err!(
+                "Snapshot version {} is newer than the supported version {}",
+                self.version,
+                SNAPSHOT_VERSION
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use amethyst_core::transform::Transform;
+
+    use super::*;
+    use crate::prefab::ComponentRegistryBuilder;
+
+    fn registry() -> ComponentRegistry {
+        ComponentRegistryBuilder::default()
+            .auto_register_components()
+            .build()
+    }
+
+    #[test]
+    fn hierarchy_survives_round_trip() {
+        let registry = registry();
+        let mut world = World::default();
+        let parent = world.push((Transform::default(),));
+        let mut child_transform = Transform::default();
+        child_transform.set_translation_x(2.0);
+        world.push((child_transform, Parent(parent)));
+
+        let bytes = ron::ser::to_string(&WorldSnapshot::capture(&world, &registry)).unwrap();
+        let snapshot = WorldSnapshot::load_bytes(ConfigFormat::Ron, bytes.as_bytes()).unwrap();
+        assert_eq!(snapshot.len(), 2);
+
+        let mut restored = World::default();
+        let entities = snapshot.restore(&mut restored, &Resources::default(), &registry);
+        assert_eq!(entities.len(), 2);
+
+        let (child, parent) = <(&Transform, &Parent)>::query()
+            .iter(&restored)
+            .next()
+            .expect("child lost its parent");
+        assert!((child.translation().x - 2.0).abs() < f32::EPSILON);
+        assert!(entities.contains(&parent.0));
+        assert!(restored
+            .entry_ref(parent.0)
+            .unwrap()
+            .get_component::<Transform>()
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let mut snapshot = WorldSnapshot::capture(&World::default(), &registry());
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        let bytes = ron::ser::to_string(&snapshot).unwrap();
+        assert!(WorldSnapshot::load_bytes(ConfigFormat::Ron, bytes.as_bytes()).is_err());
+    }
+}
@@ -1,20 +1,25 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::HashMap, sync::Arc};
 
 use amethyst_core::ecs::{
-    query, world::EntityHasher, Entity, IntoQuery, Resources, TryWrite, World,
+    query, world::EntityHasher, Entity, IntoQuery, Resources, TryRead, World,
 };
+use legion_prefab::CookedPrefab;
 
 use crate::{
-    prefab::{ComponentRegistry, Prefab},
+    prefab::{ComponentRegistry, Prefab, PrefabOverrides},
     AssetStorage, Handle,
 };
 
 struct PrefabInstance {
+    /// The version of the prefab the instance was spawned from or last patched with.
+    base: Arc<CookedPrefab>,
     version: u32,
+    /// Maps the entities of `base` to the live entities.
     entity_map: HashMap<Entity, Entity, EntityHasher>,
 }
 
-/// Attaches prefabs to entities that have Handle<Prefab>
+/// Attaches prefabs to entities that have Handle<Prefab>, and patches the entities spawned from
+/// a prefab when a new version of it is loaded.
 pub fn prefab_spawning_tick(world: &mut World, resources: &mut Resources) {
     let component_registry = resources
         .get::<ComponentRegistry>()
@@ -23,65 +28,88 @@ pub fn prefab_spawning_tick(world: &mut World, resources: &mut Resources) {
         .get::<AssetStorage<Prefab>>()
         .expect("AssetStorage<Prefab> can not be retrieved from ECS Resources");
 
-    let mut prefabs: Vec<(
-        Entity,
-        &legion_prefab::CookedPrefab,
-        u32,
-        HashMap<Entity, Entity, EntityHasher>,
-    )> = Vec::new();
-
-    let mut entity_query = <(Entity,)>::query();
+    let mut spawns = Vec::new();
+    let mut patches = Vec::new();
 
-    <(Entity, &Handle<Prefab>, TryWrite<PrefabInstance>)>::query().for_each_mut(
-        world,
-        |(entity, handle, instance)| {
-            if let Some(Prefab {
-                cooked: Some(cooked_prefab),
-                version: prefab_version,
-                ..
-            }) = prefab_storage.get(handle)
-            {
-                let instance_version = instance.as_ref().map_or(0, |instance| instance.version);
-                if instance_version < *prefab_version {
-                    let mut entity_map = instance
-                        .as_ref()
-                        .map(|instance| instance.entity_map.clone())
-                        .unwrap_or_default();
-                    if entity_map.is_empty() {
-                        if let Some((root_entity,)) = entity_query.iter(&cooked_prefab.world).next()
-                        {
-                            entity_map.insert(*root_entity, *entity);
-                        }
-                    }
-                    prefabs.push((*entity, cooked_prefab, *prefab_version, entity_map));
-                }
+    <(
+        Entity,
+        &Handle<Prefab>,
+        TryRead<PrefabInstance>,
+        TryRead<PrefabOverrides>,
+    )>::query()
+    .for_each(world, |(entity, handle, instance, overrides)| {
+        if let Some(Prefab {
+            cooked: Some(cooked_prefab),
+            version: prefab_version,
+            ..
+        }) = prefab_storage.get(handle)
+        {
+            match instance {
+                None => spawns.push((
+                    *entity,
+                    handle.clone(),
+                    Arc::clone(cooked_prefab),
+                    *prefab_version,
+                    overrides.cloned(),
+                )),
+                Some(instance) if instance.version < *prefab_version => patches.push((
+                    *entity,
+                    Arc::clone(cooked_prefab),
+                    *prefab_version,
+                    Arc::clone(&instance.base),
+                    instance.entity_map.clone(),
+                )),
+                Some(_) => {}
             }
-        },
-    );
+        }
+    });
 
-    for (entity, prefab, version, prev_entity_map) in prefabs {
+    let mut entity_query = <(Entity,)>::query();
+    for (entity, handle, prefab, version, overrides) in spawns {
+        let mut root_map = HashMap::default();
+        if let Some((root_entity,)) = entity_query.iter(&prefab.world).next() {
+            root_map.insert(*root_entity, entity);
+        }
         let entity_map = world.clone_from(
             &prefab.world,
             &query::any(),
-            &mut component_registry.spawn_clone_impl(resources, &prev_entity_map),
+            &mut component_registry.spawn_clone_impl(resources, &root_map),
         );
 
-        let live_entities: HashSet<Entity, EntityHasher> = entity_map.values().copied().collect();
-        let prev_entities: HashSet<_, _> = prev_entity_map.values().copied().collect();
-
-        log::debug!("new entity_map: {:?}", entity_map);
-        log::debug!("old entity map: {:?}", prev_entity_map);
+        log::debug!("Spawn for {:?}: {:?}", entity, entity_map);
 
-        for value in prev_entities.difference(&live_entities).copied() {
-            if world.remove(value) {
-                log::debug!("Removed entity {:?}", value);
+        if let Some(mut entry) = world.entry(entity) {
+            entry.add_component(handle);
+            entry.add_component(PrefabInstance {
+                base: prefab,
+                version,
+                entity_map,
+            });
+            if let Some(overrides) = overrides {
+                overrides.apply(&mut entry);
+                entry.add_component(overrides);
             }
+        } else {
+            log::error!("Could not update entity");
         }
+    }
+
+    for (entity, prefab, version, base, entity_map) in patches {
+        log::debug!("Patch for {:?} to version {}", entity, version);
 
-        log::debug!("Spawn for {:?}", entity);
+        let entity_map = patch_instance(
+            world,
+            resources,
+            &component_registry,
+            entity,
+            &base,
+            &prefab,
+            entity_map,
+        );
 
         if let Some(mut entry) = world.entry(entity) {
             entry.add_component(PrefabInstance {
+                base: prefab,
                 version,
                 entity_map,
             });
@@ -90,3 +118,54 @@ pub fn prefab_spawning_tick(world: &mut World, resources: &mut Resources) {
         }
     }
 }
+
+/// Applies the changes between two versions of a prefab to the live entities of an instance, and
+/// returns the map from the entities of the new version to the live entities.
+///
+/// Entities added to the prefab are spawned, entities removed from it are removed from the world,
+/// except for the root of the instance.
+fn patch_instance(
+    world: &mut World,
+    resources: &Resources,
+    component_registry: &ComponentRegistry,
+    root: Entity,
+    base: &CookedPrefab,
+    prefab: &CookedPrefab,
+    mut entity_map: HashMap<Entity, Entity, EntityHasher>,
+) -> HashMap<Entity, Entity, EntityHasher> {
+    let mut patched = HashMap::default();
+
+    for (uuid, new_entity) in &prefab.entities {
+        let live = base
+            .entities
+            .get(uuid)
+            .and_then(|base_entity| Some((*base_entity, entity_map.remove(base_entity)?)));
+
+        if let Some((base_entity, live)) = live {
+            if let (Ok(base_entry), Ok(new_entry), Some(mut live_entry)) = (
+                base.world.entry_ref(base_entity),
+                prefab.world.entry_ref(*new_entity),
+                world.entry(live),
+            ) {
+                component_registry.patch(&base_entry, &new_entry, &mut live_entry);
+            }
+            patched.insert(*new_entity, live);
+        } else {
+            let spawned = world.clone_from_single(
+                &prefab.world,
+                *new_entity,
+                &mut component_registry.spawn_clone_impl(resources, &HashMap::default()),
+            );
+            log::debug!("Spawned entity {:?} added to the prefab", spawned);
+            patched.insert(*new_entity, spawned);
+        }
+    }
+
+    for removed in entity_map.values().copied().filter(|live| *live != root) {
+        if world.remove(removed) {
+            log::debug!("Removed entity {:?} removed from the prefab", removed);
+        }
+    }
+
+    patched
+}
@@ -7,6 +7,7 @@ use std::{
 use amethyst_core::{
     dispatcher::System,
     ecs::{systems::ParallelRunnable, SystemBuilder},
+    shrev::EventChannel,
 };
 use amethyst_error::Error;
 use crossbeam_queue::SegQueue;
@@ -16,6 +17,7 @@ use log::debug;
 
 use crate::{
     asset::{Asset, ProcessableAsset},
+    event::AssetEvent,
     loader::LoadHandle,
     progress::Tracker,
     storage::AssetStorage,
@@ -27,6 +29,8 @@ use crate::{
 ///
 /// This system can only be used if the asset data implements
 /// `Into<Result<A, BoxedErr>>`.
+///
+/// The events of the storage are published to the `EventChannel<AssetEvent<A>>`.
 #[derive(Derivative)]
 #[derivative(Default(bound = ""))]
 pub struct AssetProcessorSystem<A> {
@@ -42,11 +46,13 @@ where
             SystemBuilder::new(format!("Asset Processor: {}", A::name()))
                 .write_resource::<ProcessingQueue<A::Data>>()
                 .write_resource::<AssetStorage<A>>()
-                .build(|_, _, (queue, storage), _| {
+                .write_resource::<EventChannel<AssetEvent<A>>>()
+                .build(|_, _, (queue, storage, events), _| {
                     // drain the changed queue
                     while queue.changed.pop().is_some() {}
                     queue.process(storage, ProcessableAsset::process);
                     storage.process_custom_drop(|_| {});
+                    events.iter_write(storage.drain_events());
                 }),
         )
     }
@@ -137,12 +143,12 @@ impl<T> ProcessingQueue<T> {
     pub(crate) fn enqueue(
         &self,
         handle: LoadHandle,
-        data: T,
+        data: Result<T, Error>,
         asset_load_op: AssetLoadOp,
         version: u32,
     ) {
         self.enqueue_processed(
-            Ok(data),
+            data,
             handle,
             LoadNotifier::new(handle, Some(asset_load_op), None),
             version,
@@ -223,6 +229,7 @@ impl<T> ProcessingQueue<T> {
                     continue;
                 }
                 Err(e) => {
+                    storage.failed(handle, e.to_string());
                     load_notifier.error(e);
                     continue;
                 }
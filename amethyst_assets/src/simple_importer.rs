@@ -1,12 +1,16 @@
-use std::io::Read;
+use std::{any::Any, io::Read};
 
+use amethyst_error::{Error, ResultExt};
 use distill::importer::{
     self as importer, BoxedImporter, ImportOp, ImportedAsset, Importer, ImporterValue, SerdeObj,
 };
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
 
-use crate::{AssetUuid, Format};
+use crate::{
+    build::{run_build_steps, ImportOptions},
+    error, AssetUuid, Format,
+};
 
 /// A simple state for Importer to retain the same UUID between imports
 /// for all single-asset source files
@@ -42,13 +46,13 @@ where
     where
         Self: Sized,
     {
-        1
+        2
     }
     fn version(&self) -> u32 {
         Self::version_static()
     }
 
-    type Options = T;
+    type Options = ImportOptions<T>;
     type State = SimpleImporterState;
 
     fn import(
@@ -64,7 +68,9 @@ where
         let mut bytes = Vec::new();
         source.read_to_end(&mut bytes)?;
         let import_result = options
+            .format
             .import_simple(bytes)
+            .and_then(|data| run_build_steps(data, &options.build_steps))
             .map_err(|e| importer::Error::Boxed(e.into_error()))?;
         Ok(ImporterValue {
             assets: vec![ImportedAsset {
@@ -88,6 +94,107 @@ pub struct SourceFileImporter {
 }
 inventory::collect!(SourceFileImporter);
 
+/// The `Format` registered with `register_importer!` for a file extension, to import bytes at
+/// runtime with `Loader::load_from_bytes`.
+#[derive(Debug)]
+pub struct FormatRegistration {
+    extension: &'static str,
+    // Creates the format as a `Box<dyn Format<D>>`.
+    create: fn() -> Box<dyn Any + Send + Sync>,
+}
+inventory::collect!(FormatRegistration);
+
+impl FormatRegistration {
+    /// Registers a default `F` for the file extension.
+    ///
+    /// This function is not intended to be called directly. Use the `register_importer!` macro
+    /// instead.
+    #[must_use]
+    pub fn new<D: 'static, F: Format<D> + Default>(extension: &'static str) -> Self {
+        FormatRegistration {
+            extension,
+            create: erase_format::<D, F>,
+        }
+    }
+
+    /// File extension of the format, as given to `register_importer!`.
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        self.extension
+    }
+}
+
+fn erase_format<D: 'static, F: Format<D> + Default>() -> Box<dyn Any + Send + Sync> {
+    let format: Box<dyn Format<D>> = Box::new(F::default());
+    Box::new(format)
+}
+
+/// How `Loader::load_from_bytes` imports bytes into asset data `D`.
+pub enum BytesFormat<D> {
+    /// The format registered with `register_importer!` for a file extension, such as `"png"`.
+    Extension(String),
+    /// The given format.
+    Format(Box<dyn Format<D>>),
+}
+
+impl<D: 'static> BytesFormat<D> {
+    /// Imports `bytes` with the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no format producing `D` is registered for the extension, or if the
+    /// format can't import the bytes.
+    pub fn import(self, bytes: Vec<u8>) -> Result<D, Error> {
+        let format = match self {
+            BytesFormat::Extension(extension) => find_format(&extension)?,
+            BytesFormat::Format(format) => format,
+        };
+        format
+            .import_simple(bytes)
+            .with_context(|_| error::Error::Format(format.name()))
+    }
+}
+
+impl<D> From<&str> for BytesFormat<D> {
+    fn from(extension: &str) -> Self {
+        BytesFormat::Extension(extension.to_string())
+    }
+}
+
+impl<D> From<String> for BytesFormat<D> {
+    fn from(extension: String) -> Self {
+        BytesFormat::Extension(extension)
+    }
+}
+
+impl<D> std::fmt::Debug for BytesFormat<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BytesFormat::Extension(extension) => {
+                f.debug_tuple("Extension").field(extension).finish()
+            }
+            BytesFormat::Format(format) => f.debug_tuple("Format").field(&format.name()).finish(),
+        }
+    }
+}
+
+/// Finds a registered format producing `D` for the extension, with or without the leading `.`.
+fn find_format<D: 'static>(extension: &str) -> Result<Box<dyn Format<D>>, Error> {
+    let extension = extension.trim_start_matches('.');
+    inventory::iter::<FormatRegistration>
+        .into_iter()
+        .filter(|registration| {
+            registration
+                .extension
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(extension)
+        })
+        .map(|registration| registration.create)
+        .find_map(|create| create().downcast::<Box<dyn Format<D>>>().ok())
+        .map(|format| *format)
+        .ok_or_else(|| error::Error::NoFormat(extension.to_string()).into())
+}
+
 /// Get the registered importers and their associated extension.
 #[allow(dead_code)]
 pub fn get_source_importers(
@@ -145,5 +252,33 @@ macro_rules! register_importer {
                 instantiator: || Box::new($crate::SimpleImporter::from(<$format as Default>::default())),
             }
         }
+        $crate::inventory::submit!{
+            #![crate = $krate]
+            $crate::FormatRegistration::new::<_, $format>($ext)
+        }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Utf8Format;
+
+    #[test]
+    fn imports_bytes_with_registered_format() {
+        let imported = BytesFormat::from("UTF8TEST").import(b"text".to_vec());
+        assert_eq!(imported.unwrap(), "text");
+        let imported = BytesFormat::Format(Box::new(Utf8Format)).import(b"text".to_vec());
+        assert_eq!(imported.unwrap(), "text");
+
+        assert!(BytesFormat::<String>::from(".utf8test")
+            .import(vec![0xff])
+            .is_err());
+        assert!(BytesFormat::<Vec<u8>>::from("utf8test")
+            .import(Vec::new())
+            .is_err());
+        assert!(BytesFormat::<String>::from("unknown")
+            .import(Vec::new())
+            .is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+
+use amethyst_error::{format_err, Error, ResultExt};
+use parking_lot::Mutex;
+use zip::{DateTime, ZipArchive};
+
+use crate::{error, source::Source};
+
+/// Zip archive source.
+///
+/// Paths inside the archive use `/` as separator, just like asset paths, so the assets are found
+/// at the same paths as in the directory the archive was made from.
+pub struct ZipSource<R = File> {
+    archive: Mutex<ZipArchive<R>>,
+}
+
+impl ZipSource<File> {
+    /// Opens the zip archive at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened or isn't a zip archive.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|_| format_err!("Failed to open zip archive {:?}", path))?;
+        Self::new(file)
+    }
+}
+
+impl<R> ZipSource<R>
+where
+    R: Read + Seek + Send + 'static,
+{
+    /// Reads a zip archive, e.g. from a `Cursor` over bytes embedded in the executable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader doesn't contain a zip archive.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        Ok(ZipSource {
+            archive: Mutex::new(ZipArchive::new(reader)?),
+        })
+    }
+}
+
+impl<R> std::fmt::Debug for ZipSource<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZipSource").finish()
+    }
+}
+
+impl<R> Source for ZipSource<R>
+where
+    R: Read + Seek + Send + 'static,
+{
+    fn modified(&self, path: &str) -> Result<u64, Error> {
+        let mut archive = self.archive.lock();
+        let file = archive
+            .by_name(path)
+            .with_context(|_| format_err!("Failed to fetch metadata for {:?}", path))?;
+        Ok(unix_seconds(file.last_modified()))
+    }
+
+    fn load(&self, path: &str) -> Result<Vec<u8>, Error> {
+        let mut archive = self.archive.lock();
+        let mut file = archive
+            .by_name(path)
+            .with_context(|_| format_err!("Failed to open file {:?}", path))
+            .with_context(|_| error::Error::Source)?;
+
+        let mut v = Vec::new();
+        file.read_to_end(&mut v)
+            .with_context(|_| format_err!("Failed to read file {:?}", path))
+            .with_context(|_| error::Error::Source)?;
+
+        Ok(v)
+    }
+}
+
+/// Converts the MS-DOS timestamp of a zip entry to seconds since `UNIX_EPOCH`.
+fn unix_seconds(time: DateTime) -> u64 {
+    // Days from the civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let (month, day) = (u64::from(time.month()), u64::from(time.day()));
+    let year = u64::from(time.year()) - if month <= 2 { 1 } else { 0 };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 86_400
+        + u64::from(time.hour()) * 3_600
+        + u64::from(time.minute()) * 60
+        + u64::from(time.second())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::{write::FileOptions, ZipWriter};
+
+    use super::*;
+
+    fn archive() -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default()
+            .last_modified_time(DateTime::from_date_and_time(2020, 3, 1, 12, 30, 10).unwrap());
+        writer.start_file("subdir/asset", options).unwrap();
+        writer.write_all(b"data").unwrap();
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn loads_asset_from_archive() {
+        let source = ZipSource::new(archive()).unwrap();
+        assert_eq!(source.load("subdir/asset").unwrap(), b"data".to_vec());
+        assert!(source.load("subdir/missing").is_err());
+    }
+
+    #[test]
+    fn modified_is_unix_time() {
+        let source = ZipSource::new(archive()).unwrap();
+        assert_eq!(source.modified("subdir/asset").unwrap(), 1_583_065_810);
+    }
+}
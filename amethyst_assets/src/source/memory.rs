@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use amethyst_error::{format_err, Error};
+
+use crate::source::Source;
+
+/// Source keeping the bytes of its assets in memory, e.g. for tests or generated assets.
+#[derive(Debug, Default)]
+pub struct InMemorySource {
+    assets: HashMap<String, Vec<u8>>,
+}
+
+impl InMemorySource {
+    /// Creates an empty source.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an asset to the source, replacing the asset previously stored at `path`.
+    pub fn insert<P: Into<String>>(&mut self, path: P, bytes: Vec<u8>) -> Option<Vec<u8>> {
+        self.assets.insert(path.into(), bytes)
+    }
+
+    /// Removes the asset stored at `path`.
+    pub fn remove(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.assets.remove(path)
+    }
+
+    /// Adds an asset to the source, returning it to chain calls.
+    #[must_use]
+    pub fn with<P: Into<String>>(mut self, path: P, bytes: Vec<u8>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+}
+
+impl Source for InMemorySource {
+    fn modified(&self, path: &str) -> Result<u64, Error> {
+        if self.assets.contains_key(path) {
+            Ok(0)
+        } else {
+            Err(not_found(path))
+        }
+    }
+
+    fn load(&self, path: &str) -> Result<Vec<u8>, Error> {
+        self.assets
+            .get(path)
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+}
+
+fn not_found(path: &str) -> Error {
+    format_err!(
+        "The `{}` asset is not registered in the `InMemorySource` asset source",
+        path
+    )
+}
@@ -2,9 +2,21 @@ use amethyst_error::Error;
 #[cfg(feature = "profiler")]
 use thread_profiler::profile_scope;
 
-pub use self::dir::Directory;
+pub use self::{
+    dir::Directory,
+    memory::InMemorySource,
+    overlay::Overlay,
+    registry::{Sources, DEFAULT_SOURCE},
+};
+#[cfg(feature = "zip")]
+pub use self::archive::ZipSource;
 
+#[cfg(feature = "zip")]
+mod archive;
 mod dir;
+mod memory;
+mod overlay;
+mod registry;
 
 /// A trait for asset sources, which provides
 /// methods for loading bytes.
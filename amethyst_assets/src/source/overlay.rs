@@ -0,0 +1,103 @@
+use amethyst_error::{format_err, Error};
+
+use crate::source::Source;
+
+/// Source made of layers of other sources, each layer shadowing the assets of the layers below.
+///
+/// This lets mods replace some of the assets a game loads with `Loader::load_from`: the base
+/// assets are the bottom layer, and the directory of each mod a layer on top of it. Assets
+/// imported by the asset daemon are not affected.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_assets::{Directory, Overlay};
+///
+/// let assets = Overlay::new(Directory::new("assets"))
+///     .with_layer(Directory::new("mods/hd_textures"))
+///     .with_layer(Directory::new("mods/translation"));
+/// ```
+pub struct Overlay {
+    // Bottom layer first.
+    layers: Vec<Box<dyn Source>>,
+}
+
+impl Overlay {
+    /// Creates an overlay with `base` as its bottom layer.
+    #[must_use]
+    pub fn new<S: Source>(base: S) -> Self {
+        Overlay {
+            layers: vec![Box::new(base)],
+        }
+    }
+
+    /// Adds a layer on top of the others.
+    #[must_use]
+    pub fn with_layer<S: Source>(mut self, layer: S) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    /// Adds a layer on top of the others.
+    pub fn add_layer<S: Source>(&mut self, layer: S) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Returns the result of `f` for the topmost layer it succeeds for.
+    fn find<T, F>(&self, path: &str, f: F) -> Result<T, Error>
+    where
+        F: Fn(&dyn Source) -> Result<T, Error>,
+    {
+        let mut last_error = None;
+        for layer in self.layers.iter().rev() {
+            match f(layer.as_ref()) {
+                Ok(value) => return Ok(value),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| format_err!("No layer of the overlay contains {:?}", path)))
+    }
+}
+
+impl std::fmt::Debug for Overlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Overlay")
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl Source for Overlay {
+    fn modified(&self, path: &str) -> Result<u64, Error> {
+        self.find(path, |layer| layer.modified(path))
+    }
+
+    fn load(&self, path: &str) -> Result<Vec<u8>, Error> {
+        self.find(path, |layer| layer.load(path))
+    }
+
+    fn load_with_metadata(&self, path: &str) -> Result<(Vec<u8>, u64), Error> {
+        self.find(path, |layer| layer.load_with_metadata(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::InMemorySource;
+
+    #[test]
+    fn top_layer_shadows_base() {
+        let overlay = Overlay::new(
+            InMemorySource::new()
+                .with("texture.png", b"base".to_vec())
+                .with("mesh.obj", b"mesh".to_vec()),
+        )
+        .with_layer(InMemorySource::new().with("texture.png", b"mod".to_vec()));
+
+        assert_eq!(overlay.load("texture.png").unwrap(), b"mod".to_vec());
+        assert_eq!(overlay.load("mesh.obj").unwrap(), b"mesh".to_vec());
+        assert!(overlay.load("missing.png").is_err());
+    }
+}
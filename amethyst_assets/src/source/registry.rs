@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use amethyst_error::{format_err, Error, ResultExt};
+use fnv::FnvHashMap;
+
+use crate::{error, source::Source, Format};
+
+/// Name of the source assets are loaded from when no other source is selected.
+pub const DEFAULT_SOURCE: &str = "default";
+
+/// Resource registering asset sources by name, so assets can be loaded from a specific source
+/// with `Loader::load_from`.
+///
+/// The sources are only read by `Loader::load_from`, the asset daemon imports its own asset
+/// directories.
+///
+/// `ApplicationBuilder` registers the asset directory of the application as the
+/// [`DEFAULT_SOURCE`], sources are added with `ApplicationBuilder::with_source`.
+#[derive(Clone, Default)]
+pub struct Sources {
+    sources: FnvHashMap<String, Arc<dyn Source>>,
+}
+
+impl Sources {
+    /// Creates a registry with `source` as its default source.
+    #[must_use]
+    pub fn new<S: Source>(source: S) -> Self {
+        let mut sources = Sources::default();
+        sources.set_default(source);
+        sources
+    }
+
+    /// Registers `source` under `name`, replacing the source previously registered there.
+    pub fn insert<N: Into<String>, S: Source>(&mut self, name: N, source: S) {
+        self.sources.insert(name.into(), Arc::new(source));
+    }
+
+    /// Registers `source` as the default source.
+    pub fn set_default<S: Source>(&mut self, source: S) {
+        self.insert(DEFAULT_SOURCE, source);
+    }
+
+    /// Returns the source registered under `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&dyn Source> {
+        self.sources.get(name).map(|source| source.as_ref())
+    }
+
+    /// Returns true if a source is registered under `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.sources.contains_key(name)
+    }
+
+    /// Loads the bytes at `path` in the source registered under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such source, or if it fails to load the bytes.
+    pub fn load(&self, name: &str, path: &str) -> Result<Vec<u8>, Error> {
+        self.get(name)
+            .ok_or_else(|| format_err!("No asset source is registered as {:?}", name))?
+            .load(path)
+    }
+
+    /// Loads the bytes at `path` in the source registered under `name`, and imports them with
+    /// `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes can't be loaded, or if the format fails to import them.
+    pub fn load_data<D, F>(&self, name: &str, path: &str, format: &F) -> Result<D, Error>
+    where
+        D: 'static,
+        F: Format<D>,
+    {
+        let bytes = self.load(name, path)?;
+        format
+            .import_simple(bytes)
+            .with_context(|_| error::Error::Format(format.name()))
+            .with_context(|_| error::Error::Asset(path.to_string()))
+    }
+}
+
+impl std::fmt::Debug for Sources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sources")
+            .field("sources", &self.sources.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{source::InMemorySource, test_util::Utf8Format};
+
+    #[test]
+    fn loads_from_named_source() {
+        let mut sources = Sources::new(InMemorySource::new().with("greeting", b"hello".to_vec()));
+        sources.insert(
+            "mod",
+            InMemorySource::new().with("greeting", b"bonjour".to_vec()),
+        );
+
+        let load = |name| sources.load_data(name, "greeting", &Utf8Format).unwrap();
+        assert_eq!(load(DEFAULT_SOURCE), "hello");
+        assert_eq!(load("mod"), "bonjour");
+        assert!(sources.load("missing", "greeting").is_err());
+    }
+}
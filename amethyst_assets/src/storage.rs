@@ -1,10 +1,32 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    vec::Drain,
+};
+
 use crossbeam_queue::SegQueue;
 use distill::loader::{handle::AssetHandle, storage::IndirectionTable, LoadHandle};
 use fnv::FnvHashMap;
 
+use crate::{
+    event::{AssetEvent, AssetEventKind},
+    Asset,
+};
+
 struct AssetState<A> {
     version: u32,
     asset: A,
+    /// Value of the storage clock when the asset was last read.
+    last_used: AtomicU64,
+}
+
+impl<A> AssetState<A> {
+    fn new(version: u32, asset: A) -> Self {
+        AssetState {
+            version,
+            asset,
+            last_used: AtomicU64::new(0),
+        }
+    }
 }
 
 /// An asset storage, storing the actual assets
@@ -17,6 +39,10 @@ pub struct AssetStorage<A> {
     uncommitted: FnvHashMap<LoadHandle, AssetState<A>>,
     to_drop: SegQueue<A>,
     indirection_table: IndirectionTable,
+    clock: u64,
+    events: Vec<AssetEvent<A>>,
+    /// Sizes reported by the processing systems, for assets which can't report their own.
+    sizes: FnvHashMap<LoadHandle, usize>,
 }
 
 impl<A> AssetStorage<A> {
@@ -28,6 +54,9 @@ impl<A> AssetStorage<A> {
             uncommitted: std::collections::HashMap::default(),
             to_drop: SegQueue::new(),
             indirection_table,
+            clock: 0,
+            events: Vec::new(),
+            sizes: FnvHashMap::default(),
         }
     }
 
@@ -36,8 +65,11 @@ impl<A> AssetStorage<A> {
         for (_, data) in self.uncommitted.drain() {
             self.to_drop.push(data.asset);
         }
-        for (_, data) in self.assets.drain() {
+        self.sizes.clear();
+        for (handle, data) in self.assets.drain() {
             self.to_drop.push(data.asset);
+            self.events
+                .push(AssetEvent::new(handle, AssetEventKind::Unloaded));
         }
     }
 
@@ -45,7 +77,7 @@ impl<A> AssetStorage<A> {
         log::debug!("Updating Asset {:?}", handle);
         if let Some(data) = self
             .uncommitted
-            .insert(handle, AssetState { version, asset })
+            .insert(handle, AssetState::new(version, asset))
         {
             self.to_drop.push(data.asset);
         }
@@ -62,29 +94,66 @@ impl<A> AssetStorage<A> {
             if data.version == version {
                 self.to_drop
                     .push(self.assets.remove(&handle).unwrap().asset);
+                self.sizes.remove(&handle);
+                self.events
+                    .push(AssetEvent::new(handle, AssetEventKind::Unloaded));
             }
         }
     }
 
+    /// Unloads the committed version of an asset, whichever it is.
+    pub(crate) fn unload(&mut self, handle: LoadHandle) {
+        if let Some(version) = self.assets.get(&handle).map(|data| data.version) {
+            self.remove_asset(handle, version);
+        }
+    }
+
+    /// Records the memory used by the asset of `handle`, for asset types whose
+    /// `Asset::size_in_bytes` can't tell, e.g. because the data the size is computed from is
+    /// consumed by the processing system.
+    ///
+    /// The size is forgotten when the asset is unloaded.
+    pub fn set_size_in_bytes(&mut self, handle: LoadHandle, size_in_bytes: usize) {
+        self.sizes.insert(handle, size_in_bytes);
+    }
+
     pub(crate) fn commit_asset(&mut self, handle: LoadHandle, version: u32) {
         if let Some(data) = self.uncommitted.remove(&handle) {
             assert!(data.version == version, "attempted to commit asset version which mismatches with existing uncommitted version");
 
-            if let Some(existing) = self.assets.insert(
-                handle,
-                AssetState {
-                    version,
-                    asset: data.asset,
-                },
-            ) {
+            // The new version keeps the recency of the previous one.
+            let last_used = self.assets.get(&handle).map_or(self.clock, |existing| {
+                existing.last_used.load(Ordering::Relaxed)
+            });
+            let state = AssetState::new(version, data.asset);
+            state.last_used.store(last_used, Ordering::Relaxed);
+            let kind = if let Some(existing) = self.assets.insert(handle, state) {
                 // data already exists for the handle, drop it
                 self.to_drop.push(existing.asset);
+                AssetEventKind::Reloaded { version }
+            } else {
+                AssetEventKind::Loaded { version }
             };
+            self.events.push(AssetEvent::new(handle, kind));
         } else {
             panic!("attempted to commit asset which doesn't exist");
         }
     }
 
+    /// Records that the data of an asset could not be turned into an asset.
+    pub(crate) fn failed(&mut self, handle: LoadHandle, error: String) {
+        self.events
+            .push(AssetEvent::new(handle, AssetEventKind::Failed { error }));
+    }
+
+    /// Removes and returns the events recorded since the last call, oldest first.
+    ///
+    /// The `AssetProcessorSystem` publishes them to the `EventChannel<AssetEvent<A>>`. Custom
+    /// processing systems should do the same, or the events accumulate.
+    pub fn drain_events(&mut self) -> Drain<'_, AssetEvent<A>> {
+        self.events.drain(..)
+    }
+
     /// returns true when asset is loaded for this handle
     pub fn contains(&self, load_handle: LoadHandle) -> bool {
         let load_handle = if load_handle.is_indirect() {
@@ -99,13 +168,36 @@ impl<A> AssetStorage<A> {
         self.assets.contains_key(&load_handle)
     }
 
-    fn get_asset_state(&self, load_handle: LoadHandle) -> Option<&AssetState<A>> {
-        let load_handle = if load_handle.is_indirect() {
-            self.indirection_table.resolve(load_handle)?
+    /// Returns the direct load handle of the asset, resolving indirect handles.
+    pub(crate) fn resolve(&self, load_handle: LoadHandle) -> Option<LoadHandle> {
+        if load_handle.is_indirect() {
+            self.indirection_table.resolve(load_handle)
         } else {
-            load_handle
-        };
-        self.assets.get(&load_handle)
+            Some(load_handle)
+        }
+    }
+
+    fn get_asset_state(&self, load_handle: LoadHandle) -> Option<&AssetState<A>> {
+        let state = self.assets.get(&self.resolve(load_handle)?)?;
+        state.last_used.store(self.clock, Ordering::Relaxed);
+        Some(state)
+    }
+
+    /// Advances the clock the recency of assets is measured with, usually once per frame.
+    pub fn tick(&mut self) {
+        self.clock += 1;
+    }
+
+    /// Returns the number of ticks since the asset was last read, or `None` if it isn't loaded.
+    ///
+    /// # Parameters
+    ///
+    /// * `load_handle`: `LoadHandle` of the asset.
+    #[must_use]
+    pub fn ticks_since_use(&self, load_handle: LoadHandle) -> Option<u64> {
+        self.assets
+            .get(&self.resolve(load_handle)?)
+            .map(|state| self.clock - state.last_used.load(Ordering::Relaxed))
     }
 
     /// Returns the asset for the given load handle, or `None` if has not completed loading.
@@ -179,6 +271,36 @@ impl<A> AssetStorage<A> {
     }
 }
 
+impl<A: Asset> AssetStorage<A> {
+    /// Returns the memory used by the loaded assets, as reported by `Asset::size_in_bytes` or
+    /// `set_size_in_bytes`.
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.assets
+            .iter()
+            .filter_map(|(handle, state)| self.state_size_in_bytes(*handle, state))
+            .sum()
+    }
+
+    /// Returns the memory used by the asset, if it is loaded and reports its size.
+    ///
+    /// # Parameters
+    ///
+    /// * `load_handle`: `LoadHandle` of the asset.
+    #[must_use]
+    pub fn size_in_bytes(&self, load_handle: LoadHandle) -> Option<usize> {
+        let handle = self.resolve(load_handle)?;
+        self.state_size_in_bytes(handle, self.assets.get(&handle)?)
+    }
+
+    fn state_size_in_bytes(&self, handle: LoadHandle, state: &AssetState<A>) -> Option<usize> {
+        state
+            .asset
+            .size_in_bytes()
+            .or_else(|| self.sizes.get(&handle).copied())
+    }
+}
+
 impl<A> distill::loader::handle::TypedAssetStorage<A> for AssetStorage<A> {
     fn get<T: AssetHandle>(&self, handle: &T) -> Option<&A> {
         self.get(handle)
@@ -0,0 +1,33 @@
+//! Helpers shared by the unit tests of the crate.
+
+use amethyst_error::Error;
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+
+use crate::{Format, FormatRegistration, SimpleImporter, SourceFileImporter};
+
+/// Imports UTF-8 text, registered for the `.utf8test` extension.
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9009acb5-d044-4032-91ce-0577586b896d"]
+pub(crate) struct Utf8Format;
+
+impl Format<String> for Utf8Format {
+    fn name(&self) -> &'static str {
+        "UTF8"
+    }
+
+    fn import_simple(&self, bytes: Vec<u8>) -> Result<String, Error> {
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+inventory::submit! {
+    SourceFileImporter {
+        extension: ".utf8test",
+        instantiator: || Box::new(SimpleImporter::from(Utf8Format)),
+    }
+}
+
+inventory::submit! {
+    FormatRegistration::new::<_, Utf8Format>(".utf8test")
+}
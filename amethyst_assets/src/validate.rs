@@ -0,0 +1,603 @@
+//! Offline checks of asset directories, to catch broken assets without running the game.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    ffi::OsString,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use amethyst_error::{Error, ResultExt};
+use distill::importer::Importer;
+use fnv::FnvHashMap;
+use ron::Value;
+#[cfg(feature = "validate")]
+use structopt::StructOpt;
+use uuid::Uuid;
+
+use crate::{prefab::PrefabImporter, simple_importer::get_source_importers};
+
+/// A problem found by `validate_assets`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetProblem {
+    /// No importer is registered for the extension of the file, so it is never imported.
+    NoImporter {
+        /// The file.
+        path: PathBuf,
+    },
+    /// The file or its `.meta` file can't be read or parsed.
+    Unreadable {
+        /// The file.
+        path: PathBuf,
+        /// Why the file can't be read.
+        error: String,
+    },
+    /// The `.meta` file belongs to a source file which doesn't exist anymore.
+    OrphanedMeta {
+        /// The `.meta` file.
+        path: PathBuf,
+    },
+    /// The `.meta` file was written by another version of the importer of its source file.
+    OutdatedMeta {
+        /// The `.meta` file.
+        path: PathBuf,
+        /// The importer version written in the `.meta` file.
+        meta_version: u32,
+        /// The version of the registered importer.
+        importer_version: u32,
+    },
+    /// Several source files declare the same asset UUID.
+    DuplicateUuid {
+        /// The duplicated UUID.
+        id: Uuid,
+        /// The source files declaring it.
+        paths: Vec<PathBuf>,
+    },
+    /// A source file references an asset UUID no source file declares.
+    BrokenReference {
+        /// The referencing source file.
+        path: PathBuf,
+        /// The missing UUID.
+        id: Uuid,
+    },
+}
+
+impl fmt::Display for AssetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetProblem::NoImporter { path } => {
+                write!(f, "{}: no importer for this file type", path.display())
+            }
+            AssetProblem::Unreadable { path, error } => write!(f, "{}: {}", path.display(), error),
+            AssetProblem::OrphanedMeta { path } => {
+                write!(f, "{}: source file does not exist", path.display())
+            }
+            AssetProblem::OutdatedMeta {
+                path,
+                meta_version,
+                importer_version,
+            } => write!(
+                f,
+                "{}: written by importer version {}, current version is {}",
+                path.display(),
+                meta_version,
+                importer_version
+            ),
+            AssetProblem::DuplicateUuid { id, paths } => {
+                write!(f, "{} is declared by", id)?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            AssetProblem::BrokenReference { path, id } => {
+                write!(f, "{}: references unknown asset {}", path.display(), id)
+            }
+        }
+    }
+}
+
+/// The result of `validate_assets`.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    checked_files: usize,
+    problems: Vec<AssetProblem>,
+}
+
+impl ValidationReport {
+    /// The number of source files checked, `.meta` files excluded.
+    #[must_use]
+    pub fn checked_files(&self) -> usize {
+        self.checked_files
+    }
+
+    /// The problems found, in a stable order.
+    #[must_use]
+    pub fn problems(&self) -> &[AssetProblem] {
+        &self.problems
+    }
+
+    /// Returns true if no problem was found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Checks the assets of `asset_dirs` with the importers registered with `register_importer!`,
+/// without running the `AssetDaemon`.
+///
+/// Reports the files no importer handles, unreadable or stale `.meta` files, asset UUIDs declared
+/// by several files, and prefab or glTF references to UUIDs that no file declares. References to
+/// assets outside of `asset_dirs` are reported as broken.
+///
+/// Only the importers linked into the calling binary are known, so games registering their own
+/// importers should call this, or `run_asset_validation`, from their own binary.
+///
+/// # Errors
+///
+/// Returns an error if one of the directories can't be listed.
+pub fn validate_assets<P: AsRef<Path>>(asset_dirs: &[P]) -> Result<ValidationReport, Error> {
+    let importers = importer_versions();
+    let mut validator = Validator::default();
+    for path in source_files(asset_dirs)? {
+        validator.check(&path, &importers);
+    }
+    Ok(validator.finish())
+}
+
+/// The source files of `asset_dirs` the `AssetDaemon` hasn't imported with the current version
+/// of their importer yet: the files with a registered importer but without a readable `.meta`
+/// file written by that version.
+#[cfg(feature = "packfile")]
+pub(crate) fn pending_imports<P: AsRef<Path>>(asset_dirs: &[P]) -> Result<Vec<PathBuf>, Error> {
+    let importers = importer_versions();
+    Ok(source_files(asset_dirs)?
+        .into_iter()
+        .filter(|path| {
+            importers.get(&extension(path)).map_or(false, |version| {
+                let meta = read_ron(&meta_path(path)).ok();
+                let meta_version = meta
+                    .as_ref()
+                    .and_then(|meta| field(meta, "importer_version"))
+                    .and_then(as_u32);
+                meta_version != Some(*version)
+            })
+        })
+        .collect())
+}
+
+/// The versions of the registered importers, by lowercase file extension.
+fn importer_versions() -> FnvHashMap<String, u32> {
+    let mut importers: FnvHashMap<String, u32> = get_source_importers()
+        .map(|(extension, importer)| (extension.to_lowercase(), importer.version()))
+        .collect();
+    importers.insert("prefab".to_string(), PrefabImporter::version_static());
+    importers
+}
+
+/// The files of `asset_dirs`, sorted.
+fn source_files<P: AsRef<Path>>(asset_dirs: &[P]) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for dir in asset_dirs {
+        collect_files(dir.as_ref(), &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The lowercase extension of `path`, empty if it has none.
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Checks the assets of the given directories.
+#[cfg(feature = "validate")]
+#[derive(StructOpt, Debug)]
+struct ValidateArgs {
+    /// Directories to check the assets of.
+    #[structopt(parse(from_os_str), default_value = "assets")]
+    asset_dirs: Vec<PathBuf>,
+}
+
+/// Runs `validate_assets` on the directories given on the command line, `assets` if there are
+/// none, and prints the problems found.
+///
+/// This is the `main` of the `amethyst_validate_assets` binary of the `amethyst` crate, which
+/// knows the importers of the engine crates enabled by its features. Games registering their own
+/// importers call it from a binary of their own:
+///
+/// ```ignore
+/// fn main() -> Result<(), amethyst_error::Error> {
+///     amethyst_assets::run_asset_validation()
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if one of the directories can't be listed, or if problems were found.
+#[cfg(feature = "validate")]
+pub fn run_asset_validation() -> Result<(), Error> {
+    let report = validate_assets(&ValidateArgs::from_args().asset_dirs)?;
+    for problem in report.problems() {
+        eprintln!("{}", problem);
+    }
+    println!(
+        "Checked {} files, found {} problems",
+        report.checked_files(),
+        report.problems().len()
+    );
+    if report.is_ok() {
+        Ok(())
+    } else {
+        Err(Error::from_string("Found problems in the assets"))
+    }
+}
+
+/// Lists the files under `dir`, skipping hidden ones.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let entries = fs::read_dir(dir)
+        .with_context(|_| Error::from_string(format!("Could not list {}", dir.display())))?;
+    for entry in entries {
+        let path = entry.map_err(Error::new)?.path();
+        let hidden = path
+            .file_name()
+            .map_or(false, |name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Asset UUIDs declared and referenced by a source file.
+#[derive(Debug, Default, PartialEq)]
+struct AssetIds {
+    declared: Vec<Uuid>,
+    referenced: Vec<Uuid>,
+}
+
+#[derive(Debug, Default)]
+struct Validator {
+    report: ValidationReport,
+    declared: BTreeMap<Uuid, BTreeSet<PathBuf>>,
+    referenced: Vec<(PathBuf, Uuid)>,
+}
+
+impl Validator {
+    fn check(&mut self, path: &Path, importers: &FnvHashMap<String, u32>) {
+        let extension = extension(path);
+        if extension == "meta" {
+            // `with_extension` strips the `.meta` suffix.
+            if !path.with_extension("").is_file() {
+                self.problem(AssetProblem::OrphanedMeta {
+                    path: path.to_path_buf(),
+                });
+            }
+            return;
+        }
+
+        self.report.checked_files += 1;
+        let importer_version = match importers.get(&extension) {
+            Some(version) => *version,
+            None => {
+                self.problem(AssetProblem::NoImporter {
+                    path: path.to_path_buf(),
+                });
+                return;
+            }
+        };
+
+        let meta_path = meta_path(path);
+        if meta_path.is_file() {
+            match read_ron(&meta_path) {
+                Ok(meta) => {
+                    if let Some(meta_version) = field(&meta, "importer_version").and_then(as_u32) {
+                        if meta_version != importer_version {
+                            self.problem(AssetProblem::OutdatedMeta {
+                                path: meta_path.clone(),
+                                meta_version,
+                                importer_version,
+                            });
+                        }
+                    }
+                    let gltf = extension == "gltf" || extension == "glb";
+                    self.record(path, meta_ids(&meta, gltf));
+                }
+                Err(error) => self.problem(AssetProblem::Unreadable {
+                    path: meta_path,
+                    error,
+                }),
+            }
+        }
+
+        if extension == "prefab" {
+            match read_ron(path) {
+                Ok(prefab) => self.record(path, prefab_ids(&prefab)),
+                Err(error) => self.problem(AssetProblem::Unreadable {
+                    path: path.to_path_buf(),
+                    error,
+                }),
+            }
+        }
+    }
+
+    fn record(&mut self, path: &Path, ids: AssetIds) {
+        for id in ids.declared {
+            self.declared
+                .entry(id)
+                .or_default()
+                .insert(path.to_path_buf());
+        }
+        self.referenced.extend(
+            ids.referenced
+                .into_iter()
+                .map(|id| (path.to_path_buf(), id)),
+        );
+    }
+
+    fn problem(&mut self, problem: AssetProblem) {
+        self.report.problems.push(problem);
+    }
+
+    fn finish(mut self) -> ValidationReport {
+        for (id, paths) in &self.declared {
+            if paths.len() > 1 {
+                self.report.problems.push(AssetProblem::DuplicateUuid {
+                    id: *id,
+                    paths: paths.iter().cloned().collect(),
+                });
+            }
+        }
+        for (path, id) in self.referenced {
+            if !self.declared.contains_key(&id) {
+                self.report
+                    .problems
+                    .push(AssetProblem::BrokenReference { path, id });
+            }
+        }
+        self.report
+    }
+}
+
+/// Path of the `.meta` file of a source file.
+fn meta_path(path: &Path) -> PathBuf {
+    let mut meta = OsString::from(path.as_os_str());
+    meta.push(".meta");
+    PathBuf::from(meta)
+}
+
+fn read_ron(path: &Path) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    ron::from_str(text.trim_start_matches('\u{feff}')).map_err(|err| err.to_string())
+}
+
+/// The asset UUIDs of a `.meta` file.
+///
+/// The UUIDs minted by the glTF importer for the meshes, materials and other assets of a glTF
+/// file are referenced by the handles of its scene, so they must be declared.
+fn meta_ids(meta: &Value, gltf: bool) -> AssetIds {
+    let mut ids = AssetIds::default();
+    let state = field(meta, "importer_state");
+    ids.declared
+        .extend(state.and_then(|s| field(s, "id")).and_then(as_uuid));
+    if let Some(Value::Seq(assets)) = field(meta, "assets") {
+        ids.declared.extend(
+            assets
+                .iter()
+                .filter_map(|a| field(a, "id"))
+                .filter_map(as_uuid),
+        );
+    }
+    if let (true, Some(Value::Map(state))) = (gltf, state) {
+        for (key, value) in state.iter() {
+            if matches!(key, Value::String(key) if key.ends_with("_uuids")) {
+                collect_uuids(value, &mut ids.referenced);
+            }
+        }
+    }
+    ids
+}
+
+/// The UUID of a prefab and the UUIDs of the prefabs it references.
+fn prefab_ids(prefab: &Value) -> AssetIds {
+    let mut ids = AssetIds::default();
+    ids.declared.extend(field(prefab, "id").and_then(as_uuid));
+    collect_prefab_refs(prefab, &mut ids.referenced);
+    ids
+}
+
+fn collect_prefab_refs(value: &Value, refs: &mut Vec<Uuid>) {
+    match value {
+        Value::Map(map) => {
+            for (key, value) in map.iter() {
+                match key {
+                    Value::String(key) if key == "prefab_id" => refs.extend(as_uuid(value)),
+                    _ => collect_prefab_refs(value, refs),
+                }
+            }
+        }
+        Value::Seq(values) => {
+            for value in values {
+                collect_prefab_refs(value, refs);
+            }
+        }
+        Value::Option(Some(value)) => collect_prefab_refs(value, refs),
+        _ => {}
+    }
+}
+
+fn collect_uuids(value: &Value, uuids: &mut Vec<Uuid>) {
+    match value {
+        Value::Map(map) => {
+            for (_, value) in map.iter() {
+                collect_uuids(value, uuids);
+            }
+        }
+        Value::Seq(values) => {
+            for value in values {
+                collect_uuids(value, uuids);
+            }
+        }
+        value => uuids.extend(as_uuid(value)),
+    }
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    match value {
+        Value::Map(map) => map
+            .iter()
+            .find(|(key, _)| matches!(key, Value::String(key) if key == name))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(number) => number.as_i64().and_then(|n| u32::try_from(n).ok()),
+        _ => None,
+    }
+}
+
+fn as_uuid(value: &Value) -> Option<Uuid> {
+    match value {
+        Value::String(text) => Uuid::parse_str(text).ok(),
+        Value::Option(Some(value)) => as_uuid(value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "8b10e4fb-5a25-40b0-9ddd-a28bc842f834";
+    const DERIVED: &str = "a1e04f08-8bdd-403a-baa2-1ba83113f186";
+    const MISSING: &str = "14dec17f-ae14-40a3-8e44-e487fc423287";
+
+    fn uuid(text: &str) -> Uuid {
+        Uuid::parse_str(text).unwrap()
+    }
+
+    #[test]
+    fn reads_prefab_references() {
+        let prefab = ron::from_str(&format!(
+            r#"Prefab(
+                id: "{}",
+                objects: [
+                    // A comment
+                    PrefabRef((prefab_id: "{}", entity_overrides: [])),
+                ],
+            )"#,
+            DERIVED, BASE
+        ))
+        .unwrap();
+        assert_eq!(
+            prefab_ids(&prefab),
+            AssetIds {
+                declared: vec![uuid(DERIVED)],
+                referenced: vec![uuid(BASE)],
+            }
+        );
+    }
+
+    #[test]
+    fn reads_gltf_meta() {
+        let meta = ron::from_str(&format!(
+            r#"(
+                version: 1,
+                importer_version: 1,
+                importer_state: (
+                    id: Some("{}"),
+                    mesh_uuids: Some({{"mesh": "{}"}}),
+                ),
+            )"#,
+            DERIVED, BASE
+        ))
+        .unwrap();
+        assert_eq!(field(&meta, "importer_version").and_then(as_u32), Some(1));
+        assert_eq!(
+            meta_ids(&meta, true),
+            AssetIds {
+                declared: vec![uuid(DERIVED)],
+                referenced: vec![uuid(BASE)],
+            }
+        );
+        assert!(meta_ids(&meta, false).referenced.is_empty());
+    }
+
+    #[test]
+    fn reports_problems_of_a_tree() {
+        let dir = std::env::temp_dir().join("amethyst_validate_assets");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let prefab = |id: &str, reference: &str| {
+            format!(
+                r#"Prefab(id: "{}", objects: [PrefabRef((prefab_id: "{}", entity_overrides: []))])"#,
+                id, reference
+            )
+        };
+        fs::write(dir.join("a.prefab"), prefab(BASE, MISSING)).unwrap();
+        fs::write(dir.join("b.prefab"), prefab(BASE, BASE)).unwrap();
+        fs::write(dir.join("notes.unknown"), "").unwrap();
+        fs::write(dir.join("removed.prefab.meta"), "()").unwrap();
+
+        let report = validate_assets(&[&dir]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.checked_files(), 3);
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.problems(),
+            &[
+                AssetProblem::NoImporter {
+                    path: dir.join("notes.unknown"),
+                },
+                AssetProblem::OrphanedMeta {
+                    path: dir.join("removed.prefab.meta"),
+                },
+                AssetProblem::DuplicateUuid {
+                    id: uuid(BASE),
+                    paths: vec![dir.join("a.prefab"), dir.join("b.prefab")],
+                },
+                AssetProblem::BrokenReference {
+                    path: dir.join("a.prefab"),
+                    id: uuid(MISSING),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "packfile")]
+    #[test]
+    fn pending_imports_lack_a_current_meta() {
+        let dir = std::env::temp_dir().join("amethyst_pending_imports");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let meta = |version: u32| format!("(importer_version: {}, assets: [])", version);
+        let current = PrefabImporter::version_static();
+        for name in &["new", "imported", "outdated"] {
+            fs::write(dir.join(format!("{}.prefab", name)), "").unwrap();
+        }
+        fs::write(dir.join("imported.prefab.meta"), meta(current)).unwrap();
+        fs::write(dir.join("outdated.prefab.meta"), meta(current + 1)).unwrap();
+        fs::write(dir.join("notes.unknown"), "").unwrap();
+
+        let pending = pending_imports(&[&dir]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            pending,
+            vec![dir.join("new.prefab"), dir.join("outdated.prefab")]
+        );
+    }
+}
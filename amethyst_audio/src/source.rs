@@ -27,6 +27,10 @@ impl Asset for Source {
         "audio::Source"
     }
     type Data = AudioData;
+
+    fn size_in_bytes(&self) -> Option<usize> {
+        Some(self.bytes.len())
+    }
 }
 
 impl ProcessableAsset for Source {
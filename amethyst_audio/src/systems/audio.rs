@@ -10,7 +10,7 @@ use std::{
 use amethyst_core::{
     ecs::{Entity, EntityStore, IntoQuery, ParallelRunnable, Read, System, SystemBuilder, Write},
     math::convert,
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use rodio::SpatialSink;
 #[cfg(feature = "profiler")]
@@ -39,7 +39,7 @@ impl System for AudioSystem {
                 .read_resource::<OutputWrapper>()
                 .read_resource::<SelectedListener>()
                 .with_query(<(Entity, Read<AudioListener>)>::query())
-                .with_query(<(Write<AudioEmitter>, Read<Transform>)>::query())
+                .with_query(<(Write<AudioEmitter>, Read<GlobalTransform>)>::query())
                 .build(
                     move |_commands,
                           world,
@@ -68,9 +68,9 @@ impl System for AudioSystem {
                             if let Some(listener_transform) = world
                                 .entry_ref(entity)
                                 .ok()
-                                .and_then(|entry| entry.into_component::<Transform>().ok())
+                                .and_then(|entry| entry.into_component::<GlobalTransform>().ok())
                             {
-                                let listener_transform = listener_transform.global_matrix();
+                                let listener_transform = listener_transform.matrix();
                                 let left_ear_position: [f32; 3] = {
                                     let pos = listener_transform
                                         .transform_point(&listener.left_ear)
@@ -89,9 +89,9 @@ impl System for AudioSystem {
                                     world,
                                     |(mut audio_emitter, transform)| {
                                         let emitter_position: [f32; 3] = {
-                                            let x = transform.global_matrix()[(0, 3)];
-                                            let y = transform.global_matrix()[(1, 3)];
-                                            let z = transform.global_matrix()[(2, 3)];
+                                            let x = transform.matrix()[(0, 3)];
+                                            let y = transform.matrix()[(1, 3)];
+                                            let z = transform.matrix()[(2, 3)];
                                             [convert(x), convert(y), convert(z)]
                                         };
                                         // Remove all sinks whose sounds have ended.
@@ -0,0 +1,569 @@
+//! Configurations merged from several layers: the defaults, config files, environment variables
+//! and command line arguments.
+//!
+//! Layers are merged as JSON values, so they require the `json` feature.
+
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{read_file, Config, ConfigError};
+
+/// A layer that sets configuration values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// The `Default` value of the configuration.
+    Default,
+    /// A config file.
+    File(PathBuf),
+    /// An environment variable, e.g. `AMETHYST_DISPLAY__FULLSCREEN`.
+    Env(String),
+    /// A `--set` command line argument, e.g. `display.fullscreen=true`.
+    Arg(String),
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ConfigLayer::Default => write!(f, "default value"),
+            ConfigLayer::File(ref path) => write!(f, "file {}", path.display()),
+            ConfigLayer::Env(ref name) => write!(f, "environment variable {}", name),
+            ConfigLayer::Arg(ref arg) => write!(f, "argument --set {}", arg),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Pending {
+    File {
+        path: PathBuf,
+        optional: bool,
+    },
+    Override {
+        layer: ConfigLayer,
+        key: Vec<String>,
+        value: String,
+    },
+    Invalid(String),
+}
+
+/// Loads a configuration from layers, each layer overriding the values set by the previous ones.
+///
+/// The layers are merged in the order they are added, on top of the `Default` value of the
+/// configuration. Files only override the fields they contain, which requires the fields
+/// missing from a file to have a `#[serde(default)]`.
+///
+/// Environment variables and `--set` arguments address a field by its path, starting with the
+/// name of the configuration: `AMETHYST_DISPLAY__DIMENSIONS` and `--set display.dimensions` both
+/// set the `dimensions` of the configuration named `display`, nested fields being separated by
+/// `__` and `.` respectively. Their values are parsed as JSON, values that are not valid JSON being
+/// strings, so `true`, `[1280, 720]`, `null` and `My Game` are all valid values.
+///
+/// # Example
+///
+/// ```no_run
+/// use amethyst::window::DisplayConfig;
+/// use amethyst_config::{ConfigLayer, ConfigLayers};
+///
+/// let display = ConfigLayers::new("display")
+///     .with_file("config/display.ron")
+///     .with_optional_file(format!("config/{}/display.ron", std::env::consts::OS))
+///     .with_optional_file("user/display.ron")
+///     .with_env()
+///     .with_args(std::env::args())
+///     .load::<DisplayConfig>()
+///     .expect("Failed to load the display config");
+///
+/// if let Some(ConfigLayer::Env(name)) = display.origin("fullscreen") {
+///     println!("fullscreen is set by {}", name);
+/// }
+/// let display = display.into_inner();
+/// ```
+#[derive(Debug)]
+pub struct ConfigLayers {
+    name: String,
+    layers: Vec<Pending>,
+}
+
+impl ConfigLayers {
+    /// Creates the layers of the configuration called `name` in environment variables and
+    /// `--set` arguments.
+    #[must_use]
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        ConfigLayers {
+            name: name.into(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a config file, which must exist.
+    #[must_use]
+    pub fn with_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.layers.push(Pending::File {
+            path: path.into(),
+            optional: false,
+        });
+        self
+    }
+
+    /// Adds a config file, which is skipped if it doesn't exist.
+    #[must_use]
+    pub fn with_optional_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.layers.push(Pending::File {
+            path: path.into(),
+            optional: true,
+        });
+        self
+    }
+
+    /// Adds the environment variables of the process starting with `AMETHYST_<NAME>__`.
+    #[must_use]
+    pub fn with_env(self) -> Self {
+        self.with_env_vars(std::env::vars())
+    }
+
+    /// Adds the given environment variables starting with `AMETHYST_<NAME>__`, in order.
+    #[must_use]
+    pub fn with_env_vars<I>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let prefix = format!("AMETHYST_{}__", self.name.to_uppercase());
+        let mut vars: Vec<_> = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .collect();
+        // The order of the process environment is unspecified.
+        vars.sort();
+        for (name, value) in vars {
+            let key = name[prefix.len()..]
+                .split("__")
+                .map(str::to_lowercase)
+                .collect();
+            self.layers.push(Pending::Override {
+                layer: ConfigLayer::Env(name),
+                key,
+                value,
+            });
+        }
+        self
+    }
+
+    /// Adds the `--set <name>.<key>=<value>` arguments, ignoring the other arguments and the
+    /// `--set` arguments of other configurations.
+    #[must_use]
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let prefix = format!("{}.", self.name);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let set = if arg == "--set" {
+                match args.next() {
+                    Some(set) => set.as_ref().to_string(),
+                    None => {
+                        self.layers
+                            .push(Pending::Invalid("--set is missing its value".to_string()));
+                        break;
+                    }
+                }
+            } else if let Some(set) = arg.strip_prefix("--set=") {
+                set.to_string()
+            } else {
+                continue;
+            };
+            if !set.starts_with(&prefix) {
+                continue;
+            }
+            match set.find('=') {
+                Some(split) => self.layers.push(Pending::Override {
+                    key: set[prefix.len()..split]
+                        .split('.')
+                        .map(String::from)
+                        .collect(),
+                    value: set[split + 1..].to_string(),
+                    layer: ConfigLayer::Arg(set),
+                }),
+                None => self.layers.push(Pending::Invalid(format!(
+                    "--set {}: expected `key=value`",
+                    set
+                ))),
+            }
+        }
+        self
+    }
+
+    /// Merges the layers into the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required file doesn't exist, a file can't be parsed, or a layer sets
+    /// a value that doesn't match the type of the configuration.
+    pub fn load<T>(&self) -> Result<LayeredConfig<T>, ConfigError>
+    where
+        T: Default + Serialize + DeserializeOwned,
+    {
+        let mut merged = Merged {
+            tree: to_tree(&T::default(), &ConfigLayer::Default)?,
+            origins: BTreeMap::new(),
+        };
+        match merged.tree {
+            Value::Object(ref fields) => {
+                for field in fields.keys() {
+                    merged.origins.insert(field.clone(), ConfigLayer::Default);
+                }
+            }
+            _ => {
+                merged.origins.insert(String::new(), ConfigLayer::Default);
+            }
+        }
+
+        for pending in &self.layers {
+            let layer = match *pending {
+                Pending::File { ref path, optional } => {
+                    let bytes = match read_file(path) {
+                        Ok(bytes) => bytes,
+                        Err(ref err) if optional && err.kind() == io::ErrorKind::NotFound => {
+                            continue
+                        }
+                        Err(err) => return Err(err.into()),
+                    };
+                    let layer = ConfigLayer::File(path.clone());
+                    let value = to_tree(&T::load_bytes(path, &bytes)?, &layer)?;
+                    let written = written_keys(path, &bytes, &value)?;
+                    merged.overlay(&mut Vec::new(), value, &written, &layer);
+                    layer
+                }
+                Pending::Override {
+                    ref layer,
+                    ref key,
+                    ref value,
+                } => {
+                    let value = serde_json::from_str(value)
+                        .unwrap_or_else(|_| Value::String(value.clone()));
+                    merged.set(key, value, layer);
+                    layer.clone()
+                }
+                Pending::Invalid(ref message) => return Err(ConfigError::Layer(message.clone())),
+            };
+            // Checked after each layer to report the layer setting an invalid value.
+            T::deserialize(&merged.tree)
+                .map_err(|err| ConfigError::Layer(format!("{}: {}", layer, err)))?;
+        }
+
+        Ok(LayeredConfig {
+            config: T::deserialize(merged.tree)
+                .map_err(|err| ConfigError::Layer(err.to_string()))?,
+            origins: merged.origins,
+        })
+    }
+}
+
+fn to_tree<T: Serialize>(config: &T, layer: &ConfigLayer) -> Result<Value, ConfigError> {
+    serde_json::to_value(config).map_err(|err| ConfigError::Layer(format!("{}: {}", layer, err)))
+}
+
+/// The keys written in a file, as an object of the written fields whose values are objects of
+/// their written fields, or any other value for values written as a whole.
+fn written_keys(path: &Path, bytes: &[u8], value: &Value) -> Result<Value, ConfigError> {
+    match path.extension().and_then(std::ffi::OsStr::to_str) {
+        Some("ron") => ron::de::from_bytes(bytes)
+            .map(|written| ron_keys(&written))
+            .map_err(|err| ConfigError::FileParser(err, path.to_path_buf())),
+        Some("json") => Ok(serde_json::from_slice(bytes)?),
+        // Binary files contain every field.
+        _ => Ok(value.clone()),
+    }
+}
+
+fn ron_keys(written: &ron::Value) -> Value {
+    match *written {
+        ron::Value::Map(ref fields) => fields
+            .iter()
+            .map(|(key, value)| match *key {
+                ron::Value::String(ref key) => Some((key.clone(), ron_keys(value))),
+                _ => None,
+            })
+            .collect::<Option<Map<_, _>>>()
+            .map_or(Value::Null, Value::Object),
+        _ => Value::Null,
+    }
+}
+
+struct Merged {
+    tree: Value,
+    origins: BTreeMap<String, ConfigLayer>,
+}
+
+impl Merged {
+    /// Sets the value at `key` to `value`, creating the missing objects on the way.
+    fn set(&mut self, key: &[String], value: Value, layer: &ConfigLayer) {
+        let mut target = &mut self.tree;
+        for field in key {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            target = target
+                .as_object_mut()
+                .expect("target is an object")
+                .entry(field.clone())
+                .or_insert(Value::Null);
+        }
+        *target = value;
+        self.set_origin(&key.join("."), layer);
+    }
+
+    /// Sets the fields listed in `written` to the ones of `value`.
+    fn overlay(
+        &mut self,
+        key: &mut Vec<String>,
+        value: Value,
+        written: &Value,
+        layer: &ConfigLayer,
+    ) {
+        let target = key
+            .iter()
+            .fold(Some(&self.tree), |target, field| target?.get(field));
+        match (written, value) {
+            (Value::Object(written), Value::Object(mut fields))
+                if target.map_or(false, Value::is_object)
+                    && written.keys().all(|field| fields.contains_key(field)) =>
+            {
+                for (field, written) in written {
+                    let value = fields.remove(field).expect("written field is serialized");
+                    key.push(field.clone());
+                    self.overlay(key, value, written, layer);
+                    key.pop();
+                }
+            }
+            (_, value) => self.set(key, value, layer),
+        }
+    }
+
+    fn set_origin(&mut self, key: &str, layer: &ConfigLayer) {
+        let nested = format!("{}.", key);
+        self.origins
+            .retain(|origin, _| !(key.is_empty() || origin == key || origin.starts_with(&nested)));
+        self.origins.insert(key.to_string(), layer.clone());
+    }
+}
+
+/// A configuration loaded by `ConfigLayers`, recording the layer that set each of its values.
+#[derive(Clone, Debug)]
+pub struct LayeredConfig<T> {
+    config: T,
+    origins: BTreeMap<String, ConfigLayer>,
+}
+
+impl<T> LayeredConfig<T> {
+    /// The configuration.
+    #[must_use]
+    pub fn config(&self) -> &T {
+        &self.config
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.config
+    }
+
+    /// The layer that set the value at `key`, a path of fields separated by `.`.
+    #[must_use]
+    pub fn origin(&self, key: &str) -> Option<&ConfigLayer> {
+        let mut key = key;
+        loop {
+            if let Some(layer) = self.origins.get(key) {
+                return Some(layer);
+            }
+            if key.is_empty() {
+                return None;
+            }
+            key = key.rfind('.').map_or("", |split| &key[..split]);
+        }
+    }
+
+    /// The values set by the layers, as their keys and the last layer that set them.
+    pub fn origins(&self) -> impl Iterator<Item = (&str, &ConfigLayer)> {
+        self.origins
+            .iter()
+            .map(|(key, layer)| (key.as_str(), layer))
+    }
+}
+
+impl<T> std::ops::Deref for LayeredConfig<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    enum Mode {
+        Windowed,
+        Fullscreen(u32),
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(default)]
+    struct Window {
+        width: u32,
+        height: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(default)]
+    struct TestConfig {
+        title: String,
+        mode: Mode,
+        window: Window,
+        vsync: Option<bool>,
+    }
+
+    impl Default for Window {
+        fn default() -> Self {
+            Window {
+                width: 800,
+                height: 600,
+            }
+        }
+    }
+
+    impl Default for TestConfig {
+        fn default() -> Self {
+            TestConfig {
+                title: "Test".to_string(),
+                mode: Mode::Windowed,
+                window: Window::default(),
+                vsync: None,
+            }
+        }
+    }
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn merges_layers_in_order() {
+        let dir = std::env::temp_dir().join("amethyst_config_merges_layers_in_order");
+        fs::create_dir_all(&dir).unwrap();
+        let base = write(&dir, "base.ron", "(title: \"Base\", mode: Fullscreen(1))");
+        let user = write(&dir, "user.ron", "(window: (height: 720))");
+
+        let layered = ConfigLayers::new("test")
+            .with_file(&base)
+            .with_optional_file(dir.join("missing.ron"))
+            .with_optional_file(&user)
+            .with_env_vars(vec![
+                (
+                    "AMETHYST_TEST__WINDOW__WIDTH".to_string(),
+                    "1280".to_string(),
+                ),
+                ("AMETHYST_OTHER__TITLE".to_string(), "Other".to_string()),
+            ])
+            .with_args(vec![
+                "game",
+                "--set",
+                "test.vsync=true",
+                "--set=test.title=Args",
+            ])
+            .load::<TestConfig>()
+            .unwrap();
+
+        assert_eq!(
+            *layered.config(),
+            TestConfig {
+                title: "Args".to_string(),
+                mode: Mode::Fullscreen(1),
+                window: Window {
+                    width: 1280,
+                    height: 720,
+                },
+                vsync: Some(true),
+            }
+        );
+        assert_eq!(
+            layered.origin("title"),
+            Some(&ConfigLayer::Arg("test.title=Args".to_string()))
+        );
+        assert_eq!(layered.origin("mode"), Some(&ConfigLayer::File(base)));
+        assert_eq!(
+            layered.origin("window.height"),
+            Some(&ConfigLayer::File(user))
+        );
+        assert_eq!(
+            layered.origin("window.width"),
+            Some(&ConfigLayer::Env(
+                "AMETHYST_TEST__WINDOW__WIDTH".to_string()
+            ))
+        );
+        assert_eq!(layered.origin("missing"), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn later_layers_replace_nested_origins() {
+        let layered = ConfigLayers::new("test")
+            .with_args(vec![
+                "--set=test.window.width=1",
+                "--set=test.window={\"width\": 2}",
+            ])
+            .load::<TestConfig>()
+            .unwrap();
+
+        assert_eq!(
+            layered.window,
+            Window {
+                width: 2,
+                height: 600
+            }
+        );
+        let window = ConfigLayer::Arg("test.window={\"width\": 2}".to_string());
+        assert_eq!(layered.origin("window.width"), Some(&window));
+        assert_eq!(layered.origin("vsync"), Some(&ConfigLayer::Default));
+    }
+
+    #[test]
+    fn reports_the_invalid_layer() {
+        let error = ConfigLayers::new("test")
+            .with_env_vars(vec![(
+                "AMETHYST_TEST__VSYNC".to_string(),
+                "sometimes".to_string(),
+            )])
+            .load::<TestConfig>()
+            .unwrap_err();
+        assert!(error.to_string().contains("AMETHYST_TEST__VSYNC"));
+
+        assert!(ConfigLayers::new("test")
+            .with_args(vec!["--set", "test.title"])
+            .load::<TestConfig>()
+            .is_err());
+        assert!(matches!(
+            ConfigLayers::new("test")
+                .with_file("missing.ron")
+                .load::<TestConfig>(),
+            Err(ConfigError::File(_))
+        ));
+    }
+}
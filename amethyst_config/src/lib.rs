@@ -28,6 +28,12 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "json")]
 use serde_json::error::Error as SerJsonError;
 
+#[cfg(feature = "json")]
+pub use crate::layered::{ConfigLayer, ConfigLayers, LayeredConfig};
+
+#[cfg(feature = "json")]
+mod layered;
+
 /// Error related to anything that manages/creates configurations as well as
 /// "workspace"-related things.
 #[derive(Debug)]
@@ -48,6 +54,9 @@ pub enum ConfigError {
     /// Forward to bincode's errors
     #[cfg(feature = "binary")]
     BincodeError(BincodeError),
+    /// A layer of a `ConfigLayers` sets a value that doesn't fit the configuration.
+    #[cfg(feature = "json")]
+    Layer(String),
 }
 
 /// Config file format for serde
@@ -86,6 +95,8 @@ impl fmt::Display for ConfigError {
             ConfigError::SerdeJsonError(ref msg) => write!(f, "{}", msg),
             #[cfg(feature = "binary")]
             ConfigError::BincodeError(ref msg) => write!(f, "{}", msg),
+            #[cfg(feature = "json")]
+            ConfigError::Layer(ref msg) => write!(f, "Invalid configuration layer: {}", msg),
         }
     }
 }
@@ -127,6 +138,8 @@ impl Error for ConfigError {
             ConfigError::SerdeJsonError(_) => "Serialization or deserialization error (serde_json)",
             #[cfg(feature = "binary")]
             ConfigError::BincodeError(_) => "Serialization or deserialization error (bincode)",
+            #[cfg(feature = "json")]
+            ConfigError::Layer(_) => "Invalid configuration layer",
         }
     }
 
@@ -155,54 +168,21 @@ where
     /// Loads configuration structure from raw bytes.
     fn load_bytes_format(format: ConfigFormat, bytes: &[u8]) -> Result<Self, ConfigError>;
 
-    /// Writes a configuration structure to a file.
-    fn write_format<P: AsRef<Path>>(
-        &self,
-        format: ConfigFormat,
-        path: P,
-    ) -> Result<(), ConfigError>;
-
-    /// Writes a configuration structure to a file using Ron format.
-    #[deprecated(note = "use `write_format` instead")]
-    fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
-        self.write_format(ConfigFormat::Ron, path)
-    }
-}
-
-impl<T> Config for T
-where
-    T: for<'a> Deserialize<'a> + Serialize,
-{
-    fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
-        use std::{fs::File, io::Read};
-
-        use encoding_rs_io::DecodeReaderBytes;
-
+    /// Loads a configuration structure from the raw bytes of the file at `path`, in the format
+    /// given by its extension.
+    fn load_bytes<P: AsRef<Path>>(path: P, bytes: &[u8]) -> Result<Self, ConfigError> {
         let path = path.as_ref();
-
-        let content = {
-            let file = File::open(path)?;
-
-            // Convert UTF-8-BOM & UTF-16-BOM to regular UTF-8. Else bytes are passed through
-            let mut decoder = DecodeReaderBytes::new(file);
-
-            let mut buffer = Vec::new();
-            decoder.read_to_end(&mut buffer)?;
-
-            buffer
-        };
-
         path.extension()
             .and_then(std::ffi::OsStr::to_str)
             .map_or_else(
                 || Err(ConfigError::Extension(path.to_path_buf())),
                 |extension| {
                     match extension {
-                        "ron" => Self::load_bytes_format(ConfigFormat::Ron, &content),
+                        "ron" => Self::load_bytes_format(ConfigFormat::Ron, bytes),
                         #[cfg(feature = "json")]
-                        "json" => Self::load_bytes_format(ConfigFormat::Json, &content),
+                        "json" => Self::load_bytes_format(ConfigFormat::Json, bytes),
                         #[cfg(feature = "binary")]
-                        "bin" => Self::load_bytes_format(ConfigFormat::Binary, &content),
+                        "bin" => Self::load_bytes_format(ConfigFormat::Binary, bytes),
                         _ => Err(ConfigError::Extension(path.to_path_buf())),
                     }
                 },
@@ -216,6 +196,47 @@ where
             })
     }
 
+    /// Writes a configuration structure to a file.
+    fn write_format<P: AsRef<Path>>(
+        &self,
+        format: ConfigFormat,
+        path: P,
+    ) -> Result<(), ConfigError>;
+
+    /// Writes a configuration structure to a file using Ron format.
+    #[deprecated(note = "use `write_format` instead")]
+    fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
+        self.write_format(ConfigFormat::Ron, path)
+    }
+}
+
+/// Reads the content of a config file.
+pub(crate) fn read_file(path: &Path) -> io::Result<Vec<u8>> {
+    use std::{fs::File, io::Read};
+
+    use encoding_rs_io::DecodeReaderBytes;
+
+    let file = File::open(path)?;
+
+    // Convert UTF-8-BOM & UTF-16-BOM to regular UTF-8. Else bytes are passed through
+    let mut decoder = DecodeReaderBytes::new(file);
+
+    let mut buffer = Vec::new();
+    decoder.read_to_end(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+impl<T> Config for T
+where
+    T: for<'a> Deserialize<'a> + Serialize,
+{
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = read_file(path)?;
+        Self::load_bytes(path, &content)
+    }
+
     fn load_bytes_format(format: ConfigFormat, bytes: &[u8]) -> Result<Self, ConfigError> {
         match format {
             ConfigFormat::Ron => {
@@ -317,6 +338,21 @@ mod test {
         );
     }
 
+    #[test]
+    fn load_bytes_uses_the_extension() {
+        let parsed = TestConfig::load_bytes("config/test.ron", b"(amethyst: true)");
+        assert_eq!(TestConfig { amethyst: true }, parsed.unwrap());
+
+        match TestConfig::load_bytes("config/test.ron", b"(amethyst: ") {
+            Err(ConfigError::FileParser(_, path)) => assert_eq!(Path::new("config/test.ron"), path),
+            result => panic!("{:?}", result),
+        }
+        assert!(matches!(
+            TestConfig::load_bytes("config/test.txt", b""),
+            Err(ConfigError::Extension(_))
+        ));
+    }
+
     #[test]
     fn fail_with_error_on_invalid_syntax() {
         let real_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/invalid-syntax.ron");
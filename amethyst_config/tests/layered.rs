@@ -0,0 +1,55 @@
+#![cfg(feature = "json")]
+
+use amethyst::{
+    core::{frame_limiter::FrameRateLimitConfig, LogLevelFilter, LoggerConfig},
+    window::DisplayConfig,
+};
+use amethyst_config::{ConfigLayer, ConfigLayers};
+
+fn env(name: &str, value: &str) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+#[test]
+fn layers_engine_configs() {
+    let vars = vec![
+        env("AMETHYST_DISPLAY__DIMENSIONS", "[1280, 720]"),
+        env("AMETHYST_DISPLAY__TITLE", "Layered"),
+        env("AMETHYST_LOGGER__LEVEL_FILTER", "Warn"),
+        env("AMETHYST_FRAME_LIMIT__STRATEGY", "Sleep"),
+    ];
+    let args = vec![
+        "--set",
+        "frame_limit.fps=30",
+        "--set",
+        "display.resizable=false",
+    ];
+
+    let display = ConfigLayers::new("display")
+        .with_env_vars(vars.clone())
+        .with_args(&args)
+        .load::<DisplayConfig>()
+        .unwrap();
+    assert_eq!(display.title, "Layered");
+    assert_eq!(display.dimensions, Some((1280, 720)));
+    assert!(!display.resizable);
+    assert_eq!(display.origin("decorations"), Some(&ConfigLayer::Default));
+
+    let logger = ConfigLayers::new("logger")
+        .with_env_vars(vars.clone())
+        .with_args(&args)
+        .load::<LoggerConfig>()
+        .unwrap();
+    assert_eq!(logger.level_filter, LogLevelFilter::Warn);
+
+    let frame_limit = ConfigLayers::new("frame_limit")
+        .with_env_vars(vars)
+        .with_args(&args)
+        .load::<FrameRateLimitConfig>()
+        .unwrap();
+    assert_eq!(frame_limit.fps, 30);
+    assert_eq!(
+        frame_limit.origin("fps"),
+        Some(&ConfigLayer::Arg("frame_limit.fps=30".to_string()))
+    );
+}
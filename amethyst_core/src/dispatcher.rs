@@ -1,8 +1,30 @@
+use std::{
+    any::type_name,
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+};
+
 use amethyst_error::Error;
 
-use crate::ecs::{
-    systems::{Executor, ParallelRunnable, Step},
-    Resources, Runnable, Schedule, World,
+use crate::{
+    ecs::{
+        systems::{Executor, ParallelRunnable, Step},
+        Resources, Runnable, Schedule, World,
+    },
+    run_condition::{ConditionFlag, Conditional, RunCondition},
+};
+
+mod metrics;
+mod schedule_info;
+
+use self::metrics::{MetricsCollector, Timed};
+pub use self::{
+    metrics::{
+        DispatcherId, DispatcherMetrics, ScheduleMetrics, StepMetrics, SystemMetrics, TimingStats,
+    },
+    schedule_info::{ScheduleInfo, StepInfo, StepKind, SystemInfo},
 };
 
 /// A `SystemBundle` is a structure that adds multiple systems to the [Dispatcher] and loads/unloads all required resources.
@@ -21,6 +43,11 @@ pub trait SystemBundle {
     fn unload(&mut self, _world: &mut World, _resources: &mut Resources) -> Result<(), Error> {
         Ok(())
     }
+
+    /// Name of the bundle reported by [`Dispatcher::schedule_info`]. Defaults to the type name.
+    fn name(&self) -> &'static str {
+        type_name::<Self>()
+    }
 }
 
 /// A System builds a `ParallelRunnable` for the Dispatcher
@@ -55,6 +82,106 @@ where
     }
 }
 
+/// Ordering constraints of an entry added to [`DispatcherBuilder`].
+///
+/// Entries are identified by labels. Several entries may carry the same label, in which case the
+/// label acts as a system set: every constraint referring to it applies to all members of the set.
+/// Entries without constraints keep the order in which they were added to the builder.
+///
+/// # Examples
+///
+/// ```
+/// use amethyst::core::dispatcher::SystemOrder;
+///
+/// let order = SystemOrder::new()
+///     .label("player_movement")
+///     .label("gameplay")
+///     .after("input")
+///     .before("transform");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SystemOrder {
+    labels: Vec<Cow<'static, str>>,
+    before: Vec<Cow<'static, str>>,
+    after: Vec<Cow<'static, str>>,
+}
+
+impl SystemOrder {
+    /// Creates an empty set of ordering constraints.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a label to the entry, which other entries can refer to.
+    #[must_use]
+    pub fn label<L: Into<Cow<'static, str>>>(mut self, label: L) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Requires the entry to run before every entry carrying `label`.
+    #[must_use]
+    pub fn before<L: Into<Cow<'static, str>>>(mut self, label: L) -> Self {
+        self.before.push(label.into());
+        self
+    }
+
+    /// Requires the entry to run after every entry carrying `label`.
+    #[must_use]
+    pub fn after<L: Into<Cow<'static, str>>>(mut self, label: L) -> Self {
+        self.after.push(label.into());
+        self
+    }
+
+    /// Labels carried by the entry.
+    #[must_use]
+    pub fn labels(&self) -> &[Cow<'static, str>] {
+        &self.labels
+    }
+
+    fn merge(&mut self, other: &SystemOrder) {
+        self.labels.extend(other.labels.iter().cloned());
+        self.before.extend(other.before.iter().cloned());
+        self.after.extend(other.after.iter().cloned());
+    }
+}
+
+/// Error produced when the ordering constraints of a [`DispatcherBuilder`] can't be satisfied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OrderError {
+    /// An entry is ordered relative to a label that no entry carries.
+    MissingLabel {
+        /// Entry declaring the constraint.
+        entry: String,
+        /// Label which couldn't be found.
+        label: String,
+    },
+    /// Ordering constraints form a cycle. Contains the entries of the cycle in execution order.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::MissingLabel { entry, label } => write!(
+                f,
+                "`{}` is ordered relative to label `{}`, but no system carries it",
+                entry, label
+            ),
+            OrderError::Cycle(entries) => write!(
+                f,
+                "System ordering constraints form a cycle: {} -> {}",
+                entries.join(" -> "),
+                entries.first().map_or("", String::as_str)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
 /// This structure is an intermediate step for building [Dispatcher]. When [`DispatcherBuilder::build`] is called,
 /// all system bundles are evaluated by calling [`SystemBundle::load`]. This structure is used to split systems
 /// (executable by [Schedule]) and system bundles (used for cleanup with unload).
@@ -67,6 +194,18 @@ pub struct DispatcherData<'a> {
     accumulator: Vec<Box<dyn ParallelRunnable + 'static>>,
     /// Bundles that can be later used for cleanup by calling [SystemBundle::unload].
     bundles: Vec<Box<dyn SystemBundle + 'a>>,
+    /// Entries collected from all bundles, waiting for their ordering to be resolved.
+    pending: Vec<DispatcherEntry>,
+    /// Constraints shared by all members of a system set.
+    set_orders: HashMap<Cow<'static, str>, SystemOrder>,
+    /// Run conditions evaluated by the [Dispatcher], with the flags of the entries they gate.
+    conditions: Vec<(Box<dyn RunCondition>, ConditionFlag)>,
+    /// Description of the systems held by the accumulator.
+    accumulator_info: Vec<SystemInfo>,
+    /// Description of the steps built so far.
+    schedule_info: ScheduleInfo,
+    /// Timing slots of the systems built so far.
+    metrics: MetricsCollector,
 }
 
 impl<'a> DispatcherData<'a> {
@@ -76,37 +215,327 @@ impl<'a> DispatcherData<'a> {
             std::mem::swap(&mut self.accumulator, &mut systems);
             let executor = Executor::new(systems);
             self.steps.push(Step::Systems(executor));
+            self.schedule_info.steps.push(StepInfo {
+                kind: StepKind::Systems,
+                systems: std::mem::take(&mut self.accumulator_info),
+            });
         }
     }
+
+    fn push_step(&mut self, step: Step, kind: StepKind, info: Option<SystemInfo>) {
+        self.finalize_executor();
+        self.steps.push(step);
+        self.schedule_info.steps.push(StepInfo {
+            kind,
+            systems: info.into_iter().collect(),
+        });
+    }
+
+    fn push_item(&mut self, entry: DispatcherEntry) {
+        let DispatcherEntry {
+            item,
+            order,
+            conditions,
+            bundle,
+        } = entry;
+        let conditional = !conditions.is_empty();
+        let item = if conditional {
+            gate_item(item, conditions)
+        } else {
+            item
+        };
+        let item = time_item(item, &mut self.metrics);
+        let describe = |mut info: SystemInfo| {
+            info.bundle = bundle.map(ToString::to_string);
+            info.labels = order.labels.iter().map(ToString::to_string).collect();
+            info.conditional = conditional;
+            info
+        };
+
+        match item {
+            DispatcherItem::System(s) => {
+                self.accumulator_info
+                    .push(describe(SystemInfo::from_runnable(&*s)));
+                self.accumulator.push(s);
+            }
+            DispatcherItem::FlushCmdBuffers => {
+                self.push_step(Step::FlushCmdBuffers, StepKind::FlushCmdBuffers, None);
+            }
+            DispatcherItem::ThreadLocalFn(f) => {
+                let info = describe(SystemInfo {
+                    name: "thread local function".to_string(),
+                    ..SystemInfo::default()
+                });
+                self.push_step(Step::ThreadLocalFn(f), StepKind::ThreadLocalFn, Some(info));
+            }
+            DispatcherItem::ThreadLocalSystem(s) => {
+                let info = describe(SystemInfo::from_runnable(&*s));
+                self.push_step(
+                    Step::ThreadLocalSystem(s),
+                    StepKind::ThreadLocalSystem,
+                    Some(info),
+                );
+            }
+            DispatcherItem::SystemBundle(_) => {
+                unreachable!("Bundles are unpacked before ordering is resolved")
+            }
+        }
+    }
+
+    /// Sorts pending entries according to their ordering constraints and turns them into steps.
+    ///
+    /// Among the entries whose constraints are satisfied, the one added first is always picked,
+    /// so entries without constraints keep their insertion order.
+    fn resolve(&mut self) -> Result<(), OrderError> {
+        let mut entries = std::mem::take(&mut self.pending);
+        for entry in &mut entries {
+            let mut inherited = SystemOrder::default();
+            for label in &entry.order.labels {
+                if let Some(set_order) = self.set_orders.get(label) {
+                    inherited.before.extend(set_order.before.iter().cloned());
+                    inherited.after.extend(set_order.after.iter().cloned());
+                }
+            }
+            entry.order.merge(&inherited);
+        }
+
+        let mut successors = vec![Vec::new(); entries.len()];
+        let mut in_degree = vec![0_usize; entries.len()];
+        {
+            let mut labelled = HashMap::<&str, Vec<usize>>::new();
+            for (index, entry) in entries.iter().enumerate() {
+                for label in &entry.order.labels {
+                    labelled.entry(label.as_ref()).or_default().push(index);
+                }
+            }
+
+            for (index, entry) in entries.iter().enumerate() {
+                let constraints = entry
+                    .order
+                    .before
+                    .iter()
+                    .map(|label| (label, true))
+                    .chain(entry.order.after.iter().map(|label| (label, false)));
+                for (label, before) in constraints {
+                    let others =
+                        labelled
+                            .get(label.as_ref())
+                            .ok_or_else(|| OrderError::MissingLabel {
+                                entry: entry.describe(),
+                                label: label.to_string(),
+                            })?;
+                    for &other in others.iter().filter(|&&other| other != index) {
+                        let (first, second) = if before {
+                            (index, other)
+                        } else {
+                            (other, index)
+                        };
+                        successors[first].push(second);
+                        in_degree[second] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut ready = in_degree
+            .iter()
+            .enumerate()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(index, _)| Reverse(index))
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(entries.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            sorted.push(index);
+            for &next in &successors[index] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if sorted.len() < entries.len() {
+            let cycle = find_cycle(&successors, &in_degree)
+                .into_iter()
+                .map(|index| entries[index].describe())
+                .collect();
+            return Err(OrderError::Cycle(cycle));
+        }
+
+        let mut entries = entries.into_iter().map(Some).collect::<Vec<_>>();
+        for index in sorted {
+            let entry = entries[index].take().expect("Entry was sorted twice");
+            self.push_item(entry);
+        }
+        self.finalize_executor();
+
+        Ok(())
+    }
+}
+
+/// Wraps an item so it only runs while all `conditions` are met.
+fn gate_item(item: DispatcherItem, conditions: Vec<ConditionFlag>) -> DispatcherItem {
+    match item {
+        DispatcherItem::System(system) => DispatcherItem::System(Box::new(Conditional {
+            flags: conditions,
+            system,
+        })),
+        DispatcherItem::ThreadLocalSystem(system) => {
+            DispatcherItem::ThreadLocalSystem(Box::new(Conditional {
+                flags: conditions,
+                system,
+            }))
+        }
+        DispatcherItem::ThreadLocalFn(mut f) => DispatcherItem::ThreadLocalFn(Box::new(
+            move |world: &mut World, resources: &mut Resources| {
+                if ConditionFlag::all_set(&conditions) {
+                    f(world, resources);
+                }
+            },
+        )),
+        item => item,
+    }
+}
+
+/// Wraps an item so its wall time is recorded by `metrics`.
+fn time_item(item: DispatcherItem, metrics: &mut MetricsCollector) -> DispatcherItem {
+    match item {
+        DispatcherItem::System(system) => DispatcherItem::System(Box::new(Timed {
+            slot: metrics.system_slot(),
+            system,
+        })),
+        DispatcherItem::ThreadLocalSystem(system) => {
+            DispatcherItem::ThreadLocalSystem(Box::new(Timed {
+                slot: metrics.system_slot(),
+                system,
+            }))
+        }
+        DispatcherItem::ThreadLocalFn(f) => {
+            DispatcherItem::ThreadLocalFn(metrics.system_slot().time_fn(f))
+        }
+        item => item,
+    }
+}
+
+/// Finds a cycle among the entries left unsorted, which are the ones with a non-zero in-degree.
+/// Returns the indices of the cycle in execution order.
+fn find_cycle(successors: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+    let unsorted = |index: usize| in_degree[index] > 0;
+
+    // Every unsorted entry has at least one unsorted predecessor, so walking predecessors
+    // eventually revisits an entry, which must be part of a cycle.
+    let mut predecessor = vec![None; successors.len()];
+    for (from, nexts) in successors.iter().enumerate() {
+        if unsorted(from) {
+            for &to in nexts.iter().filter(|&&to| unsorted(to)) {
+                predecessor[to] = Some(from);
+            }
+        }
+    }
+
+    let mut visited = vec![false; successors.len()];
+    let mut current = (0..successors.len())
+        .find(|&index| unsorted(index))
+        .expect("Cycle searched without unsorted entries");
+    while !visited[current] {
+        visited[current] = true;
+        current = predecessor[current].expect("Unsorted entry without unsorted predecessor");
+    }
+
+    let start = current;
+    let mut cycle = vec![start];
+    let mut current = predecessor[start].expect("Unsorted entry without unsorted predecessor");
+    while current != start {
+        cycle.push(current);
+        current = predecessor[current].expect("Unsorted entry without unsorted predecessor");
+    }
+    cycle.reverse();
+
+    // Start with the entry added first, so the reported cycle doesn't depend on traversal order.
+    if let Some(first) = (0..cycle.len()).min_by_key(|&position| cycle[position]) {
+        cycle.rotate_left(first);
+    }
+    cycle
 }
 
 /// A builder which is used to construct [Dispatcher] from multiple systems and system bundles.
+///
+/// Systems are executed in the order they were added, unless they are given ordering
+/// constraints with [`SystemOrder`]. Constraints are resolved across all bundles when the
+/// [Dispatcher] is built, so a bundle can run its systems relative to systems of other bundles
+/// regardless of the order in which the bundles were added.
 #[derive(Default)]
 #[allow(missing_debug_implementations)]
 pub struct DispatcherBuilder {
-    items: Vec<DispatcherItem>,
+    items: Vec<DispatcherEntry>,
+    set_orders: Vec<(Cow<'static, str>, SystemOrder)>,
+    conditions: Vec<(Box<dyn RunCondition>, ConditionFlag)>,
 }
 
 impl<'a> DispatcherBuilder {
     /// Adds a system to the schedule.
     pub fn add_system<S: System + 'a>(&mut self, system: S) -> &mut Self {
+        self.add_system_ordered(system, SystemOrder::default())
+    }
+
+    /// Adds a system to the schedule with the given ordering constraints.
+    pub fn add_system_ordered<S: System + 'a>(
+        &mut self,
+        system: S,
+        order: SystemOrder,
+    ) -> &mut Self {
         log::debug!("Building system");
-        self.items.push(DispatcherItem::System(system.build()));
-        self
+        self.push(DispatcherItem::System(system.build()), order)
+    }
+
+    /// Adds a system to the schedule which only runs while `condition` is met.
+    ///
+    /// See the [`run_condition`](crate::run_condition) module for details.
+    pub fn add_system_if<S: System + 'a, C: RunCondition>(
+        &mut self,
+        system: S,
+        condition: C,
+    ) -> &mut Self {
+        log::debug!("Building system");
+        self.push_if(DispatcherItem::System(system.build()), condition)
     }
 
     /// Adds a thread local system to the schedule. This system will be executed on the main thread.
     pub fn add_thread_local<T: ThreadLocalSystem<'a> + 'a>(&mut self, system: T) -> &mut Self {
-        self.items
-            .push(DispatcherItem::ThreadLocalSystem(system.build()));
-        self
+        self.add_thread_local_ordered(system, SystemOrder::default())
+    }
+
+    /// Adds a thread local system to the schedule with the given ordering constraints.
+    pub fn add_thread_local_ordered<T: ThreadLocalSystem<'a> + 'a>(
+        &mut self,
+        system: T,
+        order: SystemOrder,
+    ) -> &mut Self {
+        self.push(DispatcherItem::ThreadLocalSystem(system.build()), order)
+    }
+
+    /// Adds a thread local system to the schedule which only runs while `condition` is met.
+    pub fn add_thread_local_if<T: ThreadLocalSystem<'a> + 'a, C: RunCondition>(
+        &mut self,
+        system: T,
+        condition: C,
+    ) -> &mut Self {
+        self.push_if(DispatcherItem::ThreadLocalSystem(system.build()), condition)
     }
 
     /// Waits for executing systems to complete, and the flushes all outstanding system
     /// command buffers.
     pub fn flush(&mut self) -> &mut Self {
-        self.items.push(DispatcherItem::FlushCmdBuffers);
-        self
+        self.flush_ordered(SystemOrder::default())
+    }
+
+    /// Flushes the command buffers with the given ordering constraints.
+    ///
+    /// A flush without constraints can be moved before the systems added ahead of it when they
+    /// are ordered after other systems, so a flush needed by ordered systems should be ordered
+    /// relative to them as well.
+    pub fn flush_ordered(&mut self, order: SystemOrder) -> &mut Self {
+        self.push(DispatcherItem::FlushCmdBuffers, order)
     }
 
     /// Adds a thread local function to the schedule. This function will be executed on the main thread.
@@ -114,50 +543,162 @@ impl<'a> DispatcherBuilder {
         &mut self,
         f: F,
     ) -> &mut Self {
-        self.items.push(DispatcherItem::ThreadLocalFn(
-            Box::new(f) as Box<dyn FnMut(&mut World, &mut Resources) + 'static>
-        ));
-        self
+        self.add_thread_local_fn_ordered(f, SystemOrder::default())
+    }
+
+    /// Adds a thread local function to the schedule with the given ordering constraints.
+    pub fn add_thread_local_fn_ordered<F: FnMut(&mut World, &mut Resources) + 'static>(
+        &mut self,
+        f: F,
+        order: SystemOrder,
+    ) -> &mut Self {
+        self.push(
+            DispatcherItem::ThreadLocalFn(
+                Box::new(f) as Box<dyn FnMut(&mut World, &mut Resources) + 'static>
+            ),
+            order,
+        )
+    }
+
+    /// Adds a thread local function to the schedule which only runs while `condition` is met.
+    pub fn add_thread_local_fn_if<F, C>(&mut self, f: F, condition: C) -> &mut Self
+    where
+        F: FnMut(&mut World, &mut Resources) + 'static,
+        C: RunCondition,
+    {
+        self.push_if(
+            DispatcherItem::ThreadLocalFn(
+                Box::new(f) as Box<dyn FnMut(&mut World, &mut Resources) + 'static>
+            ),
+            condition,
+        )
     }
 
     /// Adds [`SystemBundle`] to the dispatcher. System bundles allow inserting multiple systems
     /// and initialize any required entities or resources.
     pub fn add_bundle<T: SystemBundle + 'static>(&mut self, bundle: T) -> &mut Self {
-        self.items
-            .push(DispatcherItem::SystemBundle(Box::new(bundle)));
+        self.add_bundle_ordered(bundle, SystemOrder::default())
+    }
+
+    /// Adds [`SystemBundle`] to the dispatcher with the given ordering constraints. The labels and
+    /// constraints are applied to every entry added by the bundle.
+    pub fn add_bundle_ordered<T: SystemBundle + 'static>(
+        &mut self,
+        bundle: T,
+        order: SystemOrder,
+    ) -> &mut Self {
+        self.push(DispatcherItem::SystemBundle(Box::new(bundle)), order)
+    }
+
+    /// Adds [`SystemBundle`] to the dispatcher. Every entry added by the bundle only runs while
+    /// `condition` is met. This makes it possible to restrict a group of systems to a specific
+    /// game state without building a separate [Dispatcher] for it.
+    ///
+    /// The bundle itself is always loaded and unloaded together with the [Dispatcher].
+    pub fn add_bundle_if<T: SystemBundle + 'static, C: RunCondition>(
+        &mut self,
+        bundle: T,
+        condition: C,
+    ) -> &mut Self {
+        self.push_if(DispatcherItem::SystemBundle(Box::new(bundle)), condition)
+    }
+
+    /// Adds ordering constraints to every entry carrying the label `set`. Only the `before` and
+    /// `after` constraints of `order` are taken into account.
+    pub fn configure_set<L: Into<Cow<'static, str>>>(
+        &mut self,
+        set: L,
+        order: SystemOrder,
+    ) -> &mut Self {
+        self.set_orders.push((set.into(), order));
+        self
+    }
+
+    fn push(&mut self, item: DispatcherItem, order: SystemOrder) -> &mut Self {
+        self.items.push(DispatcherEntry {
+            item,
+            order,
+            conditions: Vec::new(),
+            bundle: None,
+        });
         self
     }
 
-    /// Evaluates all system bundles (recursively). Resulting systems and unpacked bundles are put into [`DispatcherData`].
+    fn push_if<C: RunCondition>(&mut self, item: DispatcherItem, condition: C) -> &mut Self {
+        let flag = ConditionFlag::default();
+        self.conditions.push((Box::new(condition), flag.clone()));
+        self.items.push(DispatcherEntry {
+            item,
+            order: SystemOrder::default(),
+            conditions: vec![flag],
+            bundle: None,
+        });
+        self
+    }
+
+    /// Evaluates all system bundles (recursively) and resolves the ordering constraints of the
+    /// resulting entries. Resulting systems and unpacked bundles are put into [`DispatcherData`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OrderError`] if a constraint refers to a missing label or if the constraints
+    /// form a cycle.
     pub fn load(
         &'a mut self,
         world: &mut World,
         resources: &mut Resources,
         data: &mut DispatcherData<'static>,
     ) -> Result<(), Error> {
-        for item in self.items.drain(..) {
-            match item {
-                DispatcherItem::System(s) => data.accumulator.push(s),
-                DispatcherItem::FlushCmdBuffers => {
-                    data.finalize_executor();
-                    data.steps.push(Step::FlushCmdBuffers);
-                }
-                DispatcherItem::ThreadLocalFn(f) => {
-                    data.finalize_executor();
-                    data.steps.push(Step::ThreadLocalFn(f));
-                }
-                DispatcherItem::ThreadLocalSystem(s) => {
-                    data.finalize_executor();
-                    data.steps.push(Step::ThreadLocalSystem(s));
-                }
+        self.collect(world, resources, data, &SystemOrder::default(), &[], None)?;
+        data.resolve()?;
+
+        Ok(())
+    }
+
+    /// Evaluates all system bundles (recursively) and moves the resulting entries into `data`,
+    /// together with the constraints and run conditions inherited from enclosing bundles, and
+    /// the name of the innermost one.
+    fn collect(
+        &mut self,
+        world: &mut World,
+        resources: &mut Resources,
+        data: &mut DispatcherData<'static>,
+        inherited_order: &SystemOrder,
+        inherited_conditions: &[ConditionFlag],
+        bundle_name: Option<&'static str>,
+    ) -> Result<(), Error> {
+        for (set, order) in self.set_orders.drain(..) {
+            data.set_orders.entry(set).or_default().merge(&order);
+        }
+        data.conditions.append(&mut self.conditions);
+
+        for mut entry in self.items.drain(..) {
+            entry.order.merge(inherited_order);
+            entry.conditions.extend_from_slice(inherited_conditions);
+            match entry.item {
                 DispatcherItem::SystemBundle(mut bundle) => {
                     {
                         let mut builder = DispatcherBuilder::default();
                         bundle.load(world, resources, &mut builder)?;
-                        builder.load(world, resources, data)?;
+                        builder.collect(
+                            world,
+                            resources,
+                            data,
+                            &entry.order,
+                            &entry.conditions,
+                            Some(bundle.name()),
+                        )?;
                     }
                     data.bundles.push(bundle);
                 }
+                item => {
+                    data.pending.push(DispatcherEntry {
+                        item,
+                        order: entry.order,
+                        conditions: entry.conditions,
+                        bundle: bundle_name,
+                    });
+                }
             }
         }
 
@@ -165,6 +706,10 @@ impl<'a> DispatcherBuilder {
     }
 
     /// Finalizes the builder into a [Dispatcher]. This also evaluates all system bundles by calling [`SystemBundle::load`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a bundle fails to load or if the ordering constraints can't be satisfied.
     pub fn build(
         &mut self,
         world: &mut World,
@@ -174,9 +719,15 @@ impl<'a> DispatcherBuilder {
 
         self.flush().load(world, resources, &mut data)?;
 
+        let mut metrics = data.metrics;
+        metrics.register(resources, &data.schedule_info);
+
         Ok(Dispatcher {
-            schedule: Schedule::from(data.steps),
+            schedule: Schedule::from(metrics.instrument_steps(data.steps)),
             bundles: data.bundles,
+            conditions: data.conditions,
+            schedule_info: data.schedule_info,
+            metrics,
         })
     }
 }
@@ -196,19 +747,80 @@ pub enum DispatcherItem {
     SystemBundle(Box<dyn SystemBundle + 'static>),
 }
 
+/// A [`DispatcherItem`] together with its ordering constraints, run conditions and the name of
+/// the bundle which added it.
+struct DispatcherEntry {
+    item: DispatcherItem,
+    order: SystemOrder,
+    conditions: Vec<ConditionFlag>,
+    bundle: Option<&'static str>,
+}
+
+impl DispatcherEntry {
+    /// Name of the entry used in error messages.
+    fn describe(&self) -> String {
+        if let Some(label) = self.order.labels.first() {
+            return label.to_string();
+        }
+
+        match &self.item {
+            DispatcherItem::System(s) => s
+                .name()
+                .map_or_else(|| "unnamed system".to_string(), ToString::to_string),
+            DispatcherItem::ThreadLocalSystem(s) => s.name().map_or_else(
+                || "unnamed thread local system".to_string(),
+                ToString::to_string,
+            ),
+            DispatcherItem::FlushCmdBuffers => "command buffer flush".to_string(),
+            DispatcherItem::ThreadLocalFn(_) => "thread local function".to_string(),
+            DispatcherItem::SystemBundle(_) => "system bundle".to_string(),
+        }
+    }
+}
+
 /// Dispatcher is created by [`DispatcherBuilder`] and contains [Schedule] used to execute all systems.
 #[allow(missing_debug_implementations)]
 pub struct Dispatcher {
     // Used to execute unload on system bundles once dispatcher is disposed.
     bundles: Vec<Box<dyn SystemBundle>>,
     schedule: Schedule,
+    // Evaluated before every execution, their results gate conditional entries.
+    conditions: Vec<(Box<dyn RunCondition>, ConditionFlag)>,
+    schedule_info: ScheduleInfo,
+    // Fills the entry of this dispatcher of the `DispatcherMetrics` resource after every execution.
+    metrics: MetricsCollector,
 }
 
 impl Dispatcher {
-    /// Executes systems according to the [Schedule].
+    /// Executes systems according to the [Schedule]. Run conditions are evaluated once, before
+    /// any system runs. Timings are published in the entry of this dispatcher of the
+    /// [`DispatcherMetrics`] resource.
     pub fn execute(&mut self, world: &mut World, resources: &mut Resources) {
+        self.metrics.begin_frame();
+
+        for (condition, flag) in &mut self.conditions {
+            flag.set(condition.evaluate(world, resources));
+        }
+
         // TODO: use ArcThreadPool from resources to dispatch legion
         self.schedule.execute(world, resources);
+
+        self.metrics.end_frame(resources);
+    }
+
+    /// Identifies this dispatcher, e.g. to find its metrics in the [`DispatcherMetrics`]
+    /// resource.
+    #[must_use]
+    pub fn id(&self) -> DispatcherId {
+        self.metrics.id()
+    }
+
+    /// Describes the steps of the [Schedule] and the systems they execute. Use
+    /// [`ScheduleInfo::to_dot`] or [`ScheduleInfo::to_json`] to export it, or print it for a
+    /// quick overview.
+    #[must_use]
+    pub fn schedule_info(&self) -> &ScheduleInfo {
+        &self.schedule_info
     }
 
     /// Unloads any resources by calling [`SystemBundle::unload`] for stored system bundles and returns [`DispatcherBuilder`]
@@ -217,6 +829,7 @@ impl Dispatcher {
         for bundle in &mut self.bundles {
             bundle.unload(world, resources)?;
         }
+        self.metrics.unregister(resources);
 
         Ok(())
     }
@@ -224,9 +837,14 @@ impl Dispatcher {
 
 #[cfg(test)]
 pub mod tests {
-    use super::*;
     use legion::SystemBuilder;
 
+    use super::*;
+    use crate::{
+        run_condition::{every_n_ticks, resource_equals, resource_exists},
+        transform::{TransformBundle, TRANSFORM_SET},
+    };
+
     struct MyResource(bool);
 
     struct MySystem;
@@ -303,4 +921,253 @@ pub mod tests {
 
         assert!(resources.get::<MyResource>().unwrap().0, true);
     }
+
+    #[derive(Default)]
+    struct ExecutionLog(Vec<&'static str>);
+
+    fn logging_system(name: &'static str) -> impl System {
+        move || {
+            SystemBuilder::new(name)
+                .write_resource::<ExecutionLog>()
+                .build(move |_, _, log, _| log.0.push(name))
+        }
+    }
+
+    fn execute_once(builder: &mut DispatcherBuilder) -> Vec<&'static str> {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(ExecutionLog::default());
+
+        let mut dispatcher = builder.build(&mut world, &mut resources).unwrap();
+        dispatcher.execute(&mut world, &mut resources);
+
+        let log = resources.get::<ExecutionLog>().unwrap();
+        log.0.clone()
+    }
+
+    fn build_error(builder: &mut DispatcherBuilder) -> String {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        builder
+            .build(&mut world, &mut resources)
+            .err()
+            .expect("Expected ordering to fail")
+            .to_string()
+    }
+
+    #[test]
+    fn dispatcher_keeps_insertion_order_without_constraints() {
+        let mut builder = DispatcherBuilder::default();
+        builder
+            .add_system(logging_system("a"))
+            .add_system(logging_system("b"))
+            .add_system(logging_system("c"));
+
+        assert_eq!(execute_once(&mut builder), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn dispatcher_orders_systems_by_label() {
+        let mut builder = DispatcherBuilder::default();
+        builder
+            .add_system_ordered(logging_system("a"), SystemOrder::new().after("c"))
+            .add_system_ordered(logging_system("b"), SystemOrder::new().label("b"))
+            .add_system_ordered(logging_system("c"), SystemOrder::new().label("c"))
+            .add_system_ordered(logging_system("d"), SystemOrder::new().before("b"));
+
+        assert_eq!(execute_once(&mut builder), vec!["c", "a", "d", "b"]);
+    }
+
+    #[test]
+    fn dispatcher_orders_system_sets() {
+        let mut builder = DispatcherBuilder::default();
+        builder
+            .configure_set("gameplay", SystemOrder::new().after("input"))
+            .add_system_ordered(logging_system("move"), SystemOrder::new().label("gameplay"))
+            .add_system_ordered(
+                logging_system("shoot"),
+                SystemOrder::new().label("gameplay"),
+            )
+            .add_system_ordered(logging_system("input"), SystemOrder::new().label("input"))
+            .add_system_ordered(logging_system("render"), SystemOrder::new().before("input"));
+
+        assert_eq!(
+            execute_once(&mut builder),
+            vec!["render", "input", "move", "shoot"]
+        );
+    }
+
+    #[test]
+    fn dispatcher_applies_bundle_order_to_its_systems() {
+        struct GameplayBundle;
+
+        impl SystemBundle for GameplayBundle {
+            fn load(
+                &mut self,
+                _world: &mut World,
+                _resources: &mut Resources,
+                builder: &mut DispatcherBuilder,
+            ) -> Result<(), Error> {
+                builder
+                    .add_system(logging_system("move"))
+                    .add_system(logging_system("shoot"));
+                Ok(())
+            }
+        }
+
+        let mut builder = DispatcherBuilder::default();
+        builder
+            .add_bundle_ordered(
+                GameplayBundle,
+                SystemOrder::new().label("gameplay").after("input"),
+            )
+            .add_system_ordered(logging_system("input"), SystemOrder::new().label("input"))
+            .add_system_ordered(logging_system("ui"), SystemOrder::new().before("gameplay"));
+
+        assert_eq!(
+            execute_once(&mut builder),
+            vec!["input", "ui", "move", "shoot"]
+        );
+    }
+
+    #[test]
+    fn dispatcher_keeps_transform_flush_between_its_systems() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        let dispatcher = DispatcherBuilder::default()
+            .add_bundle(TransformBundle)
+            .add_system_ordered(
+                logging_system("camera"),
+                SystemOrder::new().before(TRANSFORM_SET),
+            )
+            .build(&mut world, &mut resources)
+            .unwrap();
+
+        let steps: Vec<_> = dispatcher
+            .schedule_info()
+            .steps
+            .iter()
+            .map(|step| {
+                let names: Vec<_> = step.systems.iter().map(|s| s.name.as_str()).collect();
+                (step.kind, names)
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (StepKind::Systems, vec!["camera", "ParentUpdateSystem"]),
+                (StepKind::FlushCmdBuffers, vec![]),
+                (StepKind::Systems, vec!["TransformSystem"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn dispatcher_evaluates_conditions_once_per_execute() {
+        #[derive(PartialEq)]
+        enum Mode {
+            Enabled,
+            Disabled,
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(ExecutionLog::default());
+        resources.insert(Mode::Enabled);
+
+        let mut dispatcher = DispatcherBuilder::default()
+            .add_system(|| {
+                SystemBuilder::new("disable")
+                    .write_resource::<Mode>()
+                    .build(|_, _, mode, _| **mode = Mode::Disabled)
+            })
+            .add_system_if(logging_system("gated"), resource_equals(Mode::Enabled))
+            .build(&mut world, &mut resources)
+            .unwrap();
+
+        dispatcher.execute(&mut world, &mut resources);
+        dispatcher.execute(&mut world, &mut resources);
+
+        assert_eq!(resources.get::<ExecutionLog>().unwrap().0, vec!["gated"]);
+    }
+
+    #[test]
+    fn dispatcher_applies_bundle_condition_to_its_entries() {
+        struct GatedBundle;
+
+        impl SystemBundle for GatedBundle {
+            fn load(
+                &mut self,
+                _world: &mut World,
+                _resources: &mut Resources,
+                builder: &mut DispatcherBuilder,
+            ) -> Result<(), Error> {
+                builder
+                    .add_system(logging_system("system"))
+                    .add_system_if(logging_system("nested"), every_n_ticks(2))
+                    .add_thread_local_fn(|_, resources| {
+                        resources.get_mut::<ExecutionLog>().unwrap().0.push("fn");
+                    });
+                Ok(())
+            }
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(ExecutionLog::default());
+
+        let mut dispatcher = DispatcherBuilder::default()
+            .add_bundle_if(GatedBundle, resource_exists::<u32>())
+            .build(&mut world, &mut resources)
+            .unwrap();
+
+        dispatcher.execute(&mut world, &mut resources);
+        assert!(resources.get::<ExecutionLog>().unwrap().0.is_empty());
+
+        resources.insert(0_u32);
+        for _ in 0..2 {
+            dispatcher.execute(&mut world, &mut resources);
+        }
+        assert_eq!(
+            resources.get::<ExecutionLog>().unwrap().0,
+            vec!["system", "fn", "system", "nested", "fn"]
+        );
+    }
+
+    #[test]
+    fn dispatcher_reports_missing_label() {
+        let mut builder = DispatcherBuilder::default();
+        builder.add_system_ordered(
+            logging_system("a"),
+            SystemOrder::new().label("a").after("missing"),
+        );
+
+        let error = build_error(&mut builder);
+        assert!(error.contains("`a`"), "{}", error);
+        assert!(error.contains("`missing`"), "{}", error);
+    }
+
+    #[test]
+    fn dispatcher_reports_cycle() {
+        let mut builder = DispatcherBuilder::default();
+        builder
+            .add_system(logging_system("unrelated"))
+            .add_system_ordered(
+                logging_system("a"),
+                SystemOrder::new().label("a").after("c"),
+            )
+            .add_system_ordered(
+                logging_system("b"),
+                SystemOrder::new().label("b").after("a"),
+            )
+            .add_system_ordered(
+                logging_system("c"),
+                SystemOrder::new().label("c").after("b"),
+            );
+
+        let error = build_error(&mut builder);
+        assert!(error.contains("a -> b -> c -> a"), "{}", error);
+    }
 }
@@ -0,0 +1,639 @@
+//! Timing metrics collected by the [`Dispatcher`](super::Dispatcher).
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    convert::TryFrom,
+    fmt,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use legion::{
+    storage::ComponentTypeId,
+    systems::{CommandBuffer, ResourceTypeId, Runnable, Step, SystemId, UnsafeResources},
+    world::{ArchetypeAccess, WorldId},
+    Resources, World,
+};
+
+use super::schedule_info::{ScheduleInfo, StepKind};
+use crate::timing::Stopwatch;
+
+/// Number of frames averaged by default.
+const DEFAULT_WINDOW: usize = 60;
+
+static NEXT_DISPATCHER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Wall time statistics of a frame, a step or a system.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimingStats {
+    last: Duration,
+    worst: Duration,
+    sum: Duration,
+    samples: VecDeque<Duration>,
+}
+
+impl TimingStats {
+    /// Time taken during the last frame.
+    #[must_use]
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    /// Average time taken over the last frames, see [`ScheduleMetrics::window`].
+    #[must_use]
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() {
+            Duration::default()
+        } else {
+            self.sum / u32::try_from(self.samples.len()).unwrap_or(u32::MAX)
+        }
+    }
+
+    /// Longest time taken during a single frame since the metrics were last reset.
+    #[must_use]
+    pub fn worst(&self) -> Duration {
+        self.worst
+    }
+
+    fn record(&mut self, sample: Duration, window: usize) {
+        self.last = sample;
+        self.worst = self.worst.max(sample);
+        self.sum += sample;
+        self.samples.push_back(sample);
+        self.trim(window);
+    }
+
+    fn trim(&mut self, window: usize) {
+        while self.samples.len() > window {
+            if let Some(sample) = self.samples.pop_front() {
+                self.sum -= sample;
+            }
+        }
+    }
+}
+
+/// Timing metrics of a schedule step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepMetrics {
+    kind: StepKind,
+    timing: TimingStats,
+}
+
+impl StepMetrics {
+    /// Kind of the step.
+    #[must_use]
+    pub fn kind(&self) -> StepKind {
+        self.kind
+    }
+
+    /// Wall time of the step, including the time spent waiting for its systems to finish.
+    #[must_use]
+    pub fn timing(&self) -> &TimingStats {
+        &self.timing
+    }
+}
+
+/// Timing metrics of a system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemMetrics {
+    name: String,
+    step: usize,
+    timing: TimingStats,
+}
+
+impl SystemMetrics {
+    /// Name of the system, as reported by [`SystemInfo::name`](super::SystemInfo::name).
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Index of the step executing the system.
+    #[must_use]
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Wall time of the system. Systems skipped by a run condition report close to zero.
+    #[must_use]
+    pub fn timing(&self) -> &TimingStats {
+        &self.timing
+    }
+}
+
+/// Identifies a [`Dispatcher`](super::Dispatcher), see [`Dispatcher::id`](super::Dispatcher::id).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DispatcherId(u64);
+
+impl fmt::Display for DispatcherId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dispatcher {}", self.0)
+    }
+}
+
+/// Resource holding the per-system and per-step wall time of every
+/// [`Dispatcher`](super::Dispatcher), e.g. both the frame and the fixed update dispatchers of an
+/// application.
+///
+/// Every dispatcher adds its [`ScheduleMetrics`] when it is built by
+/// [`DispatcherBuilder::build`](super::DispatcherBuilder::build), updates them at the end of
+/// every [`Dispatcher::execute`](super::Dispatcher::execute), and removes them when it is
+/// unloaded. The resource is removed along with the metrics of the last dispatcher.
+///
+/// # Examples
+///
+/// ```
+/// use amethyst::core::{
+///     dispatcher::DispatcherMetrics,
+///     ecs::{DispatcherBuilder, Resources, SystemBuilder, World},
+/// };
+///
+/// let mut world = World::default();
+/// let mut resources = Resources::default();
+///
+/// let mut dispatcher = DispatcherBuilder::default()
+///     .add_system(|| SystemBuilder::new("PhysicsSystem").build(|_, _, _, _| {}))
+///     .build(&mut world, &mut resources)
+///     .unwrap();
+/// dispatcher.execute(&mut world, &mut resources);
+///
+/// let metrics = resources.get::<DispatcherMetrics>().unwrap();
+/// let physics = metrics.system("PhysicsSystem").unwrap();
+/// println!(
+///     "PhysicsSystem: {:?} on average, {:?} at worst",
+///     physics.timing().average(),
+///     physics.timing().worst()
+/// );
+/// println!("{}", metrics.get(dispatcher.id()).unwrap());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DispatcherMetrics {
+    dispatchers: BTreeMap<DispatcherId, ScheduleMetrics>,
+}
+
+impl DispatcherMetrics {
+    /// Metrics of the dispatcher `id`.
+    #[must_use]
+    pub fn get(&self, id: DispatcherId) -> Option<&ScheduleMetrics> {
+        self.dispatchers.get(&id)
+    }
+
+    /// Metrics of the dispatcher `id`, to change their window or reset them.
+    pub fn get_mut(&mut self, id: DispatcherId) -> Option<&mut ScheduleMetrics> {
+        self.dispatchers.get_mut(&id)
+    }
+
+    /// Metrics of every dispatcher, in the order the dispatchers were built.
+    pub fn iter(&self) -> impl Iterator<Item = (DispatcherId, &ScheduleMetrics)> {
+        self.dispatchers.iter().map(|(id, metrics)| (*id, metrics))
+    }
+
+    /// Metrics of the first system called `name`, searching the dispatchers in the order they
+    /// were built.
+    #[must_use]
+    pub fn system(&self, name: &str) -> Option<&SystemMetrics> {
+        self.dispatchers
+            .values()
+            .find_map(|metrics| metrics.system(name))
+    }
+}
+
+impl fmt::Display for DispatcherMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (id, metrics) in &self.dispatchers {
+            writeln!(f, "{}:", id)?;
+            write!(f, "{}", metrics)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-system and per-step wall time of a [`Dispatcher`](super::Dispatcher), part of the
+/// [`DispatcherMetrics`] resource.
+///
+/// Systems and steps are listed in the same order as in
+/// [`Dispatcher::schedule_info`](super::Dispatcher::schedule_info).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduleMetrics {
+    window: usize,
+    frames: u64,
+    frame: TimingStats,
+    steps: Vec<StepMetrics>,
+    systems: Vec<SystemMetrics>,
+}
+
+impl ScheduleMetrics {
+    fn new(info: &ScheduleInfo) -> Self {
+        let steps = info
+            .steps
+            .iter()
+            .map(|step| StepMetrics {
+                kind: step.kind,
+                timing: TimingStats::default(),
+            })
+            .collect();
+        let systems = info
+            .steps
+            .iter()
+            .enumerate()
+            .flat_map(|(index, step)| {
+                step.systems.iter().map(move |system| SystemMetrics {
+                    name: system.name.clone(),
+                    step: index,
+                    timing: TimingStats::default(),
+                })
+            })
+            .collect();
+
+        ScheduleMetrics {
+            window: DEFAULT_WINDOW,
+            frames: 0,
+            frame: TimingStats::default(),
+            steps,
+            systems,
+        }
+    }
+
+    /// Number of frames recorded since the metrics were last reset.
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Wall time of the whole [`Dispatcher::execute`](super::Dispatcher::execute), including the
+    /// evaluation of run conditions.
+    #[must_use]
+    pub fn frame(&self) -> &TimingStats {
+        &self.frame
+    }
+
+    /// Metrics of every step, in execution order.
+    #[must_use]
+    pub fn steps(&self) -> &[StepMetrics] {
+        &self.steps
+    }
+
+    /// Metrics of every system, in execution order.
+    #[must_use]
+    pub fn systems(&self) -> &[SystemMetrics] {
+        &self.systems
+    }
+
+    /// Metrics of the first system called `name`.
+    #[must_use]
+    pub fn system(&self, name: &str) -> Option<&SystemMetrics> {
+        self.systems.iter().find(|system| system.name == name)
+    }
+
+    /// Number of frames over which averages are computed. Defaults to 60.
+    #[must_use]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Sets the number of frames over which averages are computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    pub fn set_window(&mut self, window: usize) {
+        assert!(window > 0, "Metrics window must be at least one frame");
+        self.window = window;
+        for timing in self.timings_mut() {
+            timing.trim(window);
+        }
+    }
+
+    /// Clears all recorded samples, including the worst times.
+    pub fn reset(&mut self) {
+        self.frames = 0;
+        for timing in self.timings_mut() {
+            *timing = TimingStats::default();
+        }
+    }
+
+    fn timings_mut(&mut self) -> impl Iterator<Item = &mut TimingStats> {
+        std::iter::once(&mut self.frame)
+            .chain(self.steps.iter_mut().map(|step| &mut step.timing))
+            .chain(self.systems.iter_mut().map(|system| &mut system.timing))
+    }
+
+    fn record(&mut self, frame: Duration, steps: &[MetricSlot], systems: &[MetricSlot]) {
+        let window = self.window;
+        self.frames += 1;
+        self.frame.record(frame, window);
+        for (step, slot) in self.steps.iter_mut().zip(steps) {
+            step.timing.record(slot.get(), window);
+        }
+        for (system, slot) in self.systems.iter_mut().zip(systems) {
+            system.timing.record(slot.get(), window);
+        }
+    }
+}
+
+impl fmt::Display for ScheduleMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "frame: {:?} avg, {:?} worst",
+            self.frame.average(),
+            self.frame.worst()
+        )?;
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "step {} ({}): {:?} avg, {:?} worst",
+                index,
+                step.kind,
+                step.timing.average(),
+                step.timing.worst()
+            )?;
+            for system in self.systems.iter().filter(|system| system.step == index) {
+                writeln!(
+                    f,
+                    "    {}: {:?} avg, {:?} worst",
+                    system.name,
+                    system.timing.average(),
+                    system.timing.worst()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Duration measured during the current frame, shared with the instrumented step or system.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetricSlot(Arc<AtomicU64>);
+
+impl MetricSlot {
+    fn set(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.0.store(nanos, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Wraps a thread local function so its wall time is recorded in this slot.
+    pub(crate) fn time_fn(
+        &self,
+        mut f: Box<dyn FnMut(&mut World, &mut Resources) + 'static>,
+    ) -> Box<dyn FnMut(&mut World, &mut Resources) + 'static> {
+        let slot = self.clone();
+        Box::new(move |world: &mut World, resources: &mut Resources| {
+            let mut watch = Stopwatch::new();
+            watch.start();
+            f(world, resources);
+            watch.stop();
+            slot.set(watch.elapsed());
+        })
+    }
+}
+
+/// A system whose wall time is recorded in a [`MetricSlot`].
+pub(crate) struct Timed<S: ?Sized> {
+    pub(crate) slot: MetricSlot,
+    pub(crate) system: Box<S>,
+}
+
+impl<S> Runnable for Timed<S>
+where
+    S: Runnable + ?Sized,
+{
+    fn name(&self) -> Option<&SystemId> {
+        self.system.name()
+    }
+
+    fn reads(&self) -> (&[ResourceTypeId], &[ComponentTypeId]) {
+        self.system.reads()
+    }
+
+    fn writes(&self) -> (&[ResourceTypeId], &[ComponentTypeId]) {
+        self.system.writes()
+    }
+
+    fn prepare(&mut self, world: &World) {
+        self.system.prepare(world);
+    }
+
+    fn accesses_archetypes(&self) -> &ArchetypeAccess {
+        self.system.accesses_archetypes()
+    }
+
+    unsafe fn run_unsafe(&mut self, world: &World, resources: &UnsafeResources) {
+        let mut watch = Stopwatch::new();
+        watch.start();
+        self.system.run_unsafe(world, resources);
+        watch.stop();
+        self.slot.set(watch.elapsed());
+    }
+
+    fn command_buffer_mut(&mut self, world: WorldId) -> Option<&mut CommandBuffer> {
+        self.system.command_buffer_mut(world)
+    }
+}
+
+/// Measures a [`Dispatcher`](super::Dispatcher) and publishes the results in its entry of the
+/// [`DispatcherMetrics`] resource.
+#[derive(Debug)]
+pub(crate) struct MetricsCollector {
+    id: DispatcherId,
+    frame: Stopwatch,
+    step: Rc<RefCell<Stopwatch>>,
+    steps: Vec<MetricSlot>,
+    systems: Vec<MetricSlot>,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        MetricsCollector {
+            id: DispatcherId(NEXT_DISPATCHER_ID.fetch_add(1, Ordering::Relaxed)),
+            frame: Stopwatch::new(),
+            step: Rc::default(),
+            steps: Vec::new(),
+            systems: Vec::new(),
+        }
+    }
+}
+
+impl MetricsCollector {
+    /// The dispatcher measured.
+    pub(crate) fn id(&self) -> DispatcherId {
+        self.id
+    }
+
+    /// Creates the slot of the next system of the schedule.
+    pub(crate) fn system_slot(&mut self) -> MetricSlot {
+        let slot = MetricSlot::default();
+        self.systems.push(slot.clone());
+        slot
+    }
+
+    /// Follows every step with a thread local function measuring it. Thread local functions
+    /// don't split executors nor affect command buffer flushes, so the schedule is unchanged.
+    pub(crate) fn instrument_steps(&mut self, steps: Vec<Step>) -> Vec<Step> {
+        let mut instrumented = Vec::with_capacity(steps.len() * 2);
+        for step in steps {
+            let slot = MetricSlot::default();
+            self.steps.push(slot.clone());
+            let watch = Rc::clone(&self.step);
+
+            instrumented.push(step);
+            instrumented.push(Step::ThreadLocalFn(Box::new(
+                move |_: &mut World, _: &mut Resources| {
+                    let mut watch = watch.borrow_mut();
+                    slot.set(watch.elapsed());
+                    watch.restart();
+                },
+            )));
+        }
+        instrumented
+    }
+
+    /// Adds the metrics of the dispatcher to the [`DispatcherMetrics`] resource, inserting it if
+    /// needed.
+    pub(crate) fn register(&self, resources: &mut Resources, info: &ScheduleInfo) {
+        resources
+            .get_mut_or_default::<DispatcherMetrics>()
+            .dispatchers
+            .insert(self.id, ScheduleMetrics::new(info));
+    }
+
+    /// Removes the metrics of the dispatcher, and the [`DispatcherMetrics`] resource if they were
+    /// the last ones.
+    pub(crate) fn unregister(&self, resources: &mut Resources) {
+        let empty = resources
+            .get_mut::<DispatcherMetrics>()
+            .map_or(false, |mut metrics| {
+                metrics.dispatchers.remove(&self.id);
+                metrics.dispatchers.is_empty()
+            });
+        if empty {
+            resources.remove::<DispatcherMetrics>();
+        }
+    }
+
+    pub(crate) fn begin_frame(&mut self) {
+        self.frame.restart();
+        self.step.borrow_mut().restart();
+    }
+
+    pub(crate) fn end_frame(&mut self, resources: &mut Resources) {
+        self.frame.stop();
+        if let Some(mut metrics) = resources.get_mut::<DispatcherMetrics>() {
+            if let Some(metrics) = metrics.dispatchers.get_mut(&self.id) {
+                metrics.record(self.frame.elapsed(), &self.steps, &self.systems);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use legion::SystemBuilder;
+
+    use super::*;
+    use crate::dispatcher::DispatcherBuilder;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn timing_stats_average_over_window() {
+        let mut stats = TimingStats::default();
+        for sample in &[4, 2, 6, 8] {
+            stats.record(millis(*sample), 3);
+        }
+
+        assert_eq!(stats.last(), millis(8));
+        assert_eq!(stats.average(), millis(16) / 3);
+        assert_eq!(stats.worst(), millis(8));
+
+        stats.trim(1);
+        assert_eq!(stats.average(), millis(8));
+    }
+
+    #[test]
+    fn dispatcher_records_metrics() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        let mut dispatcher = DispatcherBuilder::default()
+            .add_system(|| {
+                SystemBuilder::new("SlowSystem")
+                    .build(|_, _, _, _| thread::sleep(Duration::from_millis(5)))
+            })
+            .add_thread_local_fn(|_, _| {})
+            .build(&mut world, &mut resources)
+            .unwrap();
+
+        for _ in 0..3 {
+            dispatcher.execute(&mut world, &mut resources);
+        }
+
+        let metrics = resources.get::<DispatcherMetrics>().unwrap();
+        let metrics = metrics.get(dispatcher.id()).unwrap();
+        assert_eq!(metrics.frames(), 3);
+        assert_eq!(metrics.steps().len(), 3);
+        assert_eq!(metrics.systems().len(), 2);
+
+        let slow = metrics.system("SlowSystem").unwrap();
+        assert_eq!(slow.step(), 0);
+        assert!(slow.timing().last() >= millis(5));
+        assert!(slow.timing().average() >= millis(5));
+        assert!(metrics.steps()[0].timing().worst() >= slow.timing().last());
+        assert!(metrics.frame().worst() >= metrics.steps()[0].timing().worst());
+    }
+
+    #[test]
+    fn metrics_are_kept_per_dispatcher() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        let mut first = DispatcherBuilder::default()
+            .add_system(|| SystemBuilder::new("First").build(|_, _, _, _| {}))
+            .build(&mut world, &mut resources)
+            .unwrap();
+        let mut second = DispatcherBuilder::default()
+            .add_system(|| SystemBuilder::new("Second").build(|_, _, _, _| {}))
+            .build(&mut world, &mut resources)
+            .unwrap();
+        assert_ne!(first.id(), second.id());
+
+        first.execute(&mut world, &mut resources);
+        second.execute(&mut world, &mut resources);
+        second.execute(&mut world, &mut resources);
+        {
+            let metrics = resources.get::<DispatcherMetrics>().unwrap();
+            let (first_metrics, second_metrics) = (
+                metrics.get(first.id()).unwrap(),
+                metrics.get(second.id()).unwrap(),
+            );
+            assert_eq!(first_metrics.frames(), 1);
+            assert!(first_metrics.system("Second").is_none());
+            assert_eq!(second_metrics.frames(), 2);
+            assert!(second_metrics.system("First").is_none());
+            assert_eq!(metrics.system("Second"), second_metrics.system("Second"));
+        }
+
+        let first_id = first.id();
+        first.unload(&mut world, &mut resources).unwrap();
+        {
+            let metrics = resources.get::<DispatcherMetrics>().unwrap();
+            assert!(metrics.get(first_id).is_none());
+            assert!(metrics.get(second.id()).is_some());
+        }
+        second.unload(&mut world, &mut resources).unwrap();
+        assert!(!resources.contains::<DispatcherMetrics>());
+    }
+}
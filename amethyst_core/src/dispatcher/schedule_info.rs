@@ -0,0 +1,397 @@
+//! Introspection of the schedule built by [`DispatcherBuilder`](super::DispatcherBuilder).
+
+use std::fmt::{self, Display, Write};
+
+use amethyst_error::Error;
+use serde::Serialize;
+
+use crate::ecs::Runnable;
+
+/// Description of every step of a [`Dispatcher`](super::Dispatcher), in execution order.
+///
+/// Retrieved with [`Dispatcher::schedule_info`](super::Dispatcher::schedule_info). The
+/// [`Display`] implementation gives a human readable dump, while [`ScheduleInfo::to_dot`] and
+/// [`ScheduleInfo::to_json`] export the schedule for visualization and diffing.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ScheduleInfo {
+    /// Steps in execution order.
+    pub steps: Vec<StepInfo>,
+}
+
+/// Description of a single step of the schedule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StepInfo {
+    /// Kind of the step.
+    pub kind: StepKind,
+    /// Systems executed by the step. Empty for command buffer flushes.
+    pub systems: Vec<SystemInfo>,
+}
+
+/// Kind of a schedule step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum StepKind {
+    /// Systems executed by a parallel executor.
+    Systems,
+    /// Flush of the command buffers of all previous systems.
+    FlushCmdBuffers,
+    /// A thread local system, executed on the main thread.
+    ThreadLocalSystem,
+    /// A thread local function, executed on the main thread.
+    ThreadLocalFn,
+}
+
+/// Description of a system of the schedule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SystemInfo {
+    /// Name of the system.
+    pub name: String,
+    /// Type name of the innermost [`SystemBundle`](super::SystemBundle) which added the system,
+    /// if any.
+    pub bundle: Option<String>,
+    /// Ordering labels carried by the system.
+    pub labels: Vec<String>,
+    /// Whether the system is gated by run conditions.
+    pub conditional: bool,
+    /// Resources only read by the system.
+    pub reads_resources: Vec<String>,
+    /// Resources written by the system.
+    pub writes_resources: Vec<String>,
+    /// Components only read by the system.
+    pub reads_components: Vec<String>,
+    /// Components written by the system.
+    pub writes_components: Vec<String>,
+}
+
+impl SystemInfo {
+    pub(crate) fn from_runnable<R: Runnable + ?Sized>(runnable: &R) -> Self {
+        let (read_resources, read_components) = runnable.reads();
+        let (write_resources, write_components) = runnable.writes();
+        let writes_resources = type_names(write_resources);
+        let writes_components = type_names(write_components);
+
+        SystemInfo {
+            name: runnable
+                .name()
+                .map_or_else(|| "unnamed system".to_string(), ToString::to_string),
+            reads_resources: type_names(read_resources)
+                .into_iter()
+                .filter(|name| !writes_resources.contains(name))
+                .collect(),
+            reads_components: type_names(read_components)
+                .into_iter()
+                .filter(|name| !writes_components.contains(name))
+                .collect(),
+            writes_resources,
+            writes_components,
+            ..SystemInfo::default()
+        }
+    }
+
+    /// Resources and components which prevent `self` and `other` from running in parallel,
+    /// because one of them writes data the other one accesses.
+    #[must_use]
+    pub fn conflicts(&self, other: &SystemInfo) -> Vec<&str> {
+        let mut conflicts = conflicting(
+            &self.writes_resources,
+            &self.reads_resources,
+            &other.writes_resources,
+            &other.reads_resources,
+        );
+        conflicts.extend(conflicting(
+            &self.writes_components,
+            &self.reads_components,
+            &other.writes_components,
+            &other.reads_components,
+        ));
+        conflicts
+    }
+}
+
+/// Data written by one system and accessed by the other, or read by one system and written by
+/// the other.
+fn conflicting<'a>(
+    writes: &'a [String],
+    reads: &'a [String],
+    other_writes: &[String],
+    other_reads: &[String],
+) -> Vec<&'a str> {
+    let written = writes
+        .iter()
+        .filter(|name| other_writes.contains(name) || other_reads.contains(name));
+    let read = reads.iter().filter(|name| other_writes.contains(name));
+    written.chain(read).map(String::as_str).collect()
+}
+
+fn type_names<T: Display>(ids: &[T]) -> Vec<String> {
+    let mut names = Vec::with_capacity(ids.len());
+    for name in ids.iter().map(ToString::to_string) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+impl ScheduleInfo {
+    /// Exports the schedule as a Graphviz DOT graph.
+    ///
+    /// Every step is drawn as a cluster, connected to the next step. Systems of the same step
+    /// which access the same data are connected by dashed edges labelled with that data: legion
+    /// can't run them in parallel, so they execute one after the other.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write_dot(&mut dot);
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String) -> fmt::Result {
+        writeln!(dot, "digraph schedule {{")?;
+        writeln!(dot, "    compound=true;")?;
+        writeln!(dot, "    node [shape=box];")?;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(dot, "    subgraph cluster_{} {{", index)?;
+            writeln!(dot, "        label=\"{}: {}\";", index, step.kind)?;
+            if step.systems.is_empty() {
+                writeln!(
+                    dot,
+                    "        step_{} [label=\"{}\", shape=point];",
+                    index, step.kind
+                )?;
+            }
+            for (system_index, system) in step.systems.iter().enumerate() {
+                let mut label = escape(&system.name);
+                if let Some(bundle) = &system.bundle {
+                    label.push_str("\\n(");
+                    label.push_str(&escape(bundle));
+                    label.push(')');
+                }
+                let style = if system.conditional {
+                    ", style=rounded"
+                } else {
+                    ""
+                };
+                writeln!(
+                    dot,
+                    "        step_{}_{} [label=\"{}\"{}];",
+                    index, system_index, label, style
+                )?;
+            }
+            writeln!(dot, "    }}")?;
+
+            for (first, system) in step.systems.iter().enumerate() {
+                for (second, other) in step.systems.iter().enumerate().skip(first + 1) {
+                    let conflicts = system.conflicts(other);
+                    if !conflicts.is_empty() {
+                        writeln!(
+                            dot,
+                            "    step_{}_{} -> step_{}_{} [label=\"{}\", style=dashed];",
+                            index,
+                            first,
+                            index,
+                            second,
+                            escape(&conflicts.join("\\n"))
+                        )?;
+                    }
+                }
+            }
+        }
+
+        for index in 1..self.steps.len() {
+            writeln!(
+                dot,
+                "    {} -> {} [ltail=cluster_{}, lhead=cluster_{}];",
+                self.anchor(index - 1),
+                self.anchor(index),
+                index - 1,
+                index
+            )?;
+        }
+
+        writeln!(dot, "}}")
+    }
+
+    /// Node through which edges between steps are drawn.
+    fn anchor(&self, index: usize) -> String {
+        if self.steps[index].systems.is_empty() {
+            format!("step_{}", index)
+        } else {
+            format!("step_{}_0", index)
+        }
+    }
+
+    /// Exports the schedule as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Escapes a string for use in a double quoted DOT label. Escapes written as `\n` are kept.
+fn escape(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
+
+impl Display for StepKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepKind::Systems => write!(f, "systems"),
+            StepKind::FlushCmdBuffers => write!(f, "flush command buffers"),
+            StepKind::ThreadLocalSystem => write!(f, "thread local system"),
+            StepKind::ThreadLocalFn => write!(f, "thread local function"),
+        }
+    }
+}
+
+impl Display for ScheduleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "Step {}: {}", index, step.kind)?;
+            for system in &step.systems {
+                write!(f, "    {}", system.name)?;
+                if let Some(bundle) = &system.bundle {
+                    write!(f, " ({})", bundle)?;
+                }
+                if system.conditional {
+                    write!(f, " [conditional]")?;
+                }
+                writeln!(f)?;
+                for (title, names) in &[
+                    ("labels", &system.labels),
+                    ("reads resources", &system.reads_resources),
+                    ("writes resources", &system.writes_resources),
+                    ("reads components", &system.reads_components),
+                    ("writes components", &system.writes_components),
+                ] {
+                    if !names.is_empty() {
+                        writeln!(f, "        {}: {}", title, names.join(", "))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use legion::SystemBuilder;
+
+    use super::*;
+    use crate::{
+        dispatcher::{DispatcherBuilder, SystemBundle, SystemOrder},
+        ecs::{Resources, World},
+        run_condition::resource_exists,
+        transform::Transform,
+    };
+
+    struct Score(u32);
+    struct Paused;
+
+    struct ScoreBundle;
+
+    impl SystemBundle for ScoreBundle {
+        fn load(
+            &mut self,
+            _world: &mut World,
+            resources: &mut Resources,
+            builder: &mut DispatcherBuilder,
+        ) -> Result<(), Error> {
+            resources.insert(Score(0));
+            builder
+                .add_system_ordered(
+                    || {
+                        SystemBuilder::new("ScoreSystem")
+                            .write_resource::<Score>()
+                            .read_component::<Transform>()
+                            .build(|_, _, _, _| {})
+                    },
+                    SystemOrder::new().label("score"),
+                )
+                .add_system(|| {
+                    SystemBuilder::new("ScoreDisplaySystem")
+                        .read_resource::<Score>()
+                        .build(|_, _, _, _| {})
+                });
+            Ok(())
+        }
+    }
+
+    fn schedule_info() -> ScheduleInfo {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        DispatcherBuilder::default()
+            .add_bundle(ScoreBundle)
+            .add_thread_local_fn_if(|_, _| {}, resource_exists::<Paused>())
+            .build(&mut world, &mut resources)
+            .unwrap()
+            .schedule_info()
+            .clone()
+    }
+
+    #[test]
+    fn schedule_info_lists_steps() {
+        let info = schedule_info();
+
+        let kinds = info.steps.iter().map(|step| step.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                StepKind::Systems,
+                StepKind::ThreadLocalFn,
+                StepKind::FlushCmdBuffers
+            ]
+        );
+
+        let score = &info.steps[0].systems[0];
+        assert_eq!(score.name, "ScoreSystem");
+        assert_eq!(
+            score.bundle.as_deref(),
+            Some(std::any::type_name::<ScoreBundle>())
+        );
+        assert_eq!(score.labels, vec!["score"]);
+        assert!(!score.conditional);
+        assert_eq!(score.writes_resources.len(), 1);
+        assert!(score.reads_resources.is_empty());
+        assert_eq!(score.reads_components.len(), 1);
+
+        let display = &info.steps[0].systems[1];
+        assert_eq!(display.reads_resources, score.writes_resources);
+        assert_eq!(
+            score.conflicts(display),
+            vec![score.writes_resources[0].as_str()]
+        );
+
+        let thread_local = &info.steps[1].systems[0];
+        assert!(thread_local.conditional);
+        assert_eq!(thread_local.bundle, None);
+    }
+
+    #[test]
+    fn schedule_info_exports_dot() {
+        let dot = schedule_info().to_dot();
+
+        assert!(dot.starts_with("digraph schedule {"));
+        assert!(dot.contains("step_0_0 -> step_0_1"));
+        assert!(dot.contains("step_0_0 -> step_1_0 [ltail=cluster_0, lhead=cluster_1];"));
+        assert!(dot.contains("step_1_0 -> step_2 [ltail=cluster_1, lhead=cluster_2];"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn schedule_info_exports_json() {
+        let info = schedule_info();
+        let json: serde_json::Value = serde_json::from_str(&info.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["steps"].as_array().unwrap().len(), 3);
+        assert_eq!(json["steps"][0]["kind"], "Systems");
+        assert_eq!(json["steps"][0]["systems"][0]["name"], "ScoreSystem");
+        assert_eq!(json["steps"][1]["systems"][0]["conditional"], true);
+    }
+}
@@ -0,0 +1,409 @@
+//! Indexes of entities by name, kept up to date as components are added and removed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    marker::PhantomData,
+};
+
+use amethyst_error::Error;
+use crossbeam_channel::Receiver;
+use smallvec::SmallVec;
+
+use crate::{
+    ecs::{
+        component, maybe_changed, storage::Component, world::Event, DispatcherBuilder, Entity,
+        EntityStore, IntoQuery, ParallelRunnable, Resources, System, SystemBuilder, SystemBundle,
+        World,
+    },
+    transform::Children,
+    Named,
+};
+
+/// Entities which got the component `C`, lost it, or were removed, as reported by the `World`.
+///
+/// Changes of the value of the component are not reported, they can be found with a
+/// `maybe_changed::<C>()` query filter. Entities are also reported when a component is added to or
+/// removed from an entity having `C`, as the entity moves to another archetype.
+pub struct ComponentEvents<C> {
+    events: Receiver<Event>,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C: Component> ComponentEvents<C> {
+    /// Subscribes to the changes of the entities having `C` in `world`.
+    pub fn subscribe(world: &mut World) -> Self {
+        let (sender, events) = crossbeam_channel::unbounded();
+        world.subscribe(sender, component::<C>());
+        ComponentEvents {
+            events,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Entities reported since the last call, possibly several times.
+    pub fn drain(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.events.try_iter().filter_map(|event| match event {
+            Event::EntityInserted(entity, _) | Event::EntityRemoved(entity, _) => Some(entity),
+            Event::ArchetypeCreated(_) => None,
+        })
+    }
+}
+
+impl<C> fmt::Debug for ComponentEvents<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentEvents")
+            .field("pending", &self.events.len())
+            .finish()
+    }
+}
+
+/// Component giving the name an entity is indexed by in a [`NameIndex`].
+pub trait EntityName: Component {
+    /// The name of the entity.
+    fn entity_name(&self) -> &str;
+}
+
+impl EntityName for Named {
+    fn entity_name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Resource mapping names to the entities carrying them, by their `N` component.
+///
+/// The index is maintained by the [`NameIndexSystem`] added by the [`NameIndexBundle`], which
+/// only looks at the entities whose name was added, changed or removed. Several entities can
+/// share a name, in which case lookups return the one indexed first.
+///
+/// Paths such as `"player/weapon/muzzle"` name an entity followed by the names of its descendants
+/// through their [`Children`], which makes names only need to be unique among siblings.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_core::{
+///     ecs::{DispatcherBuilder, Resources, World},
+///     transform::{Parent, Transform, TransformBundle},
+///     NameIndex, NameIndexBundle, Named,
+/// };
+///
+/// let mut world = World::default();
+/// let mut resources = Resources::default();
+/// let mut dispatcher = DispatcherBuilder::default()
+///     .add_bundle(TransformBundle)
+///     .add_bundle(NameIndexBundle::<Named>::default())
+///     .build(&mut world, &mut resources)
+///     .unwrap();
+///
+/// let player = world.push((Named::new("player"), Transform::default()));
+/// let weapon = world.push((Named::new("weapon"), Transform::default(), Parent(player)));
+/// dispatcher.execute(&mut world, &mut resources);
+///
+/// let index = resources.get::<NameIndex>().unwrap();
+/// assert_eq!(index.find("player"), Some(player));
+/// assert_eq!(index.find_path(&world, "player/weapon"), Some(weapon));
+/// ```
+pub struct NameIndex<N = Named> {
+    entities: HashMap<String, SmallVec<[Entity; 1]>>,
+    names: HashMap<Entity, String>,
+    _marker: PhantomData<fn() -> N>,
+}
+
+impl<N> NameIndex<N> {
+    /// The first indexed entity named `name`.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<Entity> {
+        self.find_all(name).first().copied()
+    }
+
+    /// All entities named `name`, in the order they were indexed.
+    #[must_use]
+    pub fn find_all(&self, name: &str) -> &[Entity] {
+        self.entities
+            .get(name)
+            .map_or(&[][..], |entities| entities.as_slice())
+    }
+
+    /// The name of `entity`, if it is indexed.
+    #[must_use]
+    pub fn name(&self, entity: Entity) -> Option<&str> {
+        self.names.get(&entity).map(String::as_str)
+    }
+
+    /// Resolves a path of names separated by `/`, the first name naming any entity and the
+    /// following ones naming a child of the previous entity.
+    ///
+    /// `world` needs read access to the `Children` of the entities.
+    pub fn find_path<W: EntityStore>(&self, world: &W, path: &str) -> Option<Entity> {
+        let mut names = path.split('/');
+        let first = names.next()?;
+        let names: Vec<&str> = names.collect();
+        self.find_all(first)
+            .iter()
+            .find_map(|entity| self.descend(world, *entity, &names))
+    }
+
+    /// Resolves a path of names separated by `/` relative to `root`, the first name naming a
+    /// child of `root`. An empty path resolves to `root`.
+    ///
+    /// `world` needs read access to the `Children` of the entities.
+    pub fn find_path_from<W: EntityStore>(
+        &self,
+        world: &W,
+        root: Entity,
+        path: &str,
+    ) -> Option<Entity> {
+        if path.is_empty() {
+            return Some(root);
+        }
+        let names: Vec<&str> = path.split('/').collect();
+        self.descend(world, root, &names)
+    }
+
+    /// The number of indexed entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns true if no entity is indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Follows `names` from `entity`, trying every child with the expected name.
+    fn descend<W: EntityStore>(&self, world: &W, entity: Entity, names: &[&str]) -> Option<Entity> {
+        let (name, names) = match names.split_first() {
+            Some(split) => split,
+            None => return Some(entity),
+        };
+        let children = world
+            .entry_ref(entity)
+            .ok()?
+            .get_component::<Children>()
+            .ok()?
+            .0
+            .clone();
+        children
+            .into_iter()
+            .filter(|child| self.name(*child) == Some(*name))
+            .find_map(|child| self.descend(world, child, names))
+    }
+
+    fn insert(&mut self, entity: Entity, name: &str) {
+        if self.name(entity) == Some(name) {
+            return;
+        }
+        self.remove(entity);
+        self.entities
+            .entry(name.to_owned())
+            .or_default()
+            .push(entity);
+        self.names.insert(entity, name.to_owned());
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(name) = self.names.remove(&entity) {
+            if let Some(entities) = self.entities.get_mut(&name) {
+                entities.retain(|e| *e != entity);
+                if entities.is_empty() {
+                    self.entities.remove(&name);
+                }
+            }
+        }
+    }
+}
+
+impl<N> Default for NameIndex<N> {
+    fn default() -> Self {
+        NameIndex {
+            entities: HashMap::new(),
+            names: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N> fmt::Debug for NameIndex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NameIndex")
+            .field("names", &self.names)
+            .finish()
+    }
+}
+
+/// Updates the [`NameIndex<N>`](NameIndex) with the entities whose `N` was added, changed or
+/// removed.
+#[derive(Debug)]
+pub struct NameIndexSystem<N> {
+    events: ComponentEvents<N>,
+}
+
+impl<N: EntityName> NameIndexSystem<N> {
+    /// Creates the system, indexing the entities reported by `events`.
+    #[must_use]
+    pub fn new(events: ComponentEvents<N>) -> Self {
+        NameIndexSystem { events }
+    }
+}
+
+impl<N: EntityName> System for NameIndexSystem<N> {
+    fn build(self) -> Box<dyn ParallelRunnable> {
+        let events = self.events;
+        Box::new(
+            SystemBuilder::new(format!("NameIndexSystem: {}", std::any::type_name::<N>()))
+                .write_resource::<NameIndex<N>>()
+                .read_component::<N>()
+                // Entities whose name may have changed in place
+                .with_query(<Entity>::query().filter(maybe_changed::<N>()))
+                .build(move |_, world, index, query| {
+                    // Entities are indexed in the order they were reported.
+                    let mut seen = HashSet::new();
+                    let changed: Vec<Entity> = events
+                        .drain()
+                        .chain(query.iter(world).copied())
+                        .filter(|entity| seen.insert(*entity))
+                        .collect();
+                    for entity in changed {
+                        let name = world.entry_ref(entity).ok().and_then(|entry| {
+                            entry
+                                .get_component::<N>()
+                                .ok()
+                                .map(|name| name.entity_name().to_owned())
+                        });
+                        match name {
+                            Some(name) => index.insert(entity, &name),
+                            None => index.remove(entity),
+                        }
+                    }
+                }),
+        )
+    }
+}
+
+/// Adds the [`NameIndex<N>`](NameIndex) resource and the [`NameIndexSystem<N>`] maintaining it.
+pub struct NameIndexBundle<N = Named> {
+    _marker: PhantomData<fn() -> N>,
+}
+
+impl<N> Default for NameIndexBundle<N> {
+    fn default() -> Self {
+        NameIndexBundle {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N> fmt::Debug for NameIndexBundle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NameIndexBundle").finish()
+    }
+}
+
+impl<N: EntityName> SystemBundle for NameIndexBundle<N> {
+    fn load(
+        &mut self,
+        world: &mut World,
+        resources: &mut Resources,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), Error> {
+        resources.insert(NameIndex::<N>::default());
+        builder.add_system(NameIndexSystem::new(ComponentEvents::<N>::subscribe(world)));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ecs::Dispatcher,
+        transform::{Parent, Transform, TransformBundle},
+    };
+
+    fn index_world() -> (Resources, World, Dispatcher) {
+        let mut resources = Resources::default();
+        let mut world = World::default();
+        let dispatcher = DispatcherBuilder::default()
+            .add_bundle(TransformBundle)
+            .add_bundle(NameIndexBundle::<Named>::default())
+            .build(&mut world, &mut resources)
+            .unwrap();
+        (resources, world, dispatcher)
+    }
+
+    fn named(name: &'static str) -> (Named, Transform) {
+        (Named::new(name), Transform::default())
+    }
+
+    #[test]
+    fn follows_added_changed_and_removed_names() {
+        let (mut res, mut world, mut dispatcher) = index_world();
+        let e1 = world.push(named("e1"));
+        let e2 = world.push(named("e2"));
+        let e3 = world.push((Transform::default(),));
+        dispatcher.execute(&mut world, &mut res);
+        {
+            let index = res.get::<NameIndex>().unwrap();
+            assert_eq!(index.find("e1"), Some(e1));
+            assert_eq!(index.find("e2"), Some(e2));
+            assert_eq!(index.len(), 2);
+        }
+
+        world
+            .entry(e1)
+            .unwrap()
+            .get_component_mut::<Named>()
+            .unwrap()
+            .0 = "renamed".into();
+        world.entry(e2).unwrap().remove_component::<Named>();
+        world.entry(e3).unwrap().add_component(Named::new("e3"));
+        dispatcher.execute(&mut world, &mut res);
+        {
+            let index = res.get::<NameIndex>().unwrap();
+            assert_eq!(index.find("e1"), None);
+            assert_eq!(index.find("renamed"), Some(e1));
+            assert_eq!(index.find("e2"), None);
+            assert_eq!(index.name(e3), Some("e3"));
+            assert_eq!(index.len(), 2);
+        }
+
+        world.remove(e3);
+        // Moving a named entity to another archetype keeps its name.
+        world.entry(e1).unwrap().add_component(3_u32);
+        dispatcher.execute(&mut world, &mut res);
+        let index = res.get::<NameIndex>().unwrap();
+        assert_eq!(index.find("e3"), None);
+        assert_eq!(index.find("renamed"), Some(e1));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn resolves_paths_through_children() {
+        let (mut res, mut world, mut dispatcher) = index_world();
+        // Two players, only the second one holds a weapon with a muzzle.
+        let player1 = world.push(named("player"));
+        world.push((Named::new("weapon"), Transform::default(), Parent(player1)));
+        let player2 = world.push(named("player"));
+        let weapon = world.push((Named::new("weapon"), Transform::default(), Parent(player2)));
+        let muzzle = world.push((Named::new("muzzle"), Transform::default(), Parent(weapon)));
+        dispatcher.execute(&mut world, &mut res);
+
+        let index = res.get::<NameIndex>().unwrap();
+        assert_eq!(index.find_all("player"), &[player1, player2]);
+        assert_eq!(
+            index.find_path(&world, "player/weapon/muzzle"),
+            Some(muzzle)
+        );
+        assert_eq!(index.find_path(&world, "player/muzzle"), None);
+        assert_eq!(index.find_path(&world, "weapon/muzzle"), Some(muzzle));
+        assert_eq!(
+            index.find_path_from(&world, player2, "weapon/muzzle"),
+            Some(muzzle)
+        );
+        assert_eq!(index.find_path_from(&world, player1, "weapon/muzzle"), None);
+        assert_eq!(index.find_path_from(&world, weapon, ""), Some(weapon));
+    }
+}
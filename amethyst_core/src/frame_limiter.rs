@@ -131,6 +131,7 @@ impl Default for FrameRateLimitStrategy {
 /// [`FrameLimiter`]: ./struct.FrameLimiter.html
 /// [`Config`]: ../../amethyst_config/trait.Config.html
 #[derive(Debug, Clone, Deserialize, Serialize, new)]
+#[serde(default)]
 pub struct FrameRateLimitConfig {
     /// Frame rate limiting strategy.
     pub strategy: FrameRateLimitStrategy,
@@ -0,0 +1,298 @@
+//! Axis aligned bounding box.
+
+use serde::{Deserialize, Serialize};
+
+use super::{closest_outside_segment, Capsule, Intersects, Obb, Ray, Sphere, Support, Triangle};
+use crate::math::{self as na, Matrix4, Point3, Vector3};
+
+/// Axis aligned bounding box, the box between the corners `min` and `max`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aabb {
+    /// The corner with the smallest coordinates
+    pub min: Point3<f32>,
+    /// The corner with the largest coordinates
+    pub max: Point3<f32>,
+}
+
+impl Aabb {
+    /// Create a new `Aabb` from its corners. No coordinate of `min` should be larger than the
+    /// same coordinate of `max`.
+    #[must_use]
+    pub fn new(min: Point3<f32>, max: Point3<f32>) -> Self {
+        Aabb { min, max }
+    }
+
+    /// Create a new `Aabb` from its center and its half size along each axis.
+    #[must_use]
+    pub fn from_center_half_extents(center: Point3<f32>, half_extents: Vector3<f32>) -> Self {
+        Aabb {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// The smallest `Aabb` containing all the `points`, or `None` if there are no points.
+    #[must_use]
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point3<f32>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(Aabb::new(first, first), |aabb, point| {
+            Aabb::new(
+                Point3::from(aabb.min.coords.inf(&point.coords)),
+                Point3::from(aabb.max.coords.sup(&point.coords)),
+            )
+        }))
+    }
+
+    /// The center of the box.
+    #[must_use]
+    pub fn center(&self) -> Point3<f32> {
+        na::center(&self.min, &self.max)
+    }
+
+    /// The half size of the box along each axis.
+    #[must_use]
+    pub fn half_extents(&self) -> Vector3<f32> {
+        (self.max - self.min) / 2.0
+    }
+
+    /// The 8 corners of the box.
+    #[must_use]
+    pub fn corners(&self) -> [Point3<f32>; 8] {
+        let (min, max) = (&self.min, &self.max);
+        [
+            Point3::new(min.x, min.y, min.z),
+            Point3::new(max.x, min.y, min.z),
+            Point3::new(min.x, max.y, min.z),
+            Point3::new(max.x, max.y, min.z),
+            Point3::new(min.x, min.y, max.z),
+            Point3::new(max.x, min.y, max.z),
+            Point3::new(min.x, max.y, max.z),
+            Point3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    /// The smallest `Aabb` containing this one and `other`.
+    #[must_use]
+    pub fn merged(&self, other: &Aabb) -> Self {
+        Aabb::new(
+            Point3::from(self.min.coords.inf(&other.min.coords)),
+            Point3::from(self.max.coords.sup(&other.max.coords)),
+        )
+    }
+
+    /// The smallest `Aabb` containing this box transformed by `matrix`, such as the
+    /// `GlobalTransform` of an entity.
+    #[must_use]
+    pub fn transformed(&self, matrix: &Matrix4<f32>) -> Self {
+        let center = matrix.transform_point(&self.center());
+        let linear = matrix.fixed_slice::<na::U3, na::U3>(0, 0).abs();
+        Aabb::from_center_half_extents(center, linear * self.half_extents())
+    }
+
+    /// Returns `true` if `point` is inside of the box.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        less_or_equal(&self.min, point) && less_or_equal(point, &self.max)
+    }
+
+    /// Returns `true` if `other` is inside of the box.
+    #[must_use]
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Returns `true` if `sphere` is inside of the box.
+    #[must_use]
+    pub fn contains_sphere(&self, sphere: &Sphere) -> bool {
+        let radius = Vector3::from_element(sphere.radius);
+        self.contains_aabb(&Aabb::from_center_half_extents(sphere.center, radius))
+    }
+
+    /// The point of the box closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        Point3::from(point.coords.sup(&self.min.coords).inf(&self.max.coords))
+    }
+
+    /// The closest points of the segment from `start` to `end` and of the box, the point of the
+    /// segment first. Both are the same point if the segment enters the box.
+    #[must_use]
+    pub fn closest_to_segment(
+        &self,
+        start: &Point3<f32>,
+        end: &Point3<f32>,
+    ) -> (Point3<f32>, Point3<f32>) {
+        let ray = Ray {
+            origin: *start,
+            direction: end - start,
+        };
+        match self.intersect_ray(&ray) {
+            Some(t) if t <= 1.0 => {
+                let entry = ray.at_distance(t);
+                (entry, entry)
+            }
+            _ => {
+                closest_outside_segment(start, end, |point| self.closest_point(point), self.edges())
+            }
+        }
+    }
+
+    /// Returns the distance along `ray` to the box, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray<f32>) -> Option<f32> {
+        let mut near = 0.0_f32;
+        let mut far = f32::INFINITY;
+        let slabs = self.min.coords.iter().zip(self.max.coords.iter());
+        let components = ray.origin.coords.iter().zip(ray.direction.iter());
+        for ((min, max), (origin, direction)) in slabs.zip(components) {
+            if direction.abs() <= f32::EPSILON {
+                // Parallel to the slab, the ray never enters it if it isn't already inside.
+                if origin < min || origin > max {
+                    return None;
+                }
+            } else {
+                let t1 = (min - origin) / direction;
+                let t2 = (max - origin) / direction;
+                near = near.max(t1.min(t2));
+                far = far.min(t1.max(t2));
+                if near > far {
+                    return None;
+                }
+            }
+        }
+        Some(near)
+    }
+
+    /// The 12 edges of the box, as pairs of corners differing along a single axis.
+    fn edges(&self) -> impl Iterator<Item = (Point3<f32>, Point3<f32>)> {
+        let corners = self.corners();
+        (0..3).flat_map(move |bit| {
+            let axis = 1 << bit;
+            (0..8)
+                .filter(move |corner| corner & axis == 0)
+                .map(move |corner| (corners[corner], corners[corner | axis]))
+        })
+    }
+}
+
+/// Returns `true` if no coordinate of `a` is larger than the same coordinate of `b`.
+fn less_or_equal(a: &Point3<f32>, b: &Point3<f32>) -> bool {
+    a.coords.iter().zip(b.coords.iter()).all(|(a, b)| a <= b)
+}
+
+impl Support for Aabb {
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32> {
+        Point3::from(
+            direction.zip_zip_map(&self.min.coords, &self.max.coords, |d, min, max| {
+                if d < 0.0 {
+                    min
+                } else {
+                    max
+                }
+            }),
+        )
+    }
+}
+
+impl Intersects for Aabb {
+    fn intersects(&self, other: &Aabb) -> bool {
+        less_or_equal(&self.min, &other.max) && less_or_equal(&other.min, &self.max)
+    }
+}
+
+impl Intersects<Obb> for Aabb {
+    fn intersects(&self, other: &Obb) -> bool {
+        Obb::from(*self).intersects(other)
+    }
+}
+
+impl Intersects<Capsule> for Aabb {
+    fn intersects(&self, other: &Capsule) -> bool {
+        let (on_segment, on_box) = self.closest_to_segment(&other.start, &other.end);
+        (on_box - on_segment).norm() <= other.radius
+    }
+}
+
+impl Intersects<Triangle> for Aabb {
+    fn intersects(&self, other: &Triangle) -> bool {
+        Obb::from(*self).intersects(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_relative_eq;
+
+    #[test]
+    fn ray_enters_through_the_nearest_face() {
+        let aabb = Aabb::new(Point3::new(1.0, -1.0, -1.0), Point3::new(3.0, 1.0, 1.0));
+        let ray = Ray {
+            origin: Point3::new(-1.0, 0.5, 0.0),
+            direction: Vector3::new(1.0, 0.0, 0.0),
+        };
+        assert_relative_eq!(aabb.intersect_ray(&ray).unwrap(), 2.0);
+
+        let inside = Ray {
+            origin: Point3::new(2.0, 0.0, 0.0),
+            ..ray
+        };
+        assert_relative_eq!(aabb.intersect_ray(&inside).unwrap(), 0.0);
+
+        let parallel = Ray {
+            origin: Point3::new(-1.0, 2.0, 0.0),
+            ..ray
+        };
+        assert_eq!(aabb.intersect_ray(&parallel), None);
+
+        let behind = Ray {
+            direction: Vector3::new(-1.0, 0.0, 0.0),
+            ..ray
+        };
+        assert_eq!(aabb.intersect_ray(&behind), None);
+    }
+
+    #[test]
+    fn segment_closest_to_an_edge() {
+        let aabb = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+        let (on_segment, on_box) =
+            aabb.closest_to_segment(&Point3::new(3.0, 0.0, 0.5), &Point3::new(0.0, 3.0, 0.5));
+        assert_relative_eq!(on_segment, Point3::new(1.5, 1.5, 0.5));
+        assert_relative_eq!(on_box, Point3::new(1.0, 1.0, 0.5));
+
+        let capsule = Capsule::new(
+            Point3::new(3.0, 0.0, 0.5),
+            Point3::new(0.0, 3.0, 0.5),
+            0.707,
+        );
+        assert!(!aabb.intersects(&capsule));
+        assert!(aabb.intersects(&Capsule {
+            radius: 0.708,
+            ..capsule
+        }));
+
+        let (on_segment, on_box) =
+            aabb.closest_to_segment(&Point3::new(-3.0, 0.0, 0.0), &Point3::new(3.0, 0.0, 0.0));
+        assert_relative_eq!(on_segment, Point3::new(-1.0, 0.0, 0.0));
+        assert_relative_eq!(on_box, on_segment);
+    }
+
+    #[test]
+    fn transformed_contains_transformed_corners() {
+        let aabb = Aabb::new(Point3::new(-1.0, -2.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+        let matrix = Matrix4::new_translation(&Vector3::new(5.0, 0.0, -1.0))
+            * Matrix4::from_euler_angles(0.3, -0.7, 1.1)
+            * Matrix4::new_nonuniform_scaling(&Vector3::new(2.0, 0.5, 1.0));
+        let transformed = aabb.transformed(&matrix);
+
+        let corners: Vec<_> = aabb
+            .corners()
+            .iter()
+            .map(|corner| matrix.transform_point(corner))
+            .collect();
+        let bounds = Aabb::from_points(&corners).unwrap();
+        assert_relative_eq!(transformed.min, bounds.min, epsilon = 1.0e-5);
+        assert_relative_eq!(transformed.max, bounds.max, epsilon = 1.0e-5);
+    }
+}
@@ -0,0 +1,176 @@
+//! Capsule.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    closest_between_segments, closest_on_segment, Intersects, Ray, Sphere, Support, Triangle,
+};
+use crate::math::{self as na, Point3, Vector3};
+
+/// Capsule, the points at most `radius` away from the segment from `start` to `end`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capsule {
+    /// The start of the segment at the core of the capsule
+    pub start: Point3<f32>,
+    /// The end of the segment at the core of the capsule
+    pub end: Point3<f32>,
+    /// The radius of the capsule around its segment
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Create a new `Capsule` from the ends of its segment and its radius.
+    #[must_use]
+    pub fn new(start: Point3<f32>, end: Point3<f32>, radius: f32) -> Self {
+        Capsule { start, end, radius }
+    }
+
+    /// The center of the capsule, halfway between the ends of its segment.
+    #[must_use]
+    pub fn center(&self) -> Point3<f32> {
+        na::center(&self.start, &self.end)
+    }
+
+    /// Returns `true` if `point` is inside of the capsule.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        self.core_sphere(point).contains_point(point)
+    }
+
+    /// Returns `true` if `sphere` is inside of the capsule.
+    #[must_use]
+    pub fn contains_sphere(&self, sphere: &Sphere) -> bool {
+        self.core_sphere(&sphere.center).contains_sphere(sphere)
+    }
+
+    /// The point of the capsule closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.core_sphere(point).closest_point(point)
+    }
+
+    /// Returns the distance along `ray` to the capsule, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray<f32>) -> Option<f32> {
+        if self.contains_point(&ray.origin) {
+            return Some(0.0);
+        }
+        [self.start, self.end]
+            .iter()
+            .filter_map(|end| Sphere::new(*end, self.radius).intersect_ray(ray))
+            .chain(self.intersect_side(ray))
+            .fold(None, |nearest: Option<f32>, t| {
+                Some(nearest.map_or(t, |nearest| nearest.min(t)))
+            })
+    }
+
+    /// The sphere of the capsule centered on the point of its segment closest to `point`.
+    fn core_sphere(&self, point: &Point3<f32>) -> Sphere {
+        Sphere::new(
+            closest_on_segment(&self.start, &self.end, point),
+            self.radius,
+        )
+    }
+
+    /// Returns the distance along `ray`, which starts outside, to the cylinder between the ends
+    /// of the capsule.
+    fn intersect_side(&self, ray: &Ray<f32>) -> Option<f32> {
+        let axis = self.end - self.start;
+        let length_squared = axis.norm_squared();
+        if length_squared <= f32::EPSILON {
+            return None;
+        }
+        let offset = ray.origin - self.start;
+        let across = |vector: &Vector3<f32>| vector - axis * (vector.dot(&axis) / length_squared);
+        let (offset_across, direction_across) = (across(&offset), across(&ray.direction));
+
+        let a = direction_across.norm_squared();
+        let b = offset_across.dot(&direction_across);
+        let c = offset_across.norm_squared() - self.radius * self.radius;
+        let discriminant = b * b - a * c;
+        if a <= f32::EPSILON || discriminant < 0.0 {
+            return None;
+        }
+        let t = (-b - discriminant.sqrt()) / a;
+        let along = (offset + ray.direction * t).dot(&axis) / length_squared;
+        if t >= 0.0 && (0.0..=1.0).contains(&along) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
+impl Support for Capsule {
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32> {
+        let end = if (self.end - self.start).dot(direction) > 0.0 {
+            self.end
+        } else {
+            self.start
+        };
+        Sphere::new(end, self.radius).support(direction)
+    }
+}
+
+impl Intersects for Capsule {
+    fn intersects(&self, other: &Capsule) -> bool {
+        let (closest1, closest2) =
+            closest_between_segments(&self.start, &self.end, &other.start, &other.end);
+        Sphere::new(closest1, self.radius).intersects(&Sphere::new(closest2, other.radius))
+    }
+}
+
+impl Intersects<Triangle> for Capsule {
+    fn intersects(&self, other: &Triangle) -> bool {
+        let (on_segment, on_triangle) = other.closest_to_segment(&self.start, &self.end);
+        (on_triangle - on_segment).norm() <= self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_relative_eq;
+
+    fn capsule() -> Capsule {
+        Capsule::new(Point3::new(0.0, -2.0, 0.0), Point3::new(0.0, 2.0, 0.0), 1.0)
+    }
+
+    #[test]
+    fn ray_hits_the_side_and_the_ends() {
+        let capsule = capsule();
+        let side = Ray {
+            origin: Point3::new(-5.0, 1.0, 0.0),
+            direction: Vector3::new(1.0, 0.0, 0.0),
+        };
+        assert_relative_eq!(capsule.intersect_ray(&side).unwrap(), 4.0);
+
+        let top = Ray {
+            origin: Point3::new(0.0, 10.0, 0.0),
+            direction: Vector3::new(0.0, -1.0, 0.0),
+        };
+        assert_relative_eq!(capsule.intersect_ray(&top).unwrap(), 7.0);
+
+        let miss = Ray {
+            origin: Point3::new(-5.0, 3.5, 0.0),
+            ..side
+        };
+        assert_eq!(capsule.intersect_ray(&miss), None);
+    }
+
+    #[test]
+    fn crossing_capsules() {
+        let capsule = capsule();
+        let crossing = Capsule::new(Point3::new(-2.0, 0.0, 1.9), Point3::new(2.0, 0.0, 1.9), 1.0);
+        assert!(capsule.intersects(&crossing));
+
+        let apart = Capsule {
+            start: Point3::new(-2.0, 0.0, 2.1),
+            end: Point3::new(2.0, 0.0, 2.1),
+            ..crossing
+        };
+        assert!(!capsule.intersects(&apart));
+        assert!(capsule.contains_point(&Point3::new(0.0, 2.9, 0.0)));
+        assert!(!capsule.contains_point(&Point3::new(0.9, 2.9, 0.0)));
+    }
+}
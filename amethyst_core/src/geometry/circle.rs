@@ -0,0 +1,107 @@
+//! 2D circle.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Intersects, Ray2D, Rect};
+use crate::math::{self as na, Point2};
+
+/// 2D circle, the points at most `radius` away from `center`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Circle {
+    /// The center of the circle
+    pub center: Point2<f32>,
+    /// The radius of the circle
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Create a new `Circle` from its center and radius.
+    #[must_use]
+    pub fn new(center: Point2<f32>, radius: f32) -> Self {
+        Circle { center, radius }
+    }
+
+    /// Returns `true` if `point` is inside of the circle.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point2<f32>) -> bool {
+        na::distance_squared(&self.center, point) <= self.radius * self.radius
+    }
+
+    /// Returns `true` if `other` is inside of the circle.
+    #[must_use]
+    pub fn contains_circle(&self, other: &Circle) -> bool {
+        na::distance(&self.center, &other.center) + other.radius <= self.radius
+    }
+
+    /// Returns `true` if `rect` is inside of the circle.
+    #[must_use]
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        // The corner of the rectangle the farthest from the center.
+        let offset = (rect.min - self.center)
+            .abs()
+            .sup(&(rect.max - self.center).abs());
+        offset.norm_squared() <= self.radius * self.radius
+    }
+
+    /// The point of the circle closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point2<f32>) -> Point2<f32> {
+        let offset = point - self.center;
+        let distance = offset.norm();
+        if distance <= self.radius {
+            *point
+        } else {
+            self.center + offset * (self.radius / distance)
+        }
+    }
+
+    /// Returns the distance along `ray` to the circle, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray2D) -> Option<f32> {
+        let offset = ray.origin - self.center;
+        let c = offset.norm_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+        let a = ray.direction.norm_squared();
+        let b = offset.dot(&ray.direction);
+        let discriminant = b * b - a * c;
+        if b >= 0.0 || discriminant < 0.0 || a <= f32::EPSILON {
+            // Going away from the circle, or missing it.
+            return None;
+        }
+        Some((-b - discriminant.sqrt()) / a)
+    }
+}
+
+impl Intersects for Circle {
+    fn intersects(&self, other: &Circle) -> bool {
+        let radius = self.radius + other.radius;
+        na::distance_squared(&self.center, &other.center) <= radius * radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx::assert_relative_eq, math::Vector2};
+
+    #[test]
+    fn circle_queries() {
+        let circle = Circle::new(Point2::new(0.0, 0.0), 2.0);
+        assert!(circle.contains_circle(&Circle::new(Point2::new(1.0, 0.0), 1.0)));
+        assert!(!circle.contains_rect(&Rect::new(Point2::new(-1.5, -1.5), Point2::new(1.5, 1.5))));
+        assert_relative_eq!(
+            circle.closest_point(&Point2::new(0.0, -4.0)),
+            Point2::new(0.0, -2.0)
+        );
+
+        let ray = Ray2D {
+            origin: Point2::new(-5.0, 0.0),
+            direction: Vector2::new(1.0, 0.0),
+        };
+        assert_relative_eq!(circle.intersect_ray(&ray).unwrap(), 3.0);
+        assert!(circle.intersects(&Circle::new(Point2::new(3.0, 0.0), 1.0)));
+        assert!(!circle.intersects(&Circle::new(Point2::new(3.1, 0.0), 1.0)));
+    }
+}
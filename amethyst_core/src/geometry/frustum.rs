@@ -0,0 +1,142 @@
+//! View frustum.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Aabb, Capsule, Intersects, Obb, Plane, Sphere, Support, Triangle};
+use crate::math::{Matrix4, Point3, Vector4};
+
+/// View frustum, the space between 6 planes facing its inside, such as the space seen by a
+/// camera.
+///
+/// The intersection tests against a frustum are conservative: a primitive is only rejected when
+/// it is entirely behind one of the planes, so a few primitives close to the edges of the frustum
+/// intersect it without having any point inside of it. This is what culling needs, since it
+/// never hides a visible primitive.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frustum {
+    /// The planes of the frustum, with their normal facing the inside, in the order of the clip
+    /// space faces `x = -1`, `x = 1`, `y = -1`, `y = 1`, `z = -1` and `z = 1`
+    pub planes: [Plane<f32>; 6],
+}
+
+impl Frustum {
+    /// Create the frustum of a view projection matrix, such as the projection matrix of a camera
+    /// multiplied by its view matrix. Its planes are the faces of the clip space cube
+    /// `[-1, 1]^3`.
+    #[must_use]
+    pub fn new(matrix: Matrix4<f32>) -> Self {
+        let row = |index| matrix.row(index).transpose();
+        let plane =
+            |coefficients: Vector4<f32>| Plane::new(coefficients.xyz(), coefficients.w).normalize();
+        Frustum {
+            planes: [
+                plane(row(3) + row(0)),
+                plane(row(3) - row(0)),
+                plane(row(3) + row(1)),
+                plane(row(3) - row(1)),
+                plane(row(3) + row(2)),
+                plane(row(3) - row(2)),
+            ],
+        }
+    }
+
+    /// Returns `true` if `point` is inside of the frustum.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.dot_point(point) >= 0.0)
+    }
+
+    /// Returns `true` if `sphere` is inside of the frustum.
+    #[must_use]
+    pub fn contains_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.dot_point(&sphere.center) >= sphere.radius)
+    }
+
+    /// Returns `true` if `aabb` is inside of the frustum.
+    #[must_use]
+    pub fn contains_aabb(&self, aabb: &Aabb) -> bool {
+        aabb.corners()
+            .iter()
+            .all(|corner| self.contains_point(corner))
+    }
+
+    /// Returns `true` if `primitive` is entirely behind one of the planes.
+    fn excludes(&self, primitive: &impl Support) -> bool {
+        self.planes
+            .iter()
+            .any(|plane| plane.dot_point(&primitive.support(plane.normal())) < 0.0)
+    }
+}
+
+impl Intersects<Sphere> for Frustum {
+    fn intersects(&self, other: &Sphere) -> bool {
+        !self.excludes(other)
+    }
+}
+
+impl Intersects<Aabb> for Frustum {
+    fn intersects(&self, other: &Aabb) -> bool {
+        !self.excludes(other)
+    }
+}
+
+impl Intersects<Obb> for Frustum {
+    fn intersects(&self, other: &Obb) -> bool {
+        !self.excludes(other)
+    }
+}
+
+impl Intersects<Capsule> for Frustum {
+    fn intersects(&self, other: &Capsule) -> bool {
+        !self.excludes(other)
+    }
+}
+
+impl Intersects<Triangle> for Frustum {
+    fn intersects(&self, other: &Triangle) -> bool {
+        !self.excludes(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frustum() -> Frustum {
+        // Looking down -Z with a 90° field of view, between 1 and 10 units away.
+        Frustum::new(Matrix4::new_perspective(
+            1.0,
+            std::f32::consts::FRAC_PI_2,
+            1.0,
+            10.0,
+        ))
+    }
+
+    #[test]
+    fn points_inside_the_view() {
+        let frustum = frustum();
+        assert!(frustum.contains_point(&Point3::new(0.0, 0.0, -5.0)));
+        assert!(frustum.contains_point(&Point3::new(4.9, -4.9, -5.0)));
+        assert!(!frustum.contains_point(&Point3::new(0.0, 0.0, 5.0)));
+        assert!(!frustum.contains_point(&Point3::new(0.0, 0.0, -0.5)));
+        assert!(!frustum.contains_point(&Point3::new(0.0, 0.0, -20.0)));
+        assert!(!frustum.contains_point(&Point3::new(6.0, 0.0, -5.0)));
+    }
+
+    #[test]
+    fn spheres_crossing_a_side() {
+        let frustum = frustum();
+        let sphere = Sphere::new(Point3::new(6.0, 0.0, -5.0), 1.0);
+        assert!(frustum.intersects(&sphere));
+        assert!(!frustum.contains_sphere(&sphere));
+        assert!(!frustum.intersects(&Sphere {
+            radius: 0.5,
+            ..sphere
+        }));
+        assert!(frustum.contains_sphere(&Sphere::new(Point3::new(0.0, 0.0, -5.0), 1.0)));
+    }
+}
@@ -0,0 +1,386 @@
+//!
+//! Geometry helper functionality.
+//!
+//! Besides the generic [`Plane`] and [`Ray`], this module provides `f32` primitives shared by
+//! picking, culling and collision code: [`Aabb`], [`Obb`], [`Sphere`], [`Capsule`], [`Triangle`]
+//! and [`Frustum`] in 3D, [`Rect`] and [`Circle`] in 2D.
+//!
+//! Every pair of primitives of the same dimension, except two frustums, can be tested for overlap
+//! with [`Intersects`]. The primitives also provide containment tests, the closest point to a
+//! given point, and `intersect_ray`, which returns the distance along the ray to the first point
+//! of the primitive, or `0.0` when the ray starts inside of it.
+use nalgebra::{one, zero, Point2, Point3, RealField, Vector2, Vector3};
+
+pub use self::{
+    aabb::Aabb, capsule::Capsule, circle::Circle, frustum::Frustum, obb::Obb, rect::Rect,
+    sphere::Sphere, triangle::Triangle,
+};
+
+mod aabb;
+mod capsule;
+mod circle;
+mod frustum;
+mod obb;
+mod rect;
+mod sphere;
+mod triangle;
+
+#[cfg(test)]
+mod properties;
+
+/// Overlap test between two primitives. Primitives which only touch each other intersect.
+///
+/// # Examples
+///
+/// ```
+/// # use amethyst::core::geometry::{Aabb, Intersects, Sphere};
+/// # use amethyst::core::math::{Point3, Vector3};
+/// let aabb = Aabb::from_center_half_extents(Point3::origin(), Vector3::new(1.0, 1.0, 1.0));
+/// let sphere = Sphere::new(Point3::new(2.0, 0.0, 0.0), 1.5);
+///
+/// assert!(aabb.intersects(&sphere));
+/// assert!(sphere.intersects(&aabb));
+/// ```
+pub trait Intersects<Rhs: ?Sized = Self> {
+    /// Returns `true` if `self` and `other` have at least one point in common.
+    fn intersects(&self, other: &Rhs) -> bool;
+}
+
+/// Implements `Intersects<A> for B` from the implementation of `Intersects<B> for A`.
+macro_rules! symmetric_intersects {
+    ($($a:ty => $b:ty),* $(,)?) => {
+        $(
+            impl Intersects<$a> for $b {
+                #[inline]
+                fn intersects(&self, other: &$a) -> bool {
+                    other.intersects(self)
+                }
+            }
+        )*
+    };
+}
+
+symmetric_intersects!(
+    Sphere => Aabb,
+    Sphere => Obb,
+    Sphere => Capsule,
+    Sphere => Triangle,
+    Aabb => Obb,
+    Aabb => Capsule,
+    Aabb => Triangle,
+    Obb => Capsule,
+    Obb => Triangle,
+    Capsule => Triangle,
+    Frustum => Sphere,
+    Frustum => Aabb,
+    Frustum => Obb,
+    Frustum => Capsule,
+    Frustum => Triangle,
+    Rect => Circle,
+);
+
+/// The point of a convex primitive the farthest along a direction.
+pub(crate) trait Support {
+    /// Returns a point of the primitive maximizing its dot product with `direction`.
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32>;
+}
+
+/// A plane which can be intersected by a ray.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Plane<T>
+where
+    T: RealField,
+{
+    /// The plane described as x,y,z normal
+    normal: Vector3<T>,
+    /// dot product of the point and normal, representing the plane position
+    bias: T,
+}
+impl<T> Plane<T>
+where
+    T: RealField,
+{
+    /// Create a new `Plane`.
+    pub fn new(normal: Vector3<T>, bias: T) -> Self {
+        Plane { normal, bias }
+    }
+
+    /// Create a new `Plane` from a point normal representation
+    pub fn from_point_normal(point: &Point3<T>, normal: &Vector3<T>) -> Self {
+        let normalized = normal.normalize();
+        Self {
+            normal: Vector3::new(normalized.x, normalized.y, normalized.z),
+            bias: point.coords.dot(&normalized),
+        }
+    }
+
+    /// Create a new `Plane` from a point normal representation
+    pub fn from_point_vectors(point: &Point3<T>, v1: &Vector3<T>, v2: &Vector3<T>) -> Self {
+        Self::from_point_normal(point, &v1.cross(v2))
+    }
+
+    /// Create a `Plane` which is facing along the X-Axis at the provided coordinate.
+    pub fn with_x(x: T) -> Self {
+        Self::from_point_normal(
+            &Point3::new(x, zero(), zero()),
+            &Vector3::new(one(), zero(), zero()),
+        )
+    }
+
+    /// Create a `Plane` which is facing along the Y-Axis at the provided coordinate.
+    pub fn with_y(y: T) -> Self {
+        Self::from_point_normal(
+            &Point3::new(zero(), y, zero()),
+            &Vector3::new(zero(), one(), zero()),
+        )
+    }
+
+    /// Create a `Plane` which is facing along the Z-Axis at the provided coordinate.
+    pub fn with_z(z: T) -> Self {
+        Self::from_point_normal(
+            &Point3::new(zero(), zero(), z),
+            &Vector3::new(zero(), zero(), one()),
+        )
+    }
+
+    /// This `Plane` normal
+    pub fn normal(&self) -> &Vector3<T> {
+        &self.normal
+    }
+
+    /// Normalized representation of this `Plane`
+    pub fn normalize(&self) -> Self {
+        let distance = self.normal.magnitude();
+        Self {
+            normal: self.normal / distance,
+            bias: self.bias / distance,
+        }
+    }
+
+    /// Returns the dot product of this `Plane` and a provided `Point3`
+    pub fn dot_point(&self, point: &Point3<T>) -> T {
+        self.normal.x * point.x + self.normal.y * point.y + self.normal.z * point.z + self.bias
+    }
+
+    /// Returns the dot product of this `Plane` and a provided `Vector3`
+    pub fn dot(&self, point: &Vector3<T>) -> T {
+        self.normal.x * point.x + self.normal.y * point.y + self.normal.z * point.z
+    }
+
+    /// Returns the dot product of this `Plane` with another `Plane`
+    pub fn dot_plane(&self, plane: &Plane<T>) -> T {
+        self.normal.x * plane.normal.x
+            + self.normal.y * plane.normal.y
+            + self.normal.z * plane.normal.z
+            + self.bias * plane.bias
+    }
+
+    /// Returns the intersection distance of the provided line given a point and direction, or `None` if none occurs.
+    pub fn intersect_line(&self, point: &Point3<T>, direction: &Vector3<T>) -> Option<T> {
+        let fv = self.dot(direction);
+        if fv.abs().is_positive() {
+            let distance = -self.dot_point(point) / fv;
+            Some(distance)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the intersection distance of the provided `Ray`, or `None` if none occurs.
+    pub fn intersect_ray(&self, ray: &Ray<T>) -> Option<T> {
+        self.intersect_line(&ray.origin, &ray.direction)
+    }
+}
+
+/// A Ray represents and infinite half-line starting at `origin` and going in specified unit length `direction`.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Ray<T>
+where
+    T: RealField,
+{
+    /// The origin point of the ray
+    pub origin: Point3<T>,
+    /// The normalized direction vector of the ray
+    pub direction: Vector3<T>,
+}
+impl<T> Ray<T>
+where
+    T: RealField,
+{
+    /// Returns the distance along the ray which intersects with the provided `Plane`1
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<T> {
+        plane.intersect_ray(self)
+    }
+
+    /// Returns a `Point` along the ray at a distance `t` from it's origin.
+    pub fn at_distance(&self, z: T) -> Point3<T> {
+        self.origin + (self.direction * z)
+    }
+}
+
+/// A 2D ray, the half-line starting at `origin` and going in the unit length `direction`.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Ray2D {
+    /// The origin point of the ray
+    pub origin: Point2<f32>,
+    /// The normalized direction vector of the ray
+    pub direction: Vector2<f32>,
+}
+
+impl Ray2D {
+    /// Returns a `Point` along the ray at a distance `t` from it's origin.
+    #[must_use]
+    pub fn at_distance(&self, t: f32) -> Point2<f32> {
+        self.origin + self.direction * t
+    }
+}
+
+/// The point of the segment from `start` to `end` closest to `point`.
+fn closest_on_segment(start: &Point3<f32>, end: &Point3<f32>, point: &Point3<f32>) -> Point3<f32> {
+    let segment = end - start;
+    let length_squared = segment.norm_squared();
+    if length_squared <= f32::EPSILON {
+        return *start;
+    }
+    let t = (point - start).dot(&segment) / length_squared;
+    start + segment * t.max(0.0).min(1.0)
+}
+
+/// The closest points of the segments from `start1` to `end1` and from `start2` to `end2`.
+#[allow(clippy::many_single_char_names, clippy::similar_names)]
+fn closest_between_segments(
+    start1: &Point3<f32>,
+    end1: &Point3<f32>,
+    start2: &Point3<f32>,
+    end2: &Point3<f32>,
+) -> (Point3<f32>, Point3<f32>) {
+    let clamp = |value: f32| value.max(0.0).min(1.0);
+    let d1 = end1 - start1;
+    let d2 = end2 - start2;
+    let r = start1 - start2;
+    let a = d1.norm_squared();
+    let e = d2.norm_squared();
+    let f = d2.dot(&r);
+    let (s, t) = if a <= f32::EPSILON && e <= f32::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f32::EPSILON {
+        (0.0, clamp(f / e))
+    } else {
+        let c = d1.dot(&r);
+        if e <= f32::EPSILON {
+            (clamp(-c / a), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            let denominator = a * e - b * b;
+            // Any point of the first segment will do for parallel segments.
+            let s = if denominator > f32::EPSILON * a * e {
+                clamp((b * f - c * e) / denominator)
+            } else {
+                0.0
+            };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                (clamp(-c / a), 0.0)
+            } else if t > 1.0 {
+                (clamp((b - c) / a), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+    (start1 + d1 * s, start2 + d2 * t)
+}
+
+/// The closest points of the segment from `start` to `end` and of a convex polyhedron the segment
+/// doesn't cross, given the closest point of the polyhedron to any point and its `edges`, the
+/// point of the segment first.
+///
+/// The closest point of the segment is then one of its ends, or its closest point to an edge: a
+/// point inside of the segment closest to a point inside of a face means that the segment is
+/// parallel to the face, and stays as close to it up to one of its ends or one of the edges.
+fn closest_outside_segment(
+    start: &Point3<f32>,
+    end: &Point3<f32>,
+    closest_point: impl Fn(&Point3<f32>) -> Point3<f32>,
+    edges: impl IntoIterator<Item = (Point3<f32>, Point3<f32>)>,
+) -> (Point3<f32>, Point3<f32>) {
+    let from_edges = edges
+        .into_iter()
+        .map(|(edge_start, edge_end)| closest_between_segments(start, end, &edge_start, &edge_end));
+    std::iter::once((*end, closest_point(end)))
+        .chain(from_edges)
+        .fold((*start, closest_point(start)), |closest, pair| {
+            if (pair.1 - pair.0).norm_squared() < (closest.1 - closest.0).norm_squared() {
+                pair
+            } else {
+                closest
+            }
+        })
+}
+
+/// Separating axis test between the convex hulls of `points1` and `points2`, which overlap if
+/// their projections overlap on every one of the `axes` covering their face normals and edge
+/// cross products. Degenerate axes are skipped.
+fn overlap_on_axes(
+    points1: &[Point3<f32>],
+    points2: &[Point3<f32>],
+    axes: impl IntoIterator<Item = Vector3<f32>>,
+) -> bool {
+    let project = |points: &[Point3<f32>], axis: &Vector3<f32>| {
+        points
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+                let projection = point.coords.dot(axis);
+                (min.min(projection), max.max(projection))
+            })
+    };
+    axes.into_iter()
+        .filter_map(|axis| axis.try_normalize(1.0e-6))
+        .all(|axis| {
+            let (min1, max1) = project(points1, &axis);
+            let (min2, max2) = project(points2, &axis);
+            min1 <= max2 && min2 <= max1
+        })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use approx::{assert_relative_eq, assert_ulps_eq};
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::mistyped_literal_suffixes)]
+    fn ray_intersect_plane() {
+        let plane = Plane::<f32>::with_z(0.0);
+
+        let ray = Ray {
+            origin: Point3::new(0.020_277_506, -0.033_236_53, 51.794),
+            direction: Vector3::new(0.179_559_51, -0.294_313_04, -0.938_689_65),
+        };
+        let distance = ray.intersect_plane(&plane).unwrap();
+        assert!(distance > 0.0);
+        let point = ray.at_distance(distance);
+        assert_ulps_eq!(point, Point3::new(9.927_818, -16.272_524, 0.0));
+
+        let ray = Ray {
+            origin: Point3::new(-0.003_106_177, 0.034_074_64, 0.799_999_95),
+            direction: Vector3::new(-0.029_389_05, 0.322_396_73, -0.946_148_3),
+        };
+        let distance = ray.intersect_plane(&plane).unwrap();
+        let point = ray.at_distance(distance);
+        assert_ulps_eq!(point, Point3::new(-0.027_955_6, 0.306_671_83, 0.0));
+    }
+
+    #[test]
+    fn at_distance() {
+        assert_relative_eq!(
+            Ray {
+                origin: Point3::new(0.0, 0.0, 50.0),
+                direction: Vector3::new(0.2, -0.3, -0.9),
+            }
+            .at_distance(5.0),
+            Point3::new(1., -1.5, 45.5)
+        )
+    }
+}
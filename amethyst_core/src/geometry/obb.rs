@@ -0,0 +1,224 @@
+//! Oriented bounding box.
+
+use serde::{Deserialize, Serialize};
+
+use super::{overlap_on_axes, Aabb, Capsule, Intersects, Ray, Support, Triangle};
+use crate::math::{Isometry3, Point3, UnitQuaternion, Vector3};
+
+/// Oriented bounding box, a box of size `2 * half_extents` rotated by `rotation` around its
+/// `center`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Obb {
+    /// The center of the box
+    pub center: Point3<f32>,
+    /// The half size of the box along each of its axes
+    pub half_extents: Vector3<f32>,
+    /// The rotation of the box axes
+    pub rotation: UnitQuaternion<f32>,
+}
+
+impl Obb {
+    /// Create a new `Obb` from its center, half size along each of its axes and rotation.
+    #[must_use]
+    pub fn new(
+        center: Point3<f32>,
+        half_extents: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) -> Self {
+        Obb {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// The box `aabb` moved by `isometry`.
+    #[must_use]
+    pub fn from_aabb(aabb: &Aabb, isometry: &Isometry3<f32>) -> Self {
+        Obb {
+            center: isometry * aabb.center(),
+            half_extents: aabb.half_extents(),
+            rotation: isometry.rotation,
+        }
+    }
+
+    /// The unit length axes of the box.
+    #[must_use]
+    pub fn axes(&self) -> [Vector3<f32>; 3] {
+        [
+            self.rotation * Vector3::x(),
+            self.rotation * Vector3::y(),
+            self.rotation * Vector3::z(),
+        ]
+    }
+
+    /// The 8 corners of the box.
+    #[must_use]
+    pub fn corners(&self) -> [Point3<f32>; 8] {
+        let mut corners = self.local_aabb().corners();
+        for corner in &mut corners {
+            *corner = self.to_world(corner);
+        }
+        corners
+    }
+
+    /// Returns `true` if `point` is inside of the box.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        self.local_aabb().contains_point(&self.to_local(point))
+    }
+
+    /// The point of the box closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        let local = self.local_aabb().closest_point(&self.to_local(point));
+        self.to_world(&local)
+    }
+
+    /// The closest points of the segment from `start` to `end` and of the box, the point of the
+    /// segment first. Both are the same point if the segment enters the box.
+    #[must_use]
+    pub fn closest_to_segment(
+        &self,
+        start: &Point3<f32>,
+        end: &Point3<f32>,
+    ) -> (Point3<f32>, Point3<f32>) {
+        let (on_segment, on_box) = self
+            .local_aabb()
+            .closest_to_segment(&self.to_local(start), &self.to_local(end));
+        (self.to_world(&on_segment), self.to_world(&on_box))
+    }
+
+    /// Returns the distance along `ray` to the box, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray<f32>) -> Option<f32> {
+        let local = Ray {
+            origin: self.to_local(&ray.origin),
+            direction: self.rotation.inverse_transform_vector(&ray.direction),
+        };
+        self.local_aabb().intersect_ray(&local)
+    }
+
+    /// The box in its own space, centered on the origin.
+    fn local_aabb(&self) -> Aabb {
+        Aabb::from_center_half_extents(Point3::origin(), self.half_extents)
+    }
+
+    fn to_local(&self, point: &Point3<f32>) -> Point3<f32> {
+        Point3::from(
+            self.rotation
+                .inverse_transform_vector(&(point - self.center)),
+        )
+    }
+
+    fn to_world(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.center + self.rotation * point.coords
+    }
+}
+
+/// The box `aabb`, without rotation.
+impl From<Aabb> for Obb {
+    fn from(aabb: Aabb) -> Self {
+        Obb::new(
+            aabb.center(),
+            aabb.half_extents(),
+            UnitQuaternion::identity(),
+        )
+    }
+}
+
+impl Support for Obb {
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32> {
+        let local = self
+            .local_aabb()
+            .support(&self.rotation.inverse_transform_vector(direction));
+        self.to_world(&local)
+    }
+}
+
+impl Intersects for Obb {
+    fn intersects(&self, other: &Obb) -> bool {
+        let (axes1, axes2) = (self.axes(), other.axes());
+        let crosses = axes1
+            .iter()
+            .flat_map(|axis1| axes2.iter().map(move |axis2| axis1.cross(axis2)));
+        let axes = axes1.iter().chain(axes2.iter()).copied().chain(crosses);
+        overlap_on_axes(&self.corners(), &other.corners(), axes)
+    }
+}
+
+impl Intersects<Capsule> for Obb {
+    fn intersects(&self, other: &Capsule) -> bool {
+        let (on_segment, on_box) = self.closest_to_segment(&other.start, &other.end);
+        (on_box - on_segment).norm() <= other.radius
+    }
+}
+
+impl Intersects<Triangle> for Obb {
+    fn intersects(&self, other: &Triangle) -> bool {
+        let axes = self.axes();
+        let edges = other.edges();
+        let crosses = axes
+            .iter()
+            .flat_map(|axis| edges.iter().map(move |edge| axis.cross(edge)));
+        let normal = other.normal();
+        let axes = axes.iter().copied().chain(Some(normal)).chain(crosses);
+        overlap_on_axes(&self.corners(), &other.vertices(), axes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+    use crate::approx::assert_relative_eq;
+
+    fn diamond() -> Obb {
+        Obb::new(
+            Point3::origin(),
+            Vector3::new(1.0, 1.0, 1.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, FRAC_PI_4),
+        )
+    }
+
+    #[test]
+    fn rotated_box_queries() {
+        let obb = diamond();
+        let corner = std::f32::consts::SQRT_2;
+
+        assert!(obb.contains_point(&Point3::new(corner - 0.01, 0.0, 0.0)));
+        assert!(!obb.contains_point(&Point3::new(1.0, 1.0, 0.0)));
+        assert_relative_eq!(
+            obb.closest_point(&Point3::new(3.0, 0.0, 0.0)),
+            Point3::new(corner, 0.0, 0.0),
+            epsilon = 1.0e-5
+        );
+
+        let ray = Ray {
+            origin: Point3::new(-5.0, 0.0, 0.0),
+            direction: Vector3::new(1.0, 0.0, 0.0),
+        };
+        assert_relative_eq!(
+            obb.intersect_ray(&ray).unwrap(),
+            5.0 - corner,
+            epsilon = 1.0e-5
+        );
+    }
+
+    #[test]
+    fn corner_against_face() {
+        let obb = diamond();
+        let other = Obb::new(
+            Point3::new(2.5, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            UnitQuaternion::identity(),
+        );
+        assert!(!obb.intersects(&other));
+        let other = Obb {
+            center: Point3::new(2.3, 0.0, 0.0),
+            ..other
+        };
+        assert!(obb.intersects(&other));
+    }
+}
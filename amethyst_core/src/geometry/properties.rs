@@ -0,0 +1,501 @@
+//! Property tests of the queries of every primitive and of the intersection tests between every
+//! pair of primitives, over primitives drawn from a seeded random generator.
+
+use std::{f32::consts::PI, fmt::Debug};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::math::{self as na, Isometry3, Matrix4, UnitQuaternion};
+
+/// Number of random primitives checked by each property.
+const CASES: usize = 200;
+
+/// Error allowed on distances.
+const TOLERANCE: f32 = 1.0e-3;
+
+/// A 3D primitive which can be drawn at random and moved around.
+trait Primitive: Support + Copy + Debug {
+    fn random(rng: &mut StdRng) -> Self;
+    fn center(&self) -> Point3<f32>;
+    fn translated(&self, offset: &Vector3<f32>) -> Self;
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32>;
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32>;
+}
+
+/// A 2D primitive which can be drawn at random and moved around.
+trait Primitive2D: Copy + Debug {
+    fn random(rng: &mut StdRng) -> Self;
+    fn center(&self) -> Point2<f32>;
+    fn translated(&self, offset: &Vector2<f32>) -> Self;
+    fn support(&self, direction: &Vector2<f32>) -> Point2<f32>;
+    fn contains(&self, point: &Point2<f32>) -> bool;
+    fn closest(&self, point: &Point2<f32>) -> Point2<f32>;
+    fn cast(&self, ray: &Ray2D) -> Option<f32>;
+}
+
+fn random_point(rng: &mut StdRng) -> Point3<f32> {
+    Point3::from(random_vector(rng, 5.0))
+}
+
+fn random_vector(rng: &mut StdRng, max: f32) -> Vector3<f32> {
+    Vector3::new(
+        rng.gen_range(-max..max),
+        rng.gen_range(-max..max),
+        rng.gen_range(-max..max),
+    )
+}
+
+fn random_half_extents(rng: &mut StdRng) -> Vector3<f32> {
+    Vector3::new(
+        rng.gen_range(0.1..3.0),
+        rng.gen_range(0.1..3.0),
+        rng.gen_range(0.1..3.0),
+    )
+}
+
+fn random_direction(rng: &mut StdRng) -> Vector3<f32> {
+    loop {
+        if let Some(direction) = random_vector(rng, 1.0).try_normalize(0.1) {
+            return direction;
+        }
+    }
+}
+
+fn random_direction_2d(rng: &mut StdRng) -> Vector2<f32> {
+    loop {
+        let vector = Vector2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+        if let Some(direction) = vector.try_normalize(0.1) {
+            return direction;
+        }
+    }
+}
+
+/// A random point inside of `primitive`, between its center and its boundary.
+fn sample<A: Primitive>(primitive: &A, rng: &mut StdRng) -> Point3<f32> {
+    let center = primitive.center();
+    let boundary = primitive.support(&random_direction(rng));
+    center + (boundary - center) * rng.gen_range(0.0..0.9)
+}
+
+fn sample_2d<A: Primitive2D>(primitive: &A, rng: &mut StdRng) -> Point2<f32> {
+    let center = primitive.center();
+    let boundary = primitive.support(&random_direction_2d(rng));
+    center + (boundary - center) * rng.gen_range(0.0..0.9)
+}
+
+impl Primitive for Sphere {
+    fn random(rng: &mut StdRng) -> Self {
+        Sphere::new(random_point(rng), rng.gen_range(0.1..3.0))
+    }
+
+    fn center(&self) -> Point3<f32> {
+        self.center
+    }
+
+    fn translated(&self, offset: &Vector3<f32>) -> Self {
+        Sphere::new(self.center + offset, self.radius)
+    }
+
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive for Aabb {
+    fn random(rng: &mut StdRng) -> Self {
+        Aabb::from_center_half_extents(random_point(rng), random_half_extents(rng))
+    }
+
+    fn center(&self) -> Point3<f32> {
+        Aabb::center(self)
+    }
+
+    fn translated(&self, offset: &Vector3<f32>) -> Self {
+        Aabb::new(self.min + offset, self.max + offset)
+    }
+
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive for Obb {
+    fn random(rng: &mut StdRng) -> Self {
+        let rotation = UnitQuaternion::from_euler_angles(
+            rng.gen_range(-PI..PI),
+            rng.gen_range(-PI..PI),
+            rng.gen_range(-PI..PI),
+        );
+        Obb::new(random_point(rng), random_half_extents(rng), rotation)
+    }
+
+    fn center(&self) -> Point3<f32> {
+        self.center
+    }
+
+    fn translated(&self, offset: &Vector3<f32>) -> Self {
+        Obb::new(self.center + offset, self.half_extents, self.rotation)
+    }
+
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive for Capsule {
+    fn random(rng: &mut StdRng) -> Self {
+        let start = random_point(rng);
+        let end = start + random_vector(rng, 3.0);
+        Capsule::new(start, end, rng.gen_range(0.1..2.0))
+    }
+
+    fn center(&self) -> Point3<f32> {
+        Capsule::center(self)
+    }
+
+    fn translated(&self, offset: &Vector3<f32>) -> Self {
+        Capsule::new(self.start + offset, self.end + offset, self.radius)
+    }
+
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive for Triangle {
+    fn random(rng: &mut StdRng) -> Self {
+        let center = random_point(rng);
+        Triangle::new(
+            center + random_vector(rng, 3.0),
+            center + random_vector(rng, 3.0),
+            center + random_vector(rng, 3.0),
+        )
+    }
+
+    fn center(&self) -> Point3<f32> {
+        self.centroid()
+    }
+
+    fn translated(&self, offset: &Vector3<f32>) -> Self {
+        Triangle::new(self.a + offset, self.b + offset, self.c + offset)
+    }
+
+    fn closest(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray<f32>) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive2D for Rect {
+    fn random(rng: &mut StdRng) -> Self {
+        let center = Point2::new(rng.gen_range(-5.0..5.0), rng.gen_range(-5.0..5.0));
+        let half_extents = Vector2::new(rng.gen_range(0.1..3.0), rng.gen_range(0.1..3.0));
+        Rect::from_center_half_extents(center, half_extents)
+    }
+
+    fn center(&self) -> Point2<f32> {
+        Rect::center(self)
+    }
+
+    fn translated(&self, offset: &Vector2<f32>) -> Self {
+        Rect::new(self.min + offset, self.max + offset)
+    }
+
+    fn support(&self, direction: &Vector2<f32>) -> Point2<f32> {
+        Point2::new(
+            if direction.x < 0.0 {
+                self.min.x
+            } else {
+                self.max.x
+            },
+            if direction.y < 0.0 {
+                self.min.y
+            } else {
+                self.max.y
+            },
+        )
+    }
+
+    fn contains(&self, point: &Point2<f32>) -> bool {
+        self.contains_point(point)
+    }
+
+    fn closest(&self, point: &Point2<f32>) -> Point2<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray2D) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+impl Primitive2D for Circle {
+    fn random(rng: &mut StdRng) -> Self {
+        let center = Point2::new(rng.gen_range(-5.0..5.0), rng.gen_range(-5.0..5.0));
+        Circle::new(center, rng.gen_range(0.1..3.0))
+    }
+
+    fn center(&self) -> Point2<f32> {
+        self.center
+    }
+
+    fn translated(&self, offset: &Vector2<f32>) -> Self {
+        Circle::new(self.center + offset, self.radius)
+    }
+
+    fn support(&self, direction: &Vector2<f32>) -> Point2<f32> {
+        self.center + direction.normalize() * self.radius
+    }
+
+    fn contains(&self, point: &Point2<f32>) -> bool {
+        self.contains_point(point)
+    }
+
+    fn closest(&self, point: &Point2<f32>) -> Point2<f32> {
+        self.closest_point(point)
+    }
+
+    fn cast(&self, ray: &Ray2D) -> Option<f32> {
+        self.intersect_ray(ray)
+    }
+}
+
+/// The closest point is no farther than any point inside, and a ray cast from outside towards
+/// a point inside hits the boundary before reaching it, while a ray cast away misses.
+fn check_queries<A: Primitive>(rng: &mut StdRng) {
+    for _ in 0..CASES {
+        let primitive = A::random(rng);
+        let inside = sample(&primitive, rng);
+
+        let point = Point3::from(random_vector(rng, 15.0));
+        let closest = primitive.closest(&point);
+        assert!(
+            na::distance(&point, &closest) <= na::distance(&point, &inside) + TOLERANCE,
+            "{:?} closest to {} is {}, but {} is closer",
+            primitive,
+            point,
+            closest,
+            inside,
+        );
+
+        let origin = primitive.center() + random_direction(rng) * 20.0;
+        let ray = Ray {
+            origin,
+            direction: (inside - origin).normalize(),
+        };
+        let distance = primitive
+            .cast(&ray)
+            .unwrap_or_else(|| panic!("{:?} missed by {:?}", ray, primitive));
+        assert!(distance <= na::distance(&origin, &inside) + TOLERANCE);
+        let hit = ray.at_distance(distance);
+        assert!(na::distance(&hit, &primitive.closest(&hit)) <= TOLERANCE);
+
+        let away = Ray {
+            direction: -ray.direction,
+            ..ray
+        };
+        assert_eq!(
+            primitive.cast(&away),
+            None,
+            "{:?} hit {:?}",
+            away,
+            primitive
+        );
+    }
+}
+
+fn check_queries_2d<A: Primitive2D>(rng: &mut StdRng) {
+    for _ in 0..CASES {
+        let primitive = A::random(rng);
+        let inside = sample_2d(&primitive, rng);
+        assert!(primitive.contains(&inside));
+
+        let point = Point2::new(rng.gen_range(-15.0..15.0), rng.gen_range(-15.0..15.0));
+        let closest = primitive.closest(&point);
+        assert!(na::distance(&point, &closest) <= na::distance(&point, &inside) + TOLERANCE);
+
+        let origin = primitive.center() + random_direction_2d(rng) * 20.0;
+        assert!(!primitive.contains(&origin));
+        let ray = Ray2D {
+            origin,
+            direction: (inside - origin).normalize(),
+        };
+        let distance = primitive
+            .cast(&ray)
+            .unwrap_or_else(|| panic!("{:?} missed by {:?}", ray, primitive));
+        assert!(distance <= na::distance(&origin, &inside) + TOLERANCE);
+        let hit = ray.at_distance(distance);
+        assert!(na::distance(&hit, &primitive.closest(&hit)) <= TOLERANCE);
+
+        let away = Ray2D {
+            direction: -ray.direction,
+            ..ray
+        };
+        assert_eq!(
+            primitive.cast(&away),
+            None,
+            "{:?} hit {:?}",
+            away,
+            primitive
+        );
+    }
+}
+
+/// Intersection tests are symmetric, primitives sharing a point intersect, and primitives
+/// separated by a gap along some direction don't.
+fn check_pair<A, B>(rng: &mut StdRng)
+where
+    A: Primitive + Intersects<B>,
+    B: Primitive + Intersects<A>,
+{
+    for _ in 0..CASES {
+        let (a, b) = (A::random(rng), B::random(rng));
+        assert_eq!(a.intersects(&b), b.intersects(&a), "{:?} {:?}", a, b);
+
+        let point = sample(&a, rng);
+        let overlapping = b.translated(&(point - b.center()));
+        assert!(a.intersects(&overlapping), "{:?} {:?}", a, overlapping);
+        assert!(overlapping.intersects(&a), "{:?} {:?}", overlapping, a);
+
+        let normal = random_direction(rng);
+        let distance = a.support(&normal).coords.dot(&normal)
+            - b.support(&-normal).coords.dot(&normal)
+            + rng.gen_range(0.01..1.0);
+        let separated = b.translated(&(normal * distance));
+        assert!(!a.intersects(&separated), "{:?} {:?}", a, separated);
+        assert!(!separated.intersects(&a), "{:?} {:?}", separated, a);
+    }
+}
+
+fn check_pair_2d<A, B>(rng: &mut StdRng)
+where
+    A: Primitive2D + Intersects<B>,
+    B: Primitive2D + Intersects<A>,
+{
+    for _ in 0..CASES {
+        let (a, b) = (A::random(rng), B::random(rng));
+        assert_eq!(a.intersects(&b), b.intersects(&a), "{:?} {:?}", a, b);
+
+        let point = sample_2d(&a, rng);
+        let overlapping = b.translated(&(point - b.center()));
+        assert!(a.intersects(&overlapping), "{:?} {:?}", a, overlapping);
+        assert!(overlapping.intersects(&a), "{:?} {:?}", overlapping, a);
+
+        let normal = random_direction_2d(rng);
+        let distance = a.support(&normal).coords.dot(&normal)
+            - b.support(&-normal).coords.dot(&normal)
+            + rng.gen_range(0.01..1.0);
+        let separated = b.translated(&(normal * distance));
+        assert!(!a.intersects(&separated), "{:?} {:?}", a, separated);
+        assert!(!separated.intersects(&a), "{:?} {:?}", separated, a);
+    }
+}
+
+/// The frustum of a random perspective camera, and its view projection matrix.
+fn random_frustum(rng: &mut StdRng) -> (Frustum, Matrix4<f32>) {
+    let projection = Matrix4::new_perspective(
+        rng.gen_range(0.5..2.0),
+        rng.gen_range(0.5..2.0),
+        rng.gen_range(0.1..1.0),
+        rng.gen_range(5.0..20.0),
+    );
+    let camera = Isometry3::new(random_vector(rng, 5.0), random_vector(rng, PI));
+    let matrix = projection * camera.inverse().to_homogeneous();
+    (Frustum::new(matrix), matrix)
+}
+
+/// Primitives with a point inside of the frustum intersect it, and primitives entirely behind
+/// one of its planes don't.
+fn check_frustum<A>(rng: &mut StdRng)
+where
+    A: Primitive + Intersects<Frustum>,
+    Frustum: Intersects<A>,
+{
+    for _ in 0..CASES {
+        let (frustum, matrix) = random_frustum(rng);
+        let primitive = A::random(rng);
+
+        let clip = Point3::new(
+            rng.gen_range(-0.9..0.9),
+            rng.gen_range(-0.9..0.9),
+            rng.gen_range(-0.9..0.9),
+        );
+        let point = matrix.try_inverse().unwrap().transform_point(&clip);
+        assert!(frustum.contains_point(&point));
+        let inside = primitive.translated(&(point - primitive.center()));
+        assert!(frustum.intersects(&inside), "{:?} {:?}", frustum, inside);
+        assert!(inside.intersects(&frustum), "{:?} {:?}", inside, frustum);
+
+        let plane = &frustum.planes[rng.gen_range(0..6)];
+        let distance =
+            plane.dot_point(&primitive.support(plane.normal())) + rng.gen_range(0.01..1.0);
+        let outside = primitive.translated(&(-plane.normal() * distance));
+        assert!(!frustum.intersects(&outside), "{:?} {:?}", frustum, outside);
+        assert!(!outside.intersects(&frustum), "{:?} {:?}", outside, frustum);
+    }
+}
+
+/// Calls `check_pair` on every ordered pair of the given primitives.
+macro_rules! check_every_pair {
+    ($rng:expr; $($a:ty),*) => {
+        check_every_pair!(@each $rng; [$($a),*]; $($a),*)
+    };
+    (@each $rng:expr; $all:tt; $($a:ty),*) => {
+        $(check_every_pair!(@with $rng; $a; $all);)*
+    };
+    (@with $rng:expr; $a:ty; [$($b:ty),*]) => {
+        $(check_pair::<$a, $b>($rng);)*
+    };
+}
+
+#[test]
+fn queries() {
+    let mut rng = StdRng::seed_from_u64(0);
+    check_queries::<Sphere>(&mut rng);
+    check_queries::<Aabb>(&mut rng);
+    check_queries::<Obb>(&mut rng);
+    check_queries::<Capsule>(&mut rng);
+    check_queries::<Triangle>(&mut rng);
+    check_queries_2d::<Rect>(&mut rng);
+    check_queries_2d::<Circle>(&mut rng);
+}
+
+#[test]
+fn every_pair() {
+    let mut rng = StdRng::seed_from_u64(1);
+    check_every_pair!(&mut rng; Sphere, Aabb, Obb, Capsule, Triangle);
+    check_pair_2d::<Rect, Rect>(&mut rng);
+    check_pair_2d::<Rect, Circle>(&mut rng);
+    check_pair_2d::<Circle, Rect>(&mut rng);
+    check_pair_2d::<Circle, Circle>(&mut rng);
+}
+
+#[test]
+fn frustum_against_every_primitive() {
+    let mut rng = StdRng::seed_from_u64(2);
+    check_frustum::<Sphere>(&mut rng);
+    check_frustum::<Aabb>(&mut rng);
+    check_frustum::<Obb>(&mut rng);
+    check_frustum::<Capsule>(&mut rng);
+    check_frustum::<Triangle>(&mut rng);
+}
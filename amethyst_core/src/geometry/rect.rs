@@ -0,0 +1,139 @@
+//! 2D axis aligned rectangle.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Circle, Intersects, Ray2D};
+use crate::math::{self as na, Point2, Vector2};
+
+/// 2D axis aligned rectangle, the rectangle between the corners `min` and `max`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    /// The corner with the smallest coordinates
+    pub min: Point2<f32>,
+    /// The corner with the largest coordinates
+    pub max: Point2<f32>,
+}
+
+impl Rect {
+    /// Create a new `Rect` from its corners. No coordinate of `min` should be larger than the
+    /// same coordinate of `max`.
+    #[must_use]
+    pub fn new(min: Point2<f32>, max: Point2<f32>) -> Self {
+        Rect { min, max }
+    }
+
+    /// Create a new `Rect` from its center and its half size along each axis.
+    #[must_use]
+    pub fn from_center_half_extents(center: Point2<f32>, half_extents: Vector2<f32>) -> Self {
+        Rect {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// The center of the rectangle.
+    #[must_use]
+    pub fn center(&self) -> Point2<f32> {
+        na::center(&self.min, &self.max)
+    }
+
+    /// The half size of the rectangle along each axis.
+    #[must_use]
+    pub fn half_extents(&self) -> Vector2<f32> {
+        (self.max - self.min) / 2.0
+    }
+
+    /// Returns `true` if `point` is inside of the rectangle.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point2<f32>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns `true` if `other` is inside of the rectangle.
+    #[must_use]
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Returns `true` if `circle` is inside of the rectangle.
+    #[must_use]
+    pub fn contains_circle(&self, circle: &Circle) -> bool {
+        let radius = Vector2::from_element(circle.radius);
+        self.contains_rect(&Rect::from_center_half_extents(circle.center, radius))
+    }
+
+    /// The point of the rectangle closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point2<f32>) -> Point2<f32> {
+        Point2::from(point.coords.sup(&self.min.coords).inf(&self.max.coords))
+    }
+
+    /// Returns the distance along `ray` to the rectangle, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray2D) -> Option<f32> {
+        let mut near = 0.0_f32;
+        let mut far = f32::INFINITY;
+        let slabs = self.min.coords.iter().zip(self.max.coords.iter());
+        let components = ray.origin.coords.iter().zip(ray.direction.iter());
+        for ((min, max), (origin, direction)) in slabs.zip(components) {
+            if direction.abs() <= f32::EPSILON {
+                // Parallel to the slab, the ray never enters it if it isn't already inside.
+                if origin < min || origin > max {
+                    return None;
+                }
+            } else {
+                let t1 = (min - origin) / direction;
+                let t2 = (max - origin) / direction;
+                near = near.max(t1.min(t2));
+                far = far.min(t1.max(t2));
+                if near > far {
+                    return None;
+                }
+            }
+        }
+        Some(near)
+    }
+}
+
+impl Intersects for Rect {
+    fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+impl Intersects<Circle> for Rect {
+    fn intersects(&self, other: &Circle) -> bool {
+        other.contains_point(&self.closest_point(&other.center))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_relative_eq;
+
+    #[test]
+    fn rect_queries() {
+        let rect = Rect::from_center_half_extents(Point2::new(1.0, 1.0), Vector2::new(2.0, 1.0));
+        assert!(rect.contains_point(&Point2::new(-1.0, 0.5)));
+        assert!(!rect.contains_point(&Point2::new(-1.5, 0.5)));
+        assert_relative_eq!(
+            rect.closest_point(&Point2::new(5.0, 5.0)),
+            Point2::new(3.0, 2.0)
+        );
+
+        let ray = Ray2D {
+            origin: Point2::new(1.0, 10.0),
+            direction: Vector2::new(0.0, -1.0),
+        };
+        assert_relative_eq!(rect.intersect_ray(&ray).unwrap(), 8.0);
+        assert!(rect.intersects(&Circle::new(Point2::new(4.0, 3.0), 1.5)));
+        assert!(!rect.intersects(&Circle::new(Point2::new(4.0, 3.0), 1.4)));
+    }
+}
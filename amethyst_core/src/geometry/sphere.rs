@@ -0,0 +1,174 @@
+//! Sphere.
+
+use serde::{Deserialize, Serialize};
+
+use super::{closest_on_segment, Aabb, Capsule, Intersects, Obb, Ray, Support, Triangle};
+use crate::math::{self as na, Matrix4, Point3, Vector3};
+
+/// Sphere, the points at most `radius` away from `center`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sphere {
+    /// The center of the sphere
+    pub center: Point3<f32>,
+    /// The radius of the sphere
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Create a new `Sphere` from its center and radius.
+    #[must_use]
+    pub fn new(center: Point3<f32>, radius: f32) -> Self {
+        Sphere { center, radius }
+    }
+
+    /// The smallest sphere containing this one transformed by `matrix`, such as the
+    /// `GlobalTransform` of an entity. Its radius is scaled by the largest scale of `matrix`.
+    #[must_use]
+    pub fn transformed(&self, matrix: &Matrix4<f32>) -> Self {
+        let linear = matrix.fixed_slice::<na::U3, na::U3>(0, 0);
+        let scale = linear
+            .column_iter()
+            .map(|column| column.norm())
+            .fold(0.0, f32::max);
+        Sphere {
+            center: matrix.transform_point(&self.center),
+            radius: self.radius * scale,
+        }
+    }
+
+    /// Returns `true` if `point` is inside of the sphere.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        na::distance_squared(&self.center, point) <= self.radius * self.radius
+    }
+
+    /// Returns `true` if `other` is inside of the sphere.
+    #[must_use]
+    pub fn contains_sphere(&self, other: &Sphere) -> bool {
+        na::distance(&self.center, &other.center) + other.radius <= self.radius
+    }
+
+    /// Returns `true` if `aabb` is inside of the sphere.
+    #[must_use]
+    pub fn contains_aabb(&self, aabb: &Aabb) -> bool {
+        // The corner of the box the farthest from the center.
+        let offset = (aabb.min - self.center)
+            .abs()
+            .sup(&(aabb.max - self.center).abs());
+        offset.norm_squared() <= self.radius * self.radius
+    }
+
+    /// The point of the sphere closest to `point`, which is `point` itself if it is inside.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        let offset = point - self.center;
+        let distance = offset.norm();
+        if distance <= self.radius {
+            *point
+        } else {
+            self.center + offset * (self.radius / distance)
+        }
+    }
+
+    /// Returns the distance along `ray` to the sphere, or `None` if the ray misses it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray<f32>) -> Option<f32> {
+        let offset = ray.origin - self.center;
+        let c = offset.norm_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+        let a = ray.direction.norm_squared();
+        let b = offset.dot(&ray.direction);
+        let discriminant = b * b - a * c;
+        if b >= 0.0 || discriminant < 0.0 || a <= f32::EPSILON {
+            // Going away from the sphere, or missing it.
+            return None;
+        }
+        Some((-b - discriminant.sqrt()) / a)
+    }
+}
+
+impl Support for Sphere {
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32> {
+        let direction = direction.try_normalize(0.0).unwrap_or_else(Vector3::zeros);
+        self.center + direction * self.radius
+    }
+}
+
+impl Intersects for Sphere {
+    fn intersects(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        na::distance_squared(&self.center, &other.center) <= radius * radius
+    }
+}
+
+impl Intersects<Aabb> for Sphere {
+    fn intersects(&self, other: &Aabb) -> bool {
+        self.contains_point(&other.closest_point(&self.center))
+    }
+}
+
+impl Intersects<Obb> for Sphere {
+    fn intersects(&self, other: &Obb) -> bool {
+        self.contains_point(&other.closest_point(&self.center))
+    }
+}
+
+impl Intersects<Capsule> for Sphere {
+    fn intersects(&self, other: &Capsule) -> bool {
+        let closest = closest_on_segment(&other.start, &other.end, &self.center);
+        Sphere::new(closest, other.radius).intersects(self)
+    }
+}
+
+impl Intersects<Triangle> for Sphere {
+    fn intersects(&self, other: &Triangle) -> bool {
+        self.contains_point(&other.closest_point(&self.center))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx::assert_relative_eq, math::UnitQuaternion};
+
+    #[test]
+    fn ray_hits_the_near_side() {
+        let sphere = Sphere::new(Point3::new(0.0, 0.0, -10.0), 2.0);
+        let ray = Ray {
+            origin: Point3::origin(),
+            direction: Vector3::new(0.0, 0.0, -1.0),
+        };
+        assert_relative_eq!(sphere.intersect_ray(&ray).unwrap(), 8.0);
+
+        let away = Ray {
+            direction: Vector3::new(0.0, 0.0, 1.0),
+            ..ray
+        };
+        assert_eq!(sphere.intersect_ray(&away), None);
+
+        let beside = Ray {
+            origin: Point3::new(2.5, 0.0, 0.0),
+            ..ray
+        };
+        assert_eq!(sphere.intersect_ray(&beside), None);
+    }
+
+    #[test]
+    fn transformed_follows_translation_rotation_and_scale() {
+        let sphere = Sphere::new(Point3::new(1.0, 0.0, 0.0), 1.0);
+        let matrix = Matrix4::new_translation(&Vector3::new(0.0, 5.0, 0.0))
+            * UnitQuaternion::from_euler_angles(0.0, 0.0, std::f32::consts::FRAC_PI_2)
+                .to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&Vector3::new(3.0, 1.0, 2.0));
+        let transformed = sphere.transformed(&matrix);
+
+        assert_relative_eq!(
+            transformed.center,
+            Point3::new(0.0, 8.0, 0.0),
+            epsilon = 1.0e-5
+        );
+        assert_relative_eq!(transformed.radius, 3.0, epsilon = 1.0e-5);
+    }
+}
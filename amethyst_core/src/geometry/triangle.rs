@@ -0,0 +1,267 @@
+//! Triangle.
+
+use serde::{Deserialize, Serialize};
+
+use super::{closest_outside_segment, overlap_on_axes, Intersects, Ray, Support};
+use crate::math::{Point3, Vector3};
+
+/// Triangle between the points `a`, `b` and `c`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Triangle {
+    /// The first corner of the triangle
+    pub a: Point3<f32>,
+    /// The second corner of the triangle
+    pub b: Point3<f32>,
+    /// The third corner of the triangle
+    pub c: Point3<f32>,
+}
+
+impl Triangle {
+    /// Create a new `Triangle` from its corners.
+    #[must_use]
+    pub fn new(a: Point3<f32>, b: Point3<f32>, c: Point3<f32>) -> Self {
+        Triangle { a, b, c }
+    }
+
+    /// The 3 corners of the triangle.
+    #[must_use]
+    pub fn vertices(&self) -> [Point3<f32>; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// The center of mass of the triangle.
+    #[must_use]
+    pub fn centroid(&self) -> Point3<f32> {
+        Point3::from((self.a.coords + self.b.coords + self.c.coords) / 3.0)
+    }
+
+    /// The unit length normal of the triangle, facing the side where `a`, `b` and `c` are
+    /// counter-clockwise, or zero if the triangle is degenerate.
+    #[must_use]
+    pub fn normal(&self) -> Vector3<f32> {
+        (self.b - self.a)
+            .cross(&(self.c - self.a))
+            .try_normalize(0.0)
+            .unwrap_or_else(Vector3::zeros)
+    }
+
+    /// The point of the triangle closest to `point`.
+    #[must_use]
+    #[allow(clippy::many_single_char_names, clippy::similar_names)]
+    pub fn closest_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        // From Real-Time Collision Detection by Christer Ericson, section 5.1.5: find the Voronoi
+        // region of the triangle containing `point`.
+        let (a, b, c) = (&self.a, &self.b, &self.c);
+        let ab = b - a;
+        let ac = c - a;
+        let ap = point - a;
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return *a;
+        }
+
+        let bp = point - b;
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return *b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return *c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denominator = va + vb + vc;
+        a + ab * (vb / denominator) + ac * (vc / denominator)
+    }
+
+    /// The closest points of the segment from `start` to `end` and of the triangle, the point of
+    /// the segment first. Both are the same point if the segment crosses the triangle.
+    #[must_use]
+    pub fn closest_to_segment(
+        &self,
+        start: &Point3<f32>,
+        end: &Point3<f32>,
+    ) -> (Point3<f32>, Point3<f32>) {
+        let ray = Ray {
+            origin: *start,
+            direction: end - start,
+        };
+        match self.intersect_ray(&ray) {
+            Some(t) if t <= 1.0 => {
+                let crossing = ray.at_distance(t);
+                (crossing, crossing)
+            }
+            _ => {
+                let edges = [(self.a, self.b), (self.b, self.c), (self.c, self.a)];
+                closest_outside_segment(
+                    start,
+                    end,
+                    |point| self.closest_point(point),
+                    edges.iter().copied(),
+                )
+            }
+        }
+    }
+
+    /// Returns the distance along `ray` to the triangle, from either side, or `None` if the ray
+    /// misses it or is parallel to it.
+    #[must_use]
+    pub fn intersect_ray(&self, ray: &Ray<f32>) -> Option<f32> {
+        // Möller–Trumbore: solve for the barycentric coordinates of the hit point.
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
+        let p = ray.direction.cross(&edge2);
+        let determinant = edge1.dot(&p);
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+        let offset = ray.origin - self.a;
+        let u = offset.dot(&p) / determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(&edge1);
+        let v = ray.direction.dot(&q) / determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = edge2.dot(&q) / determinant;
+        if t >= 0.0 {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// The edges of the triangle, not normalized.
+    pub(super) fn edges(&self) -> [Vector3<f32>; 3] {
+        [self.b - self.a, self.c - self.b, self.a - self.c]
+    }
+}
+
+impl Support for Triangle {
+    fn support(&self, direction: &Vector3<f32>) -> Point3<f32> {
+        let dot = |point: &Point3<f32>| point.coords.dot(direction);
+        let mut support = self.a;
+        for vertex in &[self.b, self.c] {
+            if dot(vertex) > dot(&support) {
+                support = *vertex;
+            }
+        }
+        support
+    }
+}
+
+impl Intersects for Triangle {
+    fn intersects(&self, other: &Triangle) -> bool {
+        let normals = [self.normal(), other.normal()];
+        let (edges1, edges2) = (self.edges(), other.edges());
+        let crosses = edges1
+            .iter()
+            .flat_map(|edge1| edges2.iter().map(move |edge2| edge1.cross(edge2)));
+        // Coplanar triangles are only separated along the normals of their edges in their plane.
+        let in_plane = edges1
+            .iter()
+            .map(|edge| normals[0].cross(edge))
+            .chain(edges2.iter().map(|edge| normals[1].cross(edge)));
+        let axes = normals.iter().copied().chain(crosses).chain(in_plane);
+        overlap_on_axes(&self.vertices(), &other.vertices(), axes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_relative_eq;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(0.0, 2.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn closest_point_in_each_region() {
+        let triangle = triangle();
+        let closest = |x, y, z| triangle.closest_point(&Point3::new(x, y, z));
+
+        assert_relative_eq!(closest(0.5, 0.5, 3.0), Point3::new(0.5, 0.5, 0.0));
+        assert_relative_eq!(closest(-1.0, -1.0, 0.0), Point3::new(0.0, 0.0, 0.0));
+        assert_relative_eq!(closest(1.0, -1.0, 1.0), Point3::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(closest(2.0, 2.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        assert_relative_eq!(closest(0.0, 5.0, 0.0), Point3::new(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn ray_hits_both_sides() {
+        let triangle = triangle();
+        let down = Ray {
+            origin: Point3::new(0.5, 0.5, 3.0),
+            direction: Vector3::new(0.0, 0.0, -1.0),
+        };
+        assert_relative_eq!(triangle.intersect_ray(&down).unwrap(), 3.0);
+
+        let up = Ray {
+            origin: Point3::new(0.5, 0.5, -3.0),
+            direction: Vector3::new(0.0, 0.0, 1.0),
+        };
+        assert_relative_eq!(triangle.intersect_ray(&up).unwrap(), 3.0);
+
+        let outside = Ray {
+            origin: Point3::new(1.5, 1.5, 3.0),
+            ..down
+        };
+        assert_eq!(triangle.intersect_ray(&outside), None);
+    }
+
+    #[test]
+    fn segment_closest_to_an_edge_or_crossing() {
+        let triangle = triangle();
+        let (on_segment, on_triangle) =
+            triangle.closest_to_segment(&Point3::new(3.0, 3.0, -1.0), &Point3::new(3.0, 3.0, 1.0));
+        assert_relative_eq!(on_segment, Point3::new(3.0, 3.0, 0.0));
+        assert_relative_eq!(on_triangle, Point3::new(1.0, 1.0, 0.0));
+
+        let (on_segment, on_triangle) =
+            triangle.closest_to_segment(&Point3::new(0.5, 0.5, -1.0), &Point3::new(0.5, 0.5, 1.0));
+        assert_relative_eq!(on_segment, Point3::new(0.5, 0.5, 0.0));
+        assert_relative_eq!(on_triangle, on_segment);
+    }
+
+    #[test]
+    fn coplanar_triangles() {
+        let triangle = triangle();
+        let offset = Vector3::new(1.5, 1.5, 0.0);
+        let moved = |offset: Vector3<f32>| {
+            Triangle::new(
+                triangle.a + offset,
+                triangle.b + offset,
+                triangle.c + offset,
+            )
+        };
+        assert!(!triangle.intersects(&moved(offset)));
+        assert!(triangle.intersects(&moved(offset * 0.6)));
+    }
+}
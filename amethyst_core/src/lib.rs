@@ -25,7 +25,6 @@ pub type ArcThreadPool = std::sync::Arc<rayon::ThreadPool>;
 pub use core::fmt; //FIXME https://github.com/amethyst/amethyst/issues/2478
 
 pub use approx;
-pub use game_clock::Time;
 pub use nalgebra as math;
 pub use num_traits as num;
 pub use shrev;
@@ -33,13 +32,14 @@ pub use simba as simd;
 
 pub use self::{
     axis::{Axis2, Axis3},
+    entity_index::{NameIndex, NameIndexBundle},
     event::EventReader,
     hidden::{Hidden, HiddenPropagate},
     logger::{start_logger, LevelFilter as LogLevelFilter, Logger, LoggerConfig, StdoutLog},
     named::Named,
     shrev::EventChannel,
-    timing::Stopwatch,
-    transform::Transform,
+    timing::{FixedStep, Stopwatch, Time},
+    transform::{Transform, Transform2D},
 };
 
 /// legion ECS reexported with some convenience types.
@@ -50,7 +50,7 @@ pub mod ecs {
         *,
     };
 
-    pub use crate::dispatcher::{Dispatcher, DispatcherBuilder, System, SystemBundle};
+    pub use crate::dispatcher::{Dispatcher, DispatcherBuilder, System, SystemBundle, SystemOrder};
 }
 
 /// Re-export under name legion for proc macros
@@ -62,6 +62,9 @@ pub mod dispatcher;
 /// The frame limiter module.
 pub mod frame_limiter;
 
+/// Indexes of entities by name.
+pub mod entity_index;
+
 /// The geometry module.
 pub mod geometry;
 
@@ -74,9 +77,13 @@ pub mod hide_hierarchy_system;
 /// The Amethyst logger based on fern
 pub mod logger;
 
+/// Seedable random number generation.
+pub mod rng;
+
 mod axis;
 mod event;
 mod hidden;
 mod named;
+pub mod run_condition;
 pub mod system_ext;
 mod timing;
@@ -0,0 +1,126 @@
+//! Seedable random number generation, so runs of an application can be reproduced.
+
+use std::sync::Mutex;
+
+use rand::{rngs::StdRng, Error as RandError, RngCore, SeedableRng};
+
+/// Generator used by engine code that has no access to the resources, e.g. widget id generation.
+static GLOBAL_RNG: Mutex<Option<StdRng>> = Mutex::new(None);
+
+/// Random number generator resource shared by the systems of an application.
+///
+/// Systems that need randomness should draw from this resource instead of `rand::thread_rng`, so
+/// that seeding it with [`EngineRng::seed_from`] makes their results reproducible. Systems writing
+/// to the same resource run in the order they were added to the dispatcher, which keeps the
+/// sequence of draws identical between runs.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_core::rng::EngineRng;
+/// use rand::Rng;
+///
+/// let mut first = EngineRng::seed_from(42);
+/// let mut second = EngineRng::seed_from(42);
+/// assert_eq!(first.gen::<u32>(), second.gen::<u32>());
+/// ```
+#[derive(Clone, Debug)]
+pub struct EngineRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl EngineRng {
+    /// Creates a generator that always produces the same sequence for the same `seed`.
+    ///
+    /// The global generator used by engine code without access to resources is left untouched,
+    /// reseed it with [`seed_global`] to make that code reproducible too.
+    #[must_use]
+    pub fn seed_from(seed: u64) -> Self {
+        EngineRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed this generator was created with.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for EngineRng {
+    /// Creates a generator with a random seed, which can be read back with [`EngineRng::seed`].
+    fn default() -> Self {
+        EngineRng::seed_from(rand::random())
+    }
+}
+
+impl RngCore for EngineRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Reseeds the global generator used by engine code without access to the resources.
+///
+/// It is shared by the whole process, so this affects every application running in it.
+/// `ApplicationBuilder::with_seed` calls it with the seed of the `EngineRng`.
+pub fn seed_global(seed: u64) {
+    *GLOBAL_RNG.lock().unwrap() = Some(StdRng::seed_from_u64(seed));
+}
+
+/// Runs `f` with the global generator, which is seeded from entropy unless
+/// [`seed_global`] was called.
+pub fn with_global_rng<F, U>(f: F) -> U
+where
+    F: FnOnce(&mut StdRng) -> U,
+{
+    let mut rng = GLOBAL_RNG.lock().unwrap();
+    f(rng.get_or_insert_with(StdRng::from_entropy))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut first = EngineRng::seed_from(7);
+        let mut second = EngineRng::seed_from(7);
+        let first: Vec<u64> = (0..8).map(|_| first.gen()).collect();
+        let second: Vec<u64> = (0..8).map(|_| second.gen()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn default_seed_reproduces_sequence() {
+        let mut rng = EngineRng::default();
+        let mut replayed = EngineRng::seed_from(rng.seed());
+        assert_eq!(rng.gen::<u64>(), replayed.gen::<u64>());
+    }
+
+    #[test]
+    fn seeding_leaves_the_global_generator_alone() {
+        seed_global(11);
+        let expected: u64 = StdRng::seed_from_u64(11).gen();
+        let _rng = EngineRng::seed_from(12);
+        let _default = EngineRng::default();
+        assert_eq!(with_global_rng(|rng| rng.gen::<u64>()), expected);
+    }
+}
@@ -0,0 +1,310 @@
+//! Run conditions
+//!
+//! Conditions deciding whether the entries of a [`Dispatcher`] are executed. Conditions are
+//! attached with [`DispatcherBuilder::add_system_if`] and its siblings, and are evaluated once at
+//! the start of every [`Dispatcher::execute`], before any system runs. All systems sharing a
+//! condition therefore observe the same result during a frame, even if a system changes the
+//! resources the condition depends on.
+//!
+//! Conditions compose with [`RunConditionExt::and`], [`RunConditionExt::or`] and
+//! [`RunConditionExt::not`].
+//!
+//! # Examples
+//!
+//! ```
+//! use amethyst::core::{
+//!     ecs::{DispatcherBuilder, Resources, SystemBuilder, World},
+//!     run_condition::{every_n_ticks, resource_matches, RunConditionExt},
+//! };
+//!
+//! struct Health(u32);
+//!
+//! let mut world = World::default();
+//! let mut resources = Resources::default();
+//! resources.insert(Health(10));
+//! resources.insert(0u32);
+//!
+//! let mut dispatcher = DispatcherBuilder::default()
+//!     .add_system_if(
+//!         || {
+//!             SystemBuilder::new("RegenerationSystem")
+//!                 .write_resource::<u32>()
+//!                 .build(|_, _, ticks, _| **ticks += 1)
+//!         },
+//!         resource_matches(|health: &Health| health.0 < 100).and(every_n_ticks(2)),
+//!     )
+//!     .build(&mut world, &mut resources)
+//!     .unwrap();
+//!
+//! for _ in 0..4 {
+//!     dispatcher.execute(&mut world, &mut resources);
+//! }
+//! assert_eq!(2, *resources.get::<u32>().unwrap());
+//! ```
+//!
+//! [`Dispatcher`]: crate::dispatcher::Dispatcher
+//! [`Dispatcher::execute`]: crate::dispatcher::Dispatcher::execute
+//! [`DispatcherBuilder::add_system_if`]: crate::dispatcher::DispatcherBuilder::add_system_if
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use legion::{
+    storage::ComponentTypeId,
+    systems::{CommandBuffer, Resource, ResourceTypeId, Runnable, SystemId, UnsafeResources},
+    world::{ArchetypeAccess, WorldId},
+    Resources, World,
+};
+
+/// A condition which decides whether systems run during a [`Dispatcher::execute`].
+///
+/// Implemented for every `FnMut(&World, &Resources) -> bool`.
+///
+/// [`Dispatcher::execute`]: crate::dispatcher::Dispatcher::execute
+pub trait RunCondition: 'static {
+    /// Evaluates the condition. Called exactly once per [`Dispatcher::execute`].
+    ///
+    /// [`Dispatcher::execute`]: crate::dispatcher::Dispatcher::execute
+    fn evaluate(&mut self, world: &World, resources: &Resources) -> bool;
+}
+
+impl<F> RunCondition for F
+where
+    F: FnMut(&World, &Resources) -> bool + 'static,
+{
+    fn evaluate(&mut self, world: &World, resources: &Resources) -> bool {
+        self(world, resources)
+    }
+}
+
+/// Combinators for [`RunCondition`].
+pub trait RunConditionExt: RunCondition + Sized {
+    /// Runs when both conditions are met. Both conditions are always evaluated, so stateful
+    /// conditions such as [`every_n_ticks`] keep counting.
+    fn and<C: RunCondition>(self, other: C) -> And<Self, C> {
+        And(self, other)
+    }
+
+    /// Runs when at least one of the conditions is met. Both conditions are always evaluated,
+    /// so stateful conditions such as [`every_n_ticks`] keep counting.
+    fn or<C: RunCondition>(self, other: C) -> Or<Self, C> {
+        Or(self, other)
+    }
+
+    /// Runs when the condition isn't met.
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+}
+
+impl<T: RunCondition> RunConditionExt for T {}
+
+/// A condition met when both inner conditions are met.
+///
+/// This is created using [`RunConditionExt::and`].
+#[derive(Debug)]
+pub struct And<A, B>(A, B);
+
+impl<A: RunCondition, B: RunCondition> RunCondition for And<A, B> {
+    fn evaluate(&mut self, world: &World, resources: &Resources) -> bool {
+        let a = self.0.evaluate(world, resources);
+        let b = self.1.evaluate(world, resources);
+        a && b
+    }
+}
+
+/// A condition met when at least one inner condition is met.
+///
+/// This is created using [`RunConditionExt::or`].
+#[derive(Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<A: RunCondition, B: RunCondition> RunCondition for Or<A, B> {
+    fn evaluate(&mut self, world: &World, resources: &Resources) -> bool {
+        let a = self.0.evaluate(world, resources);
+        let b = self.1.evaluate(world, resources);
+        a || b
+    }
+}
+
+/// A condition met when the inner condition isn't.
+///
+/// This is created using [`RunConditionExt::not`].
+#[derive(Debug)]
+pub struct Not<A>(A);
+
+impl<A: RunCondition> RunCondition for Not<A> {
+    fn evaluate(&mut self, world: &World, resources: &Resources) -> bool {
+        !self.0.evaluate(world, resources)
+    }
+}
+
+/// Met while the resource `R` exists.
+pub fn resource_exists<R: Resource>() -> impl RunCondition {
+    |_: &World, resources: &Resources| resources.contains::<R>()
+}
+
+/// Met while the resource `R` exists and satisfies `predicate`.
+pub fn resource_matches<R, F>(predicate: F) -> impl RunCondition
+where
+    R: Resource,
+    F: Fn(&R) -> bool + 'static,
+{
+    move |_: &World, resources: &Resources| {
+        resources
+            .get::<R>()
+            .map_or(false, |resource| predicate(&resource))
+    }
+}
+
+/// Met while the resource `V` exists and equals `value`.
+///
+/// This is the run condition counterpart of [`pausable`](crate::system_ext::pausable).
+pub fn resource_equals<V>(value: V) -> impl RunCondition
+where
+    V: Resource + PartialEq,
+{
+    resource_matches(move |resource: &V| *resource == value)
+}
+
+/// Met on the first evaluation and then on every `n`th one.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn every_n_ticks(n: u64) -> impl RunCondition {
+    assert!(n > 0, "`every_n_ticks` requires a non-zero interval");
+    let mut tick = 0;
+    move |_: &World, _: &Resources| {
+        let met = tick % n == 0;
+        tick += 1;
+        met
+    }
+}
+
+/// Result of a [`RunCondition`] shared between the dispatcher evaluating it and the entries it
+/// gates.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConditionFlag(Arc<AtomicBool>);
+
+impl ConditionFlag {
+    pub(crate) fn set(&self, met: bool) {
+        self.0.store(met, Ordering::Relaxed);
+    }
+
+    pub(crate) fn all_set(flags: &[ConditionFlag]) -> bool {
+        flags.iter().all(|flag| flag.0.load(Ordering::Relaxed))
+    }
+}
+
+/// A system which only runs while all of its conditions were met at the start of the frame.
+pub(crate) struct Conditional<S: ?Sized> {
+    pub(crate) flags: Vec<ConditionFlag>,
+    pub(crate) system: Box<S>,
+}
+
+impl<S> Runnable for Conditional<S>
+where
+    S: Runnable + ?Sized,
+{
+    fn name(&self) -> Option<&SystemId> {
+        self.system.name()
+    }
+
+    fn reads(&self) -> (&[ResourceTypeId], &[ComponentTypeId]) {
+        self.system.reads()
+    }
+
+    fn writes(&self) -> (&[ResourceTypeId], &[ComponentTypeId]) {
+        self.system.writes()
+    }
+
+    fn prepare(&mut self, world: &World) {
+        self.system.prepare(world);
+    }
+
+    fn accesses_archetypes(&self) -> &ArchetypeAccess {
+        self.system.accesses_archetypes()
+    }
+
+    unsafe fn run_unsafe(&mut self, world: &World, resources: &UnsafeResources) {
+        if ConditionFlag::all_set(&self.flags) {
+            self.system.run_unsafe(world, resources);
+        }
+    }
+
+    fn command_buffer_mut(&mut self, world: WorldId) -> Option<&mut CommandBuffer> {
+        self.system.command_buffer_mut(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq)]
+    enum Mode {
+        Menu,
+        Playing,
+    }
+
+    fn evaluate_n(condition: &mut impl RunCondition, resources: &Resources, n: usize) -> Vec<bool> {
+        let world = World::default();
+        (0..n)
+            .map(|_| condition.evaluate(&world, resources))
+            .collect()
+    }
+
+    #[test]
+    fn resource_conditions() {
+        let mut resources = Resources::default();
+        let mut exists = resource_exists::<Mode>();
+        let mut equals = resource_equals(Mode::Playing);
+
+        assert_eq!(evaluate_n(&mut exists, &resources, 1), vec![false]);
+        assert_eq!(evaluate_n(&mut equals, &resources, 1), vec![false]);
+
+        resources.insert(Mode::Menu);
+        assert_eq!(evaluate_n(&mut exists, &resources, 1), vec![true]);
+        assert_eq!(evaluate_n(&mut equals, &resources, 1), vec![false]);
+
+        resources.insert(Mode::Playing);
+        assert_eq!(evaluate_n(&mut equals, &resources, 1), vec![true]);
+    }
+
+    #[test]
+    fn every_n_ticks_counts_evaluations() {
+        let resources = Resources::default();
+        assert_eq!(
+            evaluate_n(&mut every_n_ticks(3), &resources, 7),
+            vec![true, false, false, true, false, false, true]
+        );
+        assert_eq!(
+            evaluate_n(&mut every_n_ticks(1), &resources, 3),
+            vec![true, true, true]
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        let mut resources = Resources::default();
+        resources.insert(Mode::Playing);
+
+        let mut and = resource_equals(Mode::Playing).and(every_n_ticks(2));
+        assert_eq!(
+            evaluate_n(&mut and, &resources, 4),
+            vec![true, false, true, false]
+        );
+
+        let mut or = resource_equals(Mode::Menu).or(every_n_ticks(2));
+        assert_eq!(
+            evaluate_n(&mut or, &resources, 4),
+            vec![true, false, true, false]
+        );
+
+        let mut not = resource_equals(Mode::Menu).not();
+        assert_eq!(evaluate_n(&mut not, &resources, 1), vec![true]);
+    }
+}
@@ -1,4 +1,8 @@
-use std::time::{Duration, Instant};
+use std::{
+    convert::TryFrom,
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
 
 use crate::timing;
 
@@ -72,13 +76,225 @@ impl Stopwatch {
     }
 }
 
+/// Frame timing of the application, and the clock of its fixed updates.
+///
+/// Dereferences to the `game_clock::Time` measuring the frames, which provides the frame deltas,
+/// the frame number and the time scale. Each frame also accumulates its scaled delta in a
+/// [`FixedStep`], so fixed updates slow down, speed up and pause along with the time scale.
+#[derive(Debug, Default)]
+pub struct Time {
+    clock: game_clock::Time,
+    fixed_step: FixedStep,
+    fixed_updates: u32,
+}
+
+impl Time {
+    /// Advances the clock by a frame which took `delta` of real time, and schedules the fixed
+    /// updates due after the scaled duration of the frame.
+    pub fn advance_frame(&mut self, delta: Duration) {
+        self.clock.advance_frame(delta);
+        self.fixed_updates = self.fixed_step.advance(self.clock.delta_time());
+    }
+
+    /// Returns whether another fixed update is due this frame, counting it as run.
+    ///
+    /// The application calls this in a loop before every frame, running a fixed update each time
+    /// it returns `true`.
+    pub fn step_fixed_update(&mut self) -> bool {
+        if self.fixed_updates > 0 {
+            self.fixed_updates -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sets the duration simulated by a single fixed update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn set_fixed_time(&mut self, step: Duration) {
+        self.clock.set_fixed_time(step);
+        self.fixed_step.set_step_length(step);
+    }
+
+    /// Progress from the last fixed update towards the next one, between 0 and 1.
+    ///
+    /// Rendering interpolates between the two last fixed updates using this factor, see
+    /// [`TransformInterpolation`](crate::transform::TransformInterpolation).
+    #[must_use]
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.fixed_step.interpolation_alpha()
+    }
+
+    /// The accumulator of the fixed updates.
+    #[must_use]
+    pub fn fixed_step(&self) -> &FixedStep {
+        &self.fixed_step
+    }
+
+    /// The accumulator of the fixed updates, e.g. to cap the fixed updates run per frame.
+    pub fn fixed_step_mut(&mut self) -> &mut FixedStep {
+        &mut self.fixed_step
+    }
+}
+
+impl Deref for Time {
+    type Target = game_clock::Time;
+
+    fn deref(&self) -> &Self::Target {
+        &self.clock
+    }
+}
+
+impl DerefMut for Time {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.clock
+    }
+}
+
+/// Accumulates frame time for fixed updates, and tells how far rendering is between two of them.
+///
+/// Every frame, [`Time`] feeds its scaled delta to [`FixedStep::advance`], which returns how many
+/// fixed updates to run. Time which doesn't make up a whole step is carried over to the next
+/// frame, so fixed updates run at a stable rate regardless of the frame rate.
+///
+/// If a frame takes so long that more than [`FixedStep::max_steps_per_frame`] steps are due,
+/// the excess steps are dropped. Otherwise slow fixed updates would make the next frame even
+/// longer, and the game would never catch up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixedStep {
+    step: Duration,
+    max_steps_per_frame: u32,
+    accumulator: Duration,
+    interpolation_alpha: f32,
+    total_steps: u64,
+    dropped_steps: u64,
+}
+
+impl Default for FixedStep {
+    fn default() -> Self {
+        FixedStep::new(Duration::from_nanos(1_000_000_000 / 60))
+    }
+}
+
+impl FixedStep {
+    /// Creates a new `FixedStep` running a fixed update every `step`, and at most 5 per frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    pub fn new(step: Duration) -> Self {
+        assert!(
+            step > Duration::new(0, 0),
+            "Fixed step length must be positive"
+        );
+        FixedStep {
+            step,
+            max_steps_per_frame: 5,
+            accumulator: Duration::new(0, 0),
+            interpolation_alpha: 0.0,
+            total_steps: 0,
+            dropped_steps: 0,
+        }
+    }
+
+    /// Duration simulated by a single fixed update.
+    #[must_use]
+    pub fn step_length(&self) -> Duration {
+        self.step
+    }
+
+    /// Sets the duration simulated by a single fixed update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn set_step_length(&mut self, step: Duration) {
+        assert!(
+            step > Duration::new(0, 0),
+            "Fixed step length must be positive"
+        );
+        self.step = step;
+    }
+
+    /// Maximum number of fixed updates run during a single frame.
+    #[must_use]
+    pub fn max_steps_per_frame(&self) -> u32 {
+        self.max_steps_per_frame
+    }
+
+    /// Sets the maximum number of fixed updates run during a single frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_steps` is zero.
+    pub fn set_max_steps_per_frame(&mut self, max_steps: u32) {
+        assert!(
+            max_steps > 0,
+            "At least one fixed step must be allowed per frame"
+        );
+        self.max_steps_per_frame = max_steps;
+    }
+
+    /// Progress from the last fixed update towards the next one, between 0 and 1.
+    #[must_use]
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+
+    /// Number of fixed updates run since the application started.
+    #[must_use]
+    pub fn total_steps(&self) -> u64 {
+        self.total_steps
+    }
+
+    /// Number of fixed updates dropped because they exceeded
+    /// [`FixedStep::max_steps_per_frame`].
+    #[must_use]
+    pub fn dropped_steps(&self) -> u64 {
+        self.dropped_steps
+    }
+
+    /// Accounts for `delta` of elapsed time and returns the number of fixed updates to run.
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        self.accumulator += delta;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_steps_per_frame {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        if self.accumulator >= self.step {
+            let step = self.step.as_nanos();
+            let accumulator = self.accumulator.as_nanos();
+            let dropped = u64::try_from(accumulator / step).unwrap_or(u64::MAX);
+            log::debug!(
+                "Dropping {} fixed steps, exceeding {} steps per frame",
+                dropped,
+                self.max_steps_per_frame
+            );
+            self.dropped_steps = self.dropped_steps.saturating_add(dropped);
+            self.accumulator =
+                Duration::from_nanos(u64::try_from(accumulator % step).unwrap_or(u64::MAX));
+        }
+
+        self.total_steps += u64::from(steps);
+        self.interpolation_alpha = self.accumulator.as_secs_f32() / self.step.as_secs_f32();
+        steps
+    }
+}
+
 // Unit tests
 #[cfg(test)]
 mod tests {
 
     use std::{thread, time::Duration};
 
-    use super::Stopwatch;
+    use super::{FixedStep, Stopwatch, Time};
 
     // Timing varies more on macOS CI
     fn get_uncertainty() -> u32 {
@@ -179,4 +395,55 @@ mod tests {
             elapsed
         );
     }
+
+    #[test]
+    fn fixed_step_carries_remainder() {
+        let mut fixed = FixedStep::new(Duration::from_millis(10));
+
+        assert_eq!(fixed.advance(Duration::from_millis(25)), 2);
+        assert!((fixed.interpolation_alpha() - 0.5).abs() < 1e-6);
+
+        assert_eq!(fixed.advance(Duration::from_millis(5)), 1);
+        assert!(fixed.interpolation_alpha().abs() < 1e-6);
+
+        assert_eq!(fixed.advance(Duration::from_millis(2)), 0);
+        assert_eq!(fixed.total_steps(), 3);
+        assert_eq!(fixed.dropped_steps(), 0);
+    }
+
+    #[test]
+    fn fixed_step_drops_excess_steps() {
+        let mut fixed = FixedStep::new(Duration::from_millis(10));
+        fixed.set_max_steps_per_frame(3);
+
+        assert_eq!(fixed.advance(Duration::from_millis(57)), 3);
+        assert_eq!(fixed.dropped_steps(), 2);
+        assert!((fixed.interpolation_alpha() - 0.7).abs() < 1e-6);
+
+        assert_eq!(fixed.advance(Duration::from_millis(3)), 1);
+        assert_eq!(fixed.total_steps(), 4);
+    }
+
+    #[test]
+    fn fixed_updates_follow_the_time_scale() {
+        let mut time = Time::default();
+        time.set_fixed_time(Duration::from_millis(10));
+
+        time.advance_frame(Duration::from_millis(25));
+        assert!(time.step_fixed_update());
+        assert!(time.step_fixed_update());
+        assert!(!time.step_fixed_update());
+        assert!((time.interpolation_alpha() - 0.5).abs() < 1e-6);
+
+        time.set_time_scale(0.0);
+        time.advance_frame(Duration::from_millis(25));
+        assert!(!time.step_fixed_update());
+
+        time.set_time_scale(2.0);
+        time.advance_frame(Duration::from_millis(10));
+        assert!(time.step_fixed_update());
+        assert!(time.step_fixed_update());
+        assert!(!time.step_fixed_update());
+        assert_eq!(time.fixed_step().total_steps(), 4);
+    }
 }
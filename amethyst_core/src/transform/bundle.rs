@@ -3,10 +3,19 @@
 use amethyst_error::Error;
 
 use crate::{
-    ecs::{DispatcherBuilder, Resources, SystemBundle, World},
-    transform::{MissingPreviousParentSystem, ParentUpdateSystem, TransformSystem},
+    ecs::{DispatcherBuilder, Resources, SystemBundle, SystemOrder, World},
+    transform::{ParentUpdateSystem, TransformSystem},
 };
 
+/// Label carried by every system of the [`TransformBundle`].
+pub const TRANSFORM_SET: &str = "transform";
+
+/// Label of the [`TransformSystem`], which computes the global matrices.
+pub const TRANSFORM_SYSTEM: &str = "transform_system";
+
+/// Label of the [`ParentUpdateSystem`], which keeps the `Children` of parents up to date.
+pub const PARENT_UPDATE_SYSTEM: &str = "parent_update_system";
+
 /// Transform bundle
 #[derive(Default)]
 #[allow(missing_debug_implementations)]
@@ -20,9 +29,25 @@ impl SystemBundle for TransformBundle {
         builder: &mut DispatcherBuilder,
     ) -> Result<(), Error> {
         builder
-            .add_system(MissingPreviousParentSystem)
-            .add_system(ParentUpdateSystem)
-            .add_system(TransformSystem);
+            .add_system_ordered(
+                ParentUpdateSystem,
+                SystemOrder::new()
+                    .label(TRANSFORM_SET)
+                    .label(PARENT_UPDATE_SYSTEM),
+            )
+            // The `Children` added by the `ParentUpdateSystem` are needed to propagate transforms.
+            .flush_ordered(
+                SystemOrder::new()
+                    .label(TRANSFORM_SET)
+                    .after(PARENT_UPDATE_SYSTEM)
+                    .before(TRANSFORM_SYSTEM),
+            )
+            .add_system_ordered(
+                TransformSystem,
+                SystemOrder::new()
+                    .label(TRANSFORM_SET)
+                    .label(TRANSFORM_SYSTEM),
+            );
 
         Ok(())
     }
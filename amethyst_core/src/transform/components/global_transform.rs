@@ -0,0 +1,118 @@
+//! Global transform component.
+
+use crate::math::{self as na, Matrix4, Point3, Vector3};
+
+/// World space transformation matrix of an entity, its [`Transform`](super::Transform) or
+/// [`Transform2D`](super::Transform2D) combined with the ones of its ancestors.
+///
+/// Computed by the [`TransformSystem`](crate::transform::TransformSystem), which adds it to the
+/// entities with a `Transform` or a `Transform2D` and updates it only when the local transform of
+/// the entity or of one of its ancestors changes, or when the entity changes
+/// [`Parent`](super::Parent).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlobalTransform(pub Matrix4<f32>);
+
+impl GlobalTransform {
+    /// The global transformation matrix.
+    #[inline]
+    #[must_use]
+    pub fn matrix(&self) -> &Matrix4<f32> {
+        &self.0
+    }
+
+    /// The position of the entity in world space.
+    #[inline]
+    #[must_use]
+    pub fn translation(&self) -> Vector3<f32> {
+        self.0.column(3).xyz()
+    }
+
+    /// Transforms `point` from the local space of the entity to world space.
+    #[inline]
+    #[must_use]
+    pub fn transform_point(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.0.transform_point(point)
+    }
+
+    /// Verifies that the `Matrix4` doesn't contain any NaN values.
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.0.as_slice().iter().all(|f| f32::is_finite(*f))
+    }
+
+    /// Calculates the inverse of this transform, which is in effect the 'view matrix' as
+    /// commonly seen in computer graphics. This function computes the view matrix for the
+    /// global transformation of the entity, and so takes into account `Parent`s.
+    ///
+    /// We can exploit the extra information we have to perform this inverse faster than `O(n^3)`.
+    #[must_use]
+    pub fn view_matrix(&self) -> Matrix4<f32> {
+        let mut res = self.0;
+
+        // Perform an in-place inversion of the 3x3 matrix
+        {
+            let mut slice3x3 = res.fixed_slice_mut::<na::U3, na::U3>(0, 0);
+            assert!(slice3x3.try_inverse_mut());
+        }
+
+        let mut translation = -res.column(3).xyz();
+        translation = res.fixed_slice::<na::U3, na::U3>(0, 0) * translation;
+
+        let mut res_trans = res.column_mut(3);
+        res_trans.x = translation.x;
+        res_trans.y = translation.y;
+        res_trans.z = translation.z;
+
+        res
+    }
+}
+
+impl Default for GlobalTransform {
+    /// The identity matrix.
+    fn default() -> Self {
+        GlobalTransform(na::one())
+    }
+}
+
+impl From<Matrix4<f32>> for GlobalTransform {
+    fn from(matrix: Matrix4<f32>) -> Self {
+        GlobalTransform(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{approx::*, math::UnitQuaternion, transform::Transform};
+
+    /// Test correctness of global view matrix vs. inverse matrix globally
+    #[test]
+    fn test_view_matrix() {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(5.0, 70.1, 43.7);
+        transform.set_scale(Vector3::new(1.0, 5.0, 8.9));
+        transform.set_rotation(
+            UnitQuaternion::rotation_between(
+                &Vector3::new(-1.0, 1.0, 2.0),
+                &Vector3::new(1.0, 0.0, 0.0),
+            )
+            .unwrap(),
+        );
+        let global = GlobalTransform::from(transform.matrix());
+
+        assert_relative_eq!(
+            global.matrix().try_inverse().unwrap(),
+            global.view_matrix(),
+            max_relative = 0.000_1,
+        );
+    }
+
+    #[test]
+    fn is_finite() {
+        let mut global = GlobalTransform::default();
+        assert!(global.is_finite());
+
+        global.0.fill_row(2, f32::NAN);
+        assert!(!global.is_finite());
+    }
+}
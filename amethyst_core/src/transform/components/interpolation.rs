@@ -0,0 +1,132 @@
+//! Transform interpolation component.
+
+use crate::{
+    math::{Isometry3, Translation3, Vector3},
+    transform::Transform,
+};
+
+/// Smooths the rendering of an entity moved by fixed updates.
+///
+/// Fixed updates usually don't line up with rendered frames, so an entity moved only by fixed
+/// updates appears to stutter. Entities with this component are instead rendered between their
+/// two last simulated poses, using [`Time::interpolation_alpha`].
+///
+/// The application records the simulated [`Transform`] after every fixed update, and restores
+/// it before the next round of fixed updates. In between, it replaces it with the interpolated
+/// pose. Entities with this component should therefore only be moved by fixed updates.
+///
+/// [`Time::interpolation_alpha`]: crate::Time::interpolation_alpha
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransformInterpolation {
+    previous: Option<Pose>,
+    current: Option<Pose>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pose {
+    isometry: Isometry3<f32>,
+    scale: Vector3<f32>,
+}
+
+impl Pose {
+    fn of(transform: &Transform) -> Self {
+        Pose {
+            isometry: *transform.isometry(),
+            scale: *transform.scale(),
+        }
+    }
+
+    fn write(&self, transform: &mut Transform) {
+        transform.set_isometry(self.isometry);
+        *transform.scale_mut() = self.scale;
+    }
+}
+
+impl TransformInterpolation {
+    /// Forgets the recorded poses, so the next move isn't interpolated. Call this after
+    /// teleporting the entity.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.current = None;
+    }
+
+    /// Records the pose simulated by a fixed update.
+    pub(crate) fn record(&mut self, transform: &Transform) {
+        let pose = Pose::of(transform);
+        self.previous = Some(self.current.unwrap_or(pose));
+        self.current = Some(pose);
+    }
+
+    /// Writes back the last simulated pose.
+    pub(crate) fn restore(&self, transform: &mut Transform) {
+        if let Some(current) = &self.current {
+            current.write(transform);
+        }
+    }
+
+    /// Writes the pose `alpha` of the way from the previous simulated pose to the last one.
+    pub(crate) fn interpolate(&self, transform: &mut Transform, alpha: f32) {
+        if let (Some(previous), Some(current)) = (&self.previous, &self.current) {
+            let translation = previous
+                .isometry
+                .translation
+                .vector
+                .lerp(&current.isometry.translation.vector, alpha);
+            let rotation = previous
+                .isometry
+                .rotation
+                .try_slerp(&current.isometry.rotation, alpha, f32::EPSILON)
+                .unwrap_or(current.isometry.rotation);
+
+            Pose {
+                isometry: Isometry3::from_parts(Translation3::from(translation), rotation),
+                scale: previous.scale.lerp(&current.scale, alpha),
+            }
+            .write(transform);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::math::UnitQuaternion;
+
+    #[test]
+    fn interpolates_between_recorded_poses() {
+        let mut interpolation = TransformInterpolation::default();
+        let mut transform = Transform::default();
+        interpolation.record(&transform);
+
+        transform.set_translation_xyz(10.0, 0.0, 0.0);
+        transform.set_rotation(UnitQuaternion::from_euler_angles(0.0, 0.0, 1.0));
+        transform.set_scale(Vector3::new(3.0, 3.0, 3.0));
+        interpolation.record(&transform);
+
+        let mut rendered = transform;
+        interpolation.interpolate(&mut rendered, 0.25);
+        assert_relative_eq!(*rendered.translation(), Vector3::new(2.5, 0.0, 0.0));
+        assert_relative_eq!(rendered.euler_angles().2, 0.25, epsilon = 1e-6);
+        assert_relative_eq!(*rendered.scale(), Vector3::new(1.5, 1.5, 1.5));
+
+        interpolation.restore(&mut rendered);
+        assert_eq!(rendered, transform);
+    }
+
+    #[test]
+    fn reset_skips_interpolation() {
+        let mut interpolation = TransformInterpolation::default();
+        let mut transform = Transform::default();
+        interpolation.record(&transform);
+        interpolation.reset();
+
+        transform.set_translation_xyz(10.0, 0.0, 0.0);
+        interpolation.record(&transform);
+
+        let mut rendered = transform;
+        interpolation.interpolate(&mut rendered, 0.5);
+        assert_eq!(rendered, transform);
+    }
+}
@@ -2,10 +2,16 @@
 
 pub use self::{
     children::Children,
+    global_transform::GlobalTransform,
+    interpolation::TransformInterpolation,
     parent::{Parent, PreviousParent},
     transform::{Transform, TransformValues},
+    transform_2d::Transform2D,
 };
 
 mod children;
+mod global_transform;
+mod interpolation;
 mod parent;
 mod transform;
+mod transform_2d;
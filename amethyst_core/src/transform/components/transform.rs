@@ -16,7 +16,10 @@ use crate::{
 
 /// Local position, rotation, and scale (from parent if it exists).
 ///
-/// Used for rendering position and orientation.
+/// The position and orientation used for rendering are the ones of the [`GlobalTransform`]
+/// computed from it.
+///
+/// [`GlobalTransform`]: crate::transform::GlobalTransform
 ///
 /// The transforms are performed in this order: scale, then rotation, then translation.
 #[derive(
@@ -46,14 +49,6 @@ pub struct Transform {
     #[get_mut = "pub"]
     #[serde_diff(opaque)]
     scale: Vector3<f32>,
-    /// The global transformation matrix.
-    #[get = "pub"]
-    #[serde_diff(opaque)]
-    pub(crate) global_matrix: Matrix4<f32>,
-    /// The parent transformation matrix.
-    #[get = "pub"]
-    #[serde_diff(opaque)]
-    pub(crate) parent_matrix: Matrix4<f32>,
 }
 
 impl Transform {
@@ -80,8 +75,6 @@ impl Transform {
         Transform {
             isometry: Isometry3::from_parts(na::convert(position), na::convert(rotation)),
             scale: na::convert(scale),
-            global_matrix: na::one(),
-            parent_matrix: na::one(),
         }
     }
 
@@ -535,14 +528,12 @@ impl Transform {
         self
     }
 
-    /// Verifies that the global `Matrix4` doesn't contain any NaN values.
+    /// Verifies that the local `Matrix4` doesn't contain any NaN values.
     #[must_use]
     pub fn is_finite(&self) -> bool {
-        self.global_matrix
-            .as_slice()
-            .iter()
-            .all(|f| f32::is_finite(*f))
+        self.matrix().as_slice().iter().all(|f| f32::is_finite(*f))
     }
+
     /// Calculates the inverse of this transform, which is in effect the 'view matrix' as
     /// commonly seen in computer graphics. This function computes the view matrix for ONLY
     /// the local transformation, and ignores any `Parent`s of this entity.
@@ -556,39 +547,6 @@ impl Transform {
             .to_homogeneous()
             .append_nonuniform_scaling(&inv_scale)
     }
-
-    /// Calculates the inverse of this transform, which is in effect the 'view matrix' as
-    /// commonly seen in computer graphics. This function computes the view matrix for the
-    /// global transformation of the entity, and so takes into account `Parent`s.
-    ///
-    /// We can exploit the extra information we have to perform this inverse faster than `O(n^3)`.
-    #[must_use]
-    pub fn global_view_matrix(&self) -> Matrix4<f32> {
-        let mut res = self.global_matrix;
-
-        // Perform an in-place inversion of the 3x3 matrix
-        {
-            let mut slice3x3 = res.fixed_slice_mut::<na::U3, na::U3>(0, 0);
-            assert!(slice3x3.try_inverse_mut());
-        }
-
-        let mut translation = -res.column(3).xyz();
-        translation = res.fixed_slice::<na::U3, na::U3>(0, 0) * translation;
-
-        let mut res_trans = res.column_mut(3);
-        res_trans.x = translation.x;
-        res_trans.y = translation.y;
-        res_trans.z = translation.z;
-
-        res
-    }
-
-    /// This function allows for test cases of copying the local matrix to the global matrix.
-    /// Useful for tests or other debug type access.
-    #[inline]
-    pub fn copy_local_to_global(&mut self) {
-        self.global_matrix = self.matrix();
-    }
 }
 
 impl Default for Transform {
@@ -597,8 +555,6 @@ impl Default for Transform {
         Transform {
             isometry: Isometry3::identity(),
             scale: Vector3::from_element(1.0),
-            global_matrix: na::one(),
-            parent_matrix: na::one(),
         }
     }
 }
@@ -775,26 +731,6 @@ mod tests {
         );
     }
 
-    /// Test correctness of global view matrix vs. inverse matrix globally
-    #[test]
-    fn test_global_view_matrix() {
-        let mut transform = Transform::default();
-        transform.set_translation_xyz(5.0, 70.1, 43.7);
-        transform.set_scale(Vector3::new(1.0, 5.0, 8.9));
-        transform.set_rotation(
-            UnitQuaternion::rotation_between(
-                &Vector3::new(-1.0, 1.0, 2.0),
-                &Vector3::new(1.0, 0.0, 0.0),
-            )
-            .unwrap(),
-        );
-
-        assert_ulps_eq!(
-            transform.global_matrix().try_inverse().unwrap(),
-            transform.global_view_matrix(),
-        );
-    }
-
     #[test]
     fn ser_deser() {
         let mut transform = Transform::default();
@@ -825,7 +761,7 @@ mod tests {
         let mut transform = Transform::default();
         assert!(transform.is_finite());
 
-        transform.global_matrix.fill_row(2, f32::NAN);
+        transform.set_translation_x(f32::NAN);
         assert!(!transform.is_finite());
     }
 }
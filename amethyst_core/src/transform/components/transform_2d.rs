@@ -0,0 +1,316 @@
+//! Local 2D transform component.
+use legion_prefab::register_component_type;
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+
+use crate::{
+    math::{Matrix4, Translation3, UnitQuaternion, Vector2, Vector3},
+    transform::Transform,
+};
+
+/// Local position, rotation around the Z axis, scale and layer of a 2D entity (from parent if it
+/// exists).
+///
+/// A cheaper alternative to [`Transform`] for 2D games: the [`TransformSystem`] computes the
+/// [`GlobalTransform`] of entities with a `Transform2D` without going through quaternions, so
+/// they are drawn by `SpriteRender`, `DrawFlat2D` and tile maps like other entities. 2D and 3D
+/// entities can be parents of one another, and an entity with both components uses its
+/// `Transform`.
+///
+/// The transforms are performed in this order: scale, then rotation, then translation.
+///
+/// [`GlobalTransform`]: crate::transform::GlobalTransform
+/// [`TransformSystem`]: crate::transform::TransformSystem
+///
+/// # Examples
+///
+/// ```
+/// # use amethyst::core::transform::Transform2D;
+/// # use amethyst::core::math::Vector2;
+/// let mut transform = Transform2D::from(Vector2::new(10.0, 20.0));
+/// transform
+///     .set_rotation(std::f32::consts::FRAC_PI_2)
+///     .set_layer(1.0);
+///
+/// assert_eq!(transform.translation().x, 10.0);
+/// assert_eq!(transform.layer(), 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize, TypeUuid, SerdeDiff)]
+#[uuid = "7230e6f0-c787-4895-b835-d706b18e3a5b"]
+#[serde(default)]
+pub struct Transform2D {
+    /// Translation in the XY plane
+    #[serde_diff(opaque)]
+    translation: Vector2<f32>,
+    /// Counter-clockwise rotation around the Z axis, in radians
+    rotation: f32,
+    /// Scale along the X and Y axes
+    #[serde_diff(opaque)]
+    scale: Vector2<f32>,
+    /// Translation along the Z axis, which orders the entities when drawing
+    layer: f32,
+}
+
+impl Transform2D {
+    /// Create a new Transform2D.
+    #[must_use]
+    pub fn new(translation: Vector2<f32>, rotation: f32, scale: Vector2<f32>, layer: f32) -> Self {
+        Transform2D {
+            translation,
+            rotation,
+            scale,
+            layer,
+        }
+    }
+
+    /// Returns the local object matrix for the transform.
+    #[inline]
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn matrix(&self) -> Matrix4<f32> {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (x, y) = (self.scale.x, self.scale.y);
+        Matrix4::new(
+            cos * x, -sin * y, 0.0, self.translation.x,
+            sin * x,  cos * y, 0.0, self.translation.y,
+            0.0,      0.0,     1.0, self.layer,
+            0.0,      0.0,     0.0, 1.0,
+        )
+    }
+
+    /// Returns a reference to the translation vector.
+    #[inline]
+    #[must_use]
+    pub fn translation(&self) -> &Vector2<f32> {
+        &self.translation
+    }
+
+    /// Returns a mutable reference to the translation vector.
+    #[inline]
+    pub fn translation_mut(&mut self) -> &mut Vector2<f32> {
+        &mut self.translation
+    }
+
+    /// Sets the translation vector.
+    #[inline]
+    pub fn set_translation(&mut self, translation: Vector2<f32>) -> &mut Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Sets the position.
+    #[inline]
+    pub fn set_translation_xy(&mut self, x: f32, y: f32) -> &mut Self {
+        self.set_translation(Vector2::new(x, y))
+    }
+
+    /// Move relatively to its current position.
+    #[inline]
+    pub fn append_translation(&mut self, translation: Vector2<f32>) -> &mut Self {
+        self.translation += translation;
+        self
+    }
+
+    /// Move relatively to its current position and orientation.
+    #[inline]
+    pub fn prepend_translation(&mut self, translation: Vector2<f32>) -> &mut Self {
+        let (sin, cos) = self.rotation.sin_cos();
+        self.translation += Vector2::new(
+            cos * translation.x - sin * translation.y,
+            sin * translation.x + cos * translation.y,
+        );
+        self
+    }
+
+    /// Returns the counter-clockwise rotation around the Z axis, in radians.
+    #[inline]
+    #[must_use]
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Sets the counter-clockwise rotation around the Z axis, in radians.
+    #[inline]
+    pub fn set_rotation(&mut self, angle: f32) -> &mut Self {
+        self.rotation = angle;
+        self
+    }
+
+    /// Rotates counter-clockwise around the Z axis by `delta_angle` radians.
+    #[inline]
+    pub fn rotate(&mut self, delta_angle: f32) -> &mut Self {
+        self.rotation += delta_angle;
+        self
+    }
+
+    /// Returns a reference to the scale vector.
+    #[inline]
+    #[must_use]
+    pub fn scale(&self) -> &Vector2<f32> {
+        &self.scale
+    }
+
+    /// Returns a mutable reference to the scale vector.
+    #[inline]
+    pub fn scale_mut(&mut self) -> &mut Vector2<f32> {
+        &mut self.scale
+    }
+
+    /// Set the scaling factor of this transform.
+    #[inline]
+    pub fn set_scale(&mut self, scale: Vector2<f32>) -> &mut Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the translation along the Z axis, which orders the entities when drawing.
+    #[inline]
+    #[must_use]
+    pub fn layer(&self) -> f32 {
+        self.layer
+    }
+
+    /// Sets the translation along the Z axis, which orders the entities when drawing.
+    #[inline]
+    pub fn set_layer(&mut self, layer: f32) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Verifies that the transform doesn't contain any NaN values.
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.translation.iter().all(|f| f32::is_finite(*f))
+            && self.rotation.is_finite()
+            && self.scale.iter().all(|f| f32::is_finite(*f))
+            && self.layer.is_finite()
+    }
+}
+
+impl Default for Transform2D {
+    /// The default transform does nothing when used to transform an entity.
+    fn default() -> Self {
+        Transform2D {
+            translation: Vector2::zeros(),
+            rotation: 0.0,
+            scale: Vector2::from_element(1.0),
+            layer: 0.0,
+        }
+    }
+}
+
+register_component_type!(Transform2D);
+
+/// Creates a Transform2D using the `Vector2` as the translation vector.
+impl From<Vector2<f32>> for Transform2D {
+    fn from(translation: Vector2<f32>) -> Self {
+        Transform2D {
+            translation,
+            ..Transform2D::default()
+        }
+    }
+}
+
+/// Creates the `Transform` with the same matrix as the `Transform2D`.
+impl From<Transform2D> for Transform {
+    fn from(transform: Transform2D) -> Self {
+        Transform::new(
+            Translation3::new(
+                transform.translation.x,
+                transform.translation.y,
+                transform.layer,
+            ),
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), transform.rotation),
+            Vector3::new(transform.scale.x, transform.scale.y, 1.0),
+        )
+    }
+}
+
+/// Projects the `Transform` on the XY plane: the Z translation becomes the layer, the rotation is
+/// reduced to the angle of the rotated X axis around the Z axis, and the Z scale is dropped.
+impl From<Transform> for Transform2D {
+    fn from(transform: Transform) -> Self {
+        let translation = transform.translation();
+        let x_axis = transform.rotation() * Vector3::x();
+        Transform2D {
+            translation: translation.xy(),
+            rotation: x_axis.y.atan2(x_axis.x),
+            scale: transform.scale().xy(),
+            layer: translation.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_3;
+
+    use crate::{
+        approx::*,
+        math::Vector2,
+        transform::{Transform, Transform2D},
+    };
+
+    fn transform() -> Transform2D {
+        Transform2D::new(
+            Vector2::new(3.0, -2.0),
+            FRAC_PI_3,
+            Vector2::new(2.0, 0.5),
+            4.0,
+        )
+    }
+
+    #[test]
+    fn matrix_matches_3d_transform() {
+        let transform = transform();
+        assert_relative_eq!(
+            transform.matrix(),
+            Transform::from(transform).matrix(),
+            max_relative = 0.000_001,
+        );
+    }
+
+    #[test]
+    fn conversion_round_trip() {
+        let transform = transform();
+        let converted = Transform2D::from(Transform::from(transform));
+
+        assert_relative_eq!(*converted.translation(), *transform.translation());
+        assert_relative_eq!(
+            converted.rotation(),
+            transform.rotation(),
+            max_relative = 0.000_001
+        );
+        assert_relative_eq!(
+            *converted.scale(),
+            *transform.scale(),
+            max_relative = 0.000_001
+        );
+        assert_relative_eq!(converted.layer(), transform.layer());
+    }
+
+    #[test]
+    fn prepend_translation_follows_rotation() {
+        let mut transform = Transform2D::default();
+        transform
+            .set_rotation(std::f32::consts::FRAC_PI_2)
+            .prepend_translation(Vector2::new(1.0, 0.0));
+
+        assert_relative_eq!(
+            *transform.translation(),
+            Vector2::new(0.0, 1.0),
+            epsilon = 0.000_001,
+        );
+    }
+
+    #[test]
+    fn ser_deser() {
+        let transform = transform();
+        let s: String =
+            ron::ser::to_string_pretty(&transform, ron::ser::PrettyConfig::default()).unwrap();
+        let transform2: Transform2D = ron::de::from_str(&s).unwrap();
+
+        assert_eq!(transform, transform2);
+    }
+}
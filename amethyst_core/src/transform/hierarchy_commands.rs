@@ -0,0 +1,509 @@
+//! Commands editing the transform hierarchy.
+
+use std::{collections::HashSet, fmt};
+
+use super::{
+    components::{Children, Parent, Transform, Transform2D},
+    transform_system::local_matrix,
+};
+use crate::{
+    ecs::{CommandBuffer, Entity, EntityStore},
+    math::{self as na, Matrix3, Matrix4, Rotation3, Translation3, UnitQuaternion, Vector3},
+};
+
+/// Error produced when a hierarchy command can't be applied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HierarchyError {
+    /// The entity would become its own ancestor.
+    Cycle {
+        /// Entity being attached.
+        entity: Entity,
+        /// Parent it was attached to, which is the entity itself or one of its descendants.
+        parent: Entity,
+    },
+}
+
+impl fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HierarchyError::Cycle { entity, parent } => write!(
+                f,
+                "Attaching {:?} to {:?} would make it its own ancestor",
+                entity, parent
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
+/// Commands editing the hierarchy of entities defined by their [`Parent`].
+///
+/// The hierarchy is read from `world` when the command is recorded, following the [`Children`]
+/// maintained by the [`ParentUpdateSystem`](super::ParentUpdateSystem) and the `Parent` of the
+/// entities. From a system, `world` is its `SubWorld`, which needs read access to `Parent`,
+/// `Children`, `Transform` and `Transform2D`.
+///
+/// The `_keep_world` commands compute the new local [`Transform`] of the entity from the
+/// `Transform`s of its current and new ancestors, so they can be used on entities moved this frame.
+/// A shear, caused by a rotation under a non uniform scale, can't be kept by a `Transform`.
+/// Entities with only a [`Transform2D`] get a new `Transform2D`, projected on the XY plane.
+///
+/// # Example
+///
+/// ```
+/// use amethyst_core::{
+///     ecs::{CommandBuffer, Resources, World},
+///     transform::{HierarchyCommands, Parent, Transform},
+/// };
+///
+/// let mut world = World::default();
+/// let mut resources = Resources::default();
+/// let mut hand_transform = Transform::default();
+/// hand_transform.set_translation_x(1.0);
+/// let hand = world.push((hand_transform,));
+/// let sword = world.push((Transform::default(), Parent(hand)));
+///
+/// // Drop the sword where it is.
+/// let mut buffer = CommandBuffer::new(&world);
+/// buffer.detach(&world, sword);
+/// buffer.flush(&mut world, &mut resources);
+///
+/// let entry = world.entry(sword).unwrap();
+/// assert!(entry.get_component::<Parent>().is_err());
+/// assert_eq!(entry.get_component::<Transform>().unwrap().translation().x, 1.0);
+/// ```
+pub trait HierarchyCommands {
+    /// Removes `entity` along with all of its descendants, and removes it from the `Children` of
+    /// its parent.
+    fn despawn_recursive<W: EntityStore>(&mut self, world: &W, entity: Entity);
+
+    /// Makes `entity` a child of `new_parent`, or a root if `new_parent` is `None`, keeping its
+    /// position, rotation and scale in world space.
+    ///
+    /// # Errors
+    ///
+    /// Fails without recording anything if `new_parent` is `entity` or one of its descendants.
+    fn reparent_keep_world<W: EntityStore>(
+        &mut self,
+        world: &W,
+        entity: Entity,
+        new_parent: Option<Entity>,
+    ) -> Result<(), HierarchyError>;
+
+    /// Makes `entity` a child of `parent`, keeping its position, rotation and scale in world
+    /// space.
+    ///
+    /// # Errors
+    ///
+    /// Fails without recording anything if `parent` is `entity` or one of its descendants.
+    fn attach_keep_world<W: EntityStore>(
+        &mut self,
+        world: &W,
+        entity: Entity,
+        parent: Entity,
+    ) -> Result<(), HierarchyError>;
+
+    /// Makes `entity` a root, keeping its position, rotation and scale in world space.
+    fn detach<W: EntityStore>(&mut self, world: &W, entity: Entity);
+}
+
+impl HierarchyCommands for CommandBuffer {
+    fn despawn_recursive<W: EntityStore>(&mut self, world: &W, entity: Entity) {
+        // The `Children` are edited when the buffer is flushed, so that the removals of siblings
+        // recorded in the same buffer all apply.
+        if let Some(parent) = parent_of(world, entity) {
+            self.exec_mut(move |world, _| {
+                if let Some(children) = world
+                    .entry(parent)
+                    .and_then(|mut entry| entry.into_component_mut::<Children>().ok())
+                {
+                    children.0.retain(|e| *e != entity);
+                }
+            });
+        }
+
+        // `Children` may refer to an ancestor while the hierarchy is being edited.
+        let mut despawned = HashSet::new();
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            if despawned.insert(entity) {
+                self.remove(entity);
+                stack.extend(children_of(world, entity).into_iter().flatten());
+            }
+        }
+    }
+
+    fn reparent_keep_world<W: EntityStore>(
+        &mut self,
+        world: &W,
+        entity: Entity,
+        new_parent: Option<Entity>,
+    ) -> Result<(), HierarchyError> {
+        match new_parent {
+            Some(parent) => self.attach_keep_world(world, entity, parent),
+            None => {
+                self.detach(world, entity);
+                Ok(())
+            }
+        }
+    }
+
+    fn attach_keep_world<W: EntityStore>(
+        &mut self,
+        world: &W,
+        entity: Entity,
+        parent: Entity,
+    ) -> Result<(), HierarchyError> {
+        if parent == entity || ancestors(world, parent).any(|ancestor| ancestor == entity) {
+            return Err(HierarchyError::Cycle { entity, parent });
+        }
+        keep_world_transform(self, world, entity, world_matrix(world, parent));
+        self.add_component(entity, Parent(parent));
+        Ok(())
+    }
+
+    fn detach<W: EntityStore>(&mut self, world: &W, entity: Entity) {
+        keep_world_transform(self, world, entity, None);
+        self.remove_component::<Parent>(entity);
+    }
+}
+
+/// Sets the `Transform` of `entity` so that its world space matrix stays the same under a parent
+/// whose world space matrix is `parent_matrix`, or without parent if it is `None`.
+fn keep_world_transform<W: EntityStore>(
+    commands: &mut CommandBuffer,
+    world: &W,
+    entity: Entity,
+    parent_matrix: Option<Matrix4<f32>>,
+) {
+    if let Some(global) = world_matrix(world, entity) {
+        let local = parent_matrix
+            .and_then(|parent| parent.try_inverse())
+            .map_or(global, |inverse| inverse * global);
+        let transform = decompose(&local);
+        let is_2d = world.entry_ref(entity).map_or(false, |entry| {
+            entry.get_component::<Transform>().is_err()
+                && entry.get_component::<Transform2D>().is_ok()
+        });
+        if is_2d {
+            commands.add_component(entity, Transform2D::from(transform));
+        } else {
+            commands.add_component(entity, transform);
+        }
+    }
+}
+
+fn parent_of<W: EntityStore>(world: &W, entity: Entity) -> Option<Entity> {
+    world
+        .entry_ref(entity)
+        .ok()?
+        .get_component::<Parent>()
+        .ok()
+        .map(|parent| parent.0)
+}
+
+fn children_of<W: EntityStore>(world: &W, entity: Entity) -> Option<Vec<Entity>> {
+    world
+        .entry_ref(entity)
+        .ok()?
+        .get_component::<Children>()
+        .ok()
+        .map(|children| children.0.to_vec())
+}
+
+/// The ancestors of `entity`, stopping before an ancestor repeats.
+fn ancestors<W: EntityStore>(world: &W, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+    let mut visited = HashSet::new();
+    visited.insert(entity);
+    std::iter::successors(parent_of(world, entity), move |parent| {
+        parent_of(world, *parent)
+    })
+    .take_while(move |ancestor| visited.insert(*ancestor))
+}
+
+/// The world space matrix of `entity`, computed like the `TransformSystem` does: ancestors without
+/// a `Transform` or a `Transform2D` end the hierarchy.
+fn world_matrix<W: EntityStore>(world: &W, entity: Entity) -> Option<Matrix4<f32>> {
+    let local = |entity| local_matrix(&world.entry_ref(entity).ok()?);
+    let mut matrix = local(entity)?;
+    for ancestor in ancestors(world, entity) {
+        match local(ancestor) {
+            Some(parent) => matrix = parent * matrix,
+            None => break,
+        }
+    }
+    Some(matrix)
+}
+
+/// Splits `matrix` into a translation, a rotation and a scale.
+fn decompose(matrix: &Matrix4<f32>) -> Transform {
+    let translation = matrix.column(3).xyz();
+    let linear: Matrix3<f32> = matrix.fixed_slice::<na::U3, na::U3>(0, 0).into_owned();
+    let mut scale = Vector3::new(
+        linear.column(0).norm(),
+        linear.column(1).norm(),
+        linear.column(2).norm(),
+    );
+    // A mirroring is kept as a negative scale.
+    if linear.determinant() < 0.0 {
+        scale.x = -scale.x;
+    }
+    let rotation = if scale.iter().all(|s| s.abs() > f32::EPSILON) {
+        UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(
+            Matrix3::from_columns(&[
+                linear.column(0) / scale.x,
+                linear.column(1) / scale.y,
+                linear.column(2) / scale.z,
+            ]),
+        ))
+    } else {
+        UnitQuaternion::identity()
+    };
+    Transform::new(Translation3::from(translation), rotation, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        approx::assert_relative_eq,
+        ecs::*,
+        transform::{GlobalTransform, TransformBundle},
+    };
+
+    fn transform_world() -> (Resources, World, Dispatcher) {
+        let mut resources = Resources::default();
+        let mut world = World::default();
+        let dispatcher = DispatcherBuilder::default()
+            .add_bundle(TransformBundle)
+            .build(&mut world, &mut resources)
+            .unwrap();
+        (resources, world, dispatcher)
+    }
+
+    fn global(world: &mut World, entity: Entity) -> Matrix4<f32> {
+        world
+            .entry(entity)
+            .unwrap()
+            .into_component::<GlobalTransform>()
+            .unwrap()
+            .0
+    }
+
+    fn transform(translation: [f32; 3], angle: f32, scale: f32) -> Transform {
+        let mut transform = Transform::from(Vector3::from(translation));
+        transform.set_rotation_z_axis(angle);
+        transform.set_scale(Vector3::new(scale, scale, scale));
+        transform
+    }
+
+    /// Pushes a chain of `depth` entities under `parent`, returning the deepest one.
+    fn push_chain(world: &mut World, parent: Entity, depth: usize) -> Entity {
+        (0..depth).fold(parent, |parent, _| {
+            world.push((transform([1.0, 0.0, 0.0], 0.1, 1.0), Parent(parent)))
+        })
+    }
+
+    #[test]
+    fn despawn_recursive_removes_deep_hierarchies() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let root = world.push((Transform::default(),));
+        let branch = world.push((Transform::default(), Parent(root)));
+        let sibling = world.push((Transform::default(), Parent(root)));
+        let leaf = push_chain(&mut world, branch, 64);
+        let other_leaf = push_chain(&mut world, branch, 3);
+        dispatcher.execute(&mut world, &mut res);
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.despawn_recursive(&world, branch);
+        buffer.flush(&mut world, &mut res);
+
+        for entity in &[branch, leaf, other_leaf] {
+            assert!(world.entry(*entity).is_none());
+        }
+        assert!(world.entry(sibling).is_some());
+        let children = world
+            .entry(root)
+            .unwrap()
+            .into_component::<Children>()
+            .unwrap();
+        assert_eq!(children.0.as_slice(), &[sibling]);
+        assert_eq!(world.len(), 2);
+    }
+
+    #[test]
+    fn despawn_recursive_removes_siblings_from_the_same_buffer() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let root = world.push((Transform::default(),));
+        let first = world.push((Transform::default(), Parent(root)));
+        let second = world.push((Transform::default(), Parent(root)));
+        let kept = world.push((Transform::default(), Parent(root)));
+        dispatcher.execute(&mut world, &mut res);
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.despawn_recursive(&world, first);
+        buffer.despawn_recursive(&world, second);
+        buffer.flush(&mut world, &mut res);
+
+        let children = world
+            .entry(root)
+            .unwrap()
+            .into_component::<Children>()
+            .unwrap();
+        assert_eq!(children.0.as_slice(), &[kept]);
+        assert_eq!(world.len(), 2);
+    }
+
+    #[test]
+    fn despawn_recursive_stops_on_cycles() {
+        let mut res = Resources::default();
+        let mut world = World::default();
+        let e1 = world.push((Transform::default(),));
+        let e2 = world.push((Transform::default(), Parent(e1)));
+        let e3 = world.push((Transform::default(), Parent(e2)));
+        // Inconsistent `Children`, as while the hierarchy is being edited.
+        world
+            .entry(e1)
+            .unwrap()
+            .add_component(Children::with(&[e2]));
+        world
+            .entry(e2)
+            .unwrap()
+            .add_component(Children::with(&[e3]));
+        world
+            .entry(e3)
+            .unwrap()
+            .add_component(Children::with(&[e1]));
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.despawn_recursive(&world, e2);
+        buffer.flush(&mut world, &mut res);
+
+        assert_eq!(world.len(), 0);
+    }
+
+    #[test]
+    fn reparent_keeps_world_transform() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let e1 = world.push((transform([1.0, 2.0, 0.0], 0.5, 2.0),));
+        let e2 = world.push((transform([-3.0, 0.0, 1.0], -1.2, 0.5),));
+        let e3 = world.push((transform([0.0, 1.0, 0.0], 0.3, 1.0), Parent(e1)));
+        let e4 = world.push((transform([0.0, 0.0, 4.0], 2.0, 3.0), Parent(e1)));
+        let leaf = push_chain(&mut world, e3, 16);
+        dispatcher.execute(&mut world, &mut res);
+        let before = global(&mut world, e3);
+        let leaf_before = global(&mut world, leaf);
+
+        for new_parent in &[Some(e2), None, Some(e4)] {
+            let mut buffer = CommandBuffer::new(&world);
+            buffer.reparent_keep_world(&world, e3, *new_parent).unwrap();
+            buffer.flush(&mut world, &mut res);
+            dispatcher.execute(&mut world, &mut res);
+
+            let parent = world
+                .entry(e3)
+                .unwrap()
+                .get_component::<Parent>()
+                .ok()
+                .copied();
+            assert_eq!(parent.map(|parent| parent.0), *new_parent);
+            assert_relative_eq!(global(&mut world, e3), before, epsilon = 1e-4);
+            assert_relative_eq!(global(&mut world, leaf), leaf_before, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn attach_and_detach_keep_world_transform() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let parent = world.push((transform([5.0, 0.0, 0.0], 1.0, 2.0),));
+        let entity = world.push((transform([0.0, 3.0, 0.0], 0.0, 1.0),));
+        dispatcher.execute(&mut world, &mut res);
+        let before = global(&mut world, entity);
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.attach_keep_world(&world, entity, parent).unwrap();
+        buffer.flush(&mut world, &mut res);
+        dispatcher.execute(&mut world, &mut res);
+        assert_relative_eq!(global(&mut world, entity), before, epsilon = 1e-5);
+        let children = world
+            .entry(parent)
+            .unwrap()
+            .into_component::<Children>()
+            .unwrap();
+        assert_eq!(children.0.as_slice(), &[entity]);
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.detach(&world, entity);
+        buffer.flush(&mut world, &mut res);
+        dispatcher.execute(&mut world, &mut res);
+        assert_relative_eq!(global(&mut world, entity), before, epsilon = 1e-5);
+        assert!(world
+            .entry(entity)
+            .unwrap()
+            .get_component::<Parent>()
+            .is_err());
+    }
+
+    #[test]
+    fn attach_keeps_2d_entities_2d() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let parent = world.push((transform([5.0, 0.0, 0.0], 1.0, 2.0),));
+        let mut local = Transform2D::default();
+        local
+            .set_translation_xy(0.0, 3.0)
+            .set_rotation(0.5)
+            .set_layer(1.0);
+        let entity = world.push((local,));
+        dispatcher.execute(&mut world, &mut res);
+        let before = global(&mut world, entity);
+
+        let mut buffer = CommandBuffer::new(&world);
+        buffer.attach_keep_world(&world, entity, parent).unwrap();
+        buffer.flush(&mut world, &mut res);
+        dispatcher.execute(&mut world, &mut res);
+        assert_relative_eq!(global(&mut world, entity), before, epsilon = 1e-5);
+        assert!(world
+            .entry(entity)
+            .unwrap()
+            .get_component::<Transform>()
+            .is_err());
+    }
+
+    #[test]
+    fn attaching_to_a_descendant_is_a_cycle() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+        let root = world.push((Transform::default(),));
+        let leaf = push_chain(&mut world, root, 32);
+        dispatcher.execute(&mut world, &mut res);
+
+        let mut buffer = CommandBuffer::new(&world);
+        for parent in &[leaf, root] {
+            assert_eq!(
+                buffer.attach_keep_world(&world, root, *parent),
+                Err(HierarchyError::Cycle {
+                    entity: root,
+                    parent: *parent
+                })
+            );
+        }
+        buffer.flush(&mut world, &mut res);
+        assert!(world
+            .entry(root)
+            .unwrap()
+            .get_component::<Parent>()
+            .is_err());
+    }
+
+    #[test]
+    fn decompose_inverts_matrix() {
+        let mut local = transform([1.0, -2.0, 3.0], 0.7, 1.0);
+        local.set_scale(Vector3::new(2.0, -0.5, 3.0));
+        assert_relative_eq!(
+            decompose(&local.matrix()).matrix(),
+            local.matrix(),
+            epsilon = 1e-5
+        );
+    }
+}
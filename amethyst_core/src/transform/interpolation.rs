@@ -0,0 +1,79 @@
+//! Rendering of entities between their two last simulated poses.
+
+use super::components::{Transform, TransformInterpolation};
+use crate::ecs::{IntoQuery, World};
+
+/// Replaces the [Transform] of entities with a [`TransformInterpolation`] by the pose
+/// interpolated with `alpha`, usually [`Time::interpolation_alpha`].
+///
+/// Called by the application after the fixed updates of a frame, before its systems run, so the
+/// `TransformSystem` computes the global matrices of the interpolated poses.
+///
+/// [`Time::interpolation_alpha`]: crate::Time::interpolation_alpha
+pub fn interpolate_simulated_transforms(world: &mut World, alpha: f32) {
+    for (interpolation, transform) in
+        <(&TransformInterpolation, &mut Transform)>::query().iter_mut(world)
+    {
+        interpolation.interpolate(transform, alpha);
+    }
+}
+
+/// Writes back the simulated [Transform] of entities with a [`TransformInterpolation`]. Called
+/// by the application before running fixed updates.
+pub fn restore_simulated_transforms(world: &mut World) {
+    for (interpolation, transform) in
+        <(&TransformInterpolation, &mut Transform)>::query().iter_mut(world)
+    {
+        interpolation.restore(transform);
+    }
+}
+
+/// Records the simulated [Transform] of entities with a [`TransformInterpolation`]. Called by
+/// the application after every fixed update.
+pub fn record_simulated_transforms(world: &mut World) {
+    for (interpolation, transform) in
+        <(&mut TransformInterpolation, &Transform)>::query().iter_mut(world)
+    {
+        interpolation.record(transform);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_fixed_updates() {
+        let mut world = World::default();
+        let entity = world.push((Transform::default(), TransformInterpolation::default()));
+        record_simulated_transforms(&mut world);
+
+        // Simulate a fixed update moving the entity.
+        restore_simulated_transforms(&mut world);
+        world
+            .entry(entity)
+            .unwrap()
+            .get_component_mut::<Transform>()
+            .unwrap()
+            .set_translation_x(4.0);
+        record_simulated_transforms(&mut world);
+
+        interpolate_simulated_transforms(&mut world, 0.5);
+        let rendered = *world
+            .entry(entity)
+            .unwrap()
+            .get_component::<Transform>()
+            .unwrap()
+            .translation();
+        assert!((rendered.x - 2.0).abs() < 1e-5, "{}", rendered.x);
+
+        restore_simulated_transforms(&mut world);
+        let simulated = *world
+            .entry(entity)
+            .unwrap()
+            .get_component::<Transform>()
+            .unwrap()
+            .translation();
+        assert!((simulated.x - 4.0).abs() < f32::EPSILON);
+    }
+}
@@ -1,13 +1,21 @@
 //! `amethyst` transform ecs module
 
 pub use self::{
-    bundle::TransformBundle, components::*,
+    bundle::{TransformBundle, PARENT_UPDATE_SYSTEM, TRANSFORM_SET, TRANSFORM_SYSTEM},
+    components::*,
+    hierarchy_commands::{HierarchyCommands, HierarchyError},
+    interpolation::{
+        interpolate_simulated_transforms, record_simulated_transforms, restore_simulated_transforms,
+    },
     missing_previous_parent_system::MissingPreviousParentSystem,
-    parent_update_system::ParentUpdateSystem, transform_system::TransformSystem,
+    parent_update_system::ParentUpdateSystem,
+    transform_system::TransformSystem,
 };
 
 pub mod bundle;
 pub mod components;
+pub mod hierarchy_commands;
+pub mod interpolation;
 pub mod missing_previous_parent_system;
 pub mod parent_update_system;
 pub mod transform_system;
@@ -4,13 +4,19 @@ use std::collections::HashMap;
 
 use smallvec::SmallVec;
 
-use super::components::{Children, Parent, PreviousParent, Transform};
+use super::components::{Children, Parent, PreviousParent, Transform, Transform2D};
 use crate::ecs::{
     component, maybe_changed, Entity, EntityStore, IntoQuery, ParallelRunnable, System,
     SystemBuilder, Write,
 };
 
 /// System that generates [Children] components for entities that are targeted by [Parent] component.
+///
+/// The `Children` of an entity are updated in the same frame as the `Parent` of its children,
+/// once the command buffers are flushed. The [`PreviousParent`] of newly parented entities is
+/// added by this system, so the [`MissingPreviousParentSystem`] doesn't need to run before it.
+///
+/// [`MissingPreviousParentSystem`]: super::MissingPreviousParentSystem
 #[derive(Debug)]
 pub struct ParentUpdateSystem;
 
@@ -22,11 +28,17 @@ impl System for ParentUpdateSystem {
                 .with_query(<(Entity, &PreviousParent)>::query().filter(!component::<Parent>()))
                 // Entities with a changed `Parent`
                 .with_query(
-                    <(Entity, &Parent, &mut PreviousParent)>::query()
-                        .filter(component::<Transform>() & maybe_changed::<Parent>()),
+                    <(Entity, &Parent, Option<&mut PreviousParent>)>::query().filter(
+                        (component::<Transform>() | component::<Transform2D>())
+                            & maybe_changed::<Parent>(),
+                    ),
+                )
+                // Deleted Parents (ie Entities with `Children` and without a `Transform` or a
+                // `Transform2D`).
+                .with_query(
+                    <(Entity, &Children)>::query()
+                        .filter(!component::<Transform>() & !component::<Transform2D>()),
                 )
-                // Deleted Parents (ie Entities with `Children` and without a `Transform`).
-                .with_query(<(Entity, &Children)>::query().filter(!component::<Transform>()))
                 .write_component::<Children>()
                 .build(move |commands, world, _resource, queries| {
                     // Entities with a missing `Parent` (ie. ones that have a `PreviousParent`), remove
@@ -47,18 +59,21 @@ impl System for ParentUpdateSystem {
                                 previous_parent_children.0.retain(|e| e != entity);
                             }
                         }
+                        commands.remove_component::<PreviousParent>(*entity);
                     }
 
                     // Tracks all newly created `Children` Components this frame.
                     let mut children_additions =
                         HashMap::<Entity, SmallVec<[Entity; 8]>>::with_capacity(16);
 
-                    // Entities with a changed Parent (that also have a PreviousParent, even if None)
+                    // Entities with a changed Parent (a missing PreviousParent is the same as None)
                     for (entity, parent, previous_parent) in queries.1.iter_mut(right) {
                         log::trace!("Parent changed for {:?}", entity);
 
                         // If the `PreviousParent` is not None.
-                        if let Some(previous_parent_entity) = previous_parent.0 {
+                        if let Some(previous_parent_entity) =
+                            previous_parent.as_ref().and_then(|previous| previous.0)
+                        {
                             // New and previous point to the same Entity, carry on, nothing to see here.
                             if previous_parent_entity == parent.0 {
                                 log::trace!(" > But the previous parent is the same, ignoring...");
@@ -77,7 +92,12 @@ impl System for ParentUpdateSystem {
                         }
 
                         // Set `PreviousParent = Parent`.
-                        *previous_parent = PreviousParent(Some(parent.0));
+                        match previous_parent {
+                            Some(previous_parent) => {
+                                *previous_parent = PreviousParent(Some(parent.0))
+                            }
+                            None => commands.add_component(*entity, PreviousParent(Some(parent.0))),
+                        }
 
                         // Add to the parent's `Children` (either the real component, or
                         // `children_additions`).
@@ -106,9 +126,9 @@ impl System for ParentUpdateSystem {
                         }
                     }
 
-                    // Deleted `Parents` (ie. Entities with a `Children` but no `Transform`).
+                    // Deleted `Parents` (ie. Entities with a `Children` but no local transform).
                     for (entity, children) in queries.2.iter(world) {
-                        log::trace!("The entity {:?} doesn't have a Transform", entity);
+                        log::trace!("The entity {:?} doesn't have a local transform", entity);
                         if children_additions.remove(entity).is_none() {
                             log::trace!(" > It needs to be remove from the ECS.");
                             for child_entity in children.0.iter() {
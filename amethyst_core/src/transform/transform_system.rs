@@ -1,12 +1,28 @@
 //! System that updates global transform matrices based on hierarchy relations.
 
-use super::components::{Parent, Transform};
-use crate::ecs::{
-    component, maybe_changed, Entity, EntityStore, IntoQuery, ParallelRunnable, System,
-    SystemBuilder,
+use std::collections::HashSet;
+
+use smallvec::SmallVec;
+
+use super::components::{Children, GlobalTransform, Parent, Transform, Transform2D};
+use crate::{
+    ecs::{
+        component, maybe_changed, world::EntryRef, CommandBuffer, Entity, EntityStore, IntoQuery,
+        ParallelRunnable, SubWorld, System, SystemBuilder,
+    },
+    math::{self as na, Matrix4},
 };
 
-/// System that updates global transform matrices based on hierarchy relations.
+/// System that updates the [`GlobalTransform`] of entities based on hierarchy relations.
+///
+/// Entities with a [`Transform`] or a [`Transform2D`] get a `GlobalTransform`, which is only
+/// recomputed when the local transform of the entity or of one of its ancestors changes, or when
+/// its [`Parent`] changes. Changes are detected per chunk of entities by legion, so static
+/// entities cost nothing. 2D and 3D entities can share a hierarchy.
+///
+/// Descendants are found through the [`Children`] maintained by the
+/// [`ParentUpdateSystem`](super::ParentUpdateSystem), whose command buffer must be flushed before
+/// this system runs, as done by the [`TransformBundle`](super::TransformBundle).
 #[derive(Debug)]
 pub struct TransformSystem;
 
@@ -14,73 +30,55 @@ impl System for TransformSystem {
     fn build(self) -> Box<dyn ParallelRunnable> {
         Box::new(
             SystemBuilder::new("TransformSystem")
-                // Entities at the hierarchy root (no parent component)
-                .with_query(
-                    <(Entity, &mut Transform)>::query()
-                        .filter(maybe_changed::<Transform>() & !component::<Parent>()),
-                )
-                // Entities that are children of some entity
-                .with_query(
-                    <(Entity, &mut Transform)>::query()
-                        .filter(maybe_changed::<Transform>() & component::<Parent>()),
-                )
-                .with_query(<(Entity, &Parent)>::query())
-                .write_component::<Transform>()
+                // Entities with a changed `Transform`
+                .with_query(<Entity>::query().filter(maybe_changed::<Transform>()))
+                // Entities with a changed `Transform2D`
+                .with_query(<Entity>::query().filter(maybe_changed::<Transform2D>()))
+                // Entities with a changed `Parent`
+                .with_query(<Entity>::query().filter(
+                    (component::<Transform>() | component::<Transform2D>())
+                        & maybe_changed::<Parent>(),
+                ))
+                // Entities without a `GlobalTransform` yet
+                .with_query(<Entity>::query().filter(
+                    (component::<Transform>() | component::<Transform2D>())
+                        & !component::<GlobalTransform>(),
+                ))
+                .read_component::<Transform>()
+                .read_component::<Transform2D>()
+                .read_component::<Parent>()
+                .read_component::<Children>()
+                .write_component::<GlobalTransform>()
                 .build(
-                    move |_commands,
+                    move |commands,
                           world,
                           _resource,
-                          (query_root, query_children, query_parent)| {
-                        // Update global transform for entities that are root of the hierarchy
-                        for (entity, transform) in query_root.iter_mut(world) {
-                            transform.global_matrix = transform.matrix();
-                            debug_assert!(
-                                transform.is_finite(),
-                                "Entity {:?} had a non-finite `Transform` {:?}",
-                                entity,
-                                transform
-                            );
-                        }
-
-                        // Update parent transforms for entities in the hierarchy
-                        // Ideally we would check if the linked parent entity exists before
-                        // accessing it's Transform component, but as the parent_update_system panics
-                        // in both cases it is ok to just unwrap/expect here.
-                        let (left, mut right) = world.split_for_query(query_parent);
-                        for (entity, parent) in query_parent.iter(&left) {
-                            let parent_has_transform = right
-                                .entry_ref(parent.0)
-                                .expect("Invalid entity in Parent component")
-                                .into_component::<Transform>()
-                                .is_ok();
-
-                            if parent_has_transform {
-                                let parent_matrix = right
-                                    .entry_ref(parent.0)
-                                    .expect("Invalid entity in Parent component")
-                                    .into_component::<Transform>()
-                                    .unwrap()
-                                    .global_matrix;
-
-                                if let Some(transform) = right
-                                    .entry_mut(*entity)
-                                    .ok()
-                                    .and_then(|entry| entry.into_component_mut::<Transform>().ok())
-                                {
-                                    transform.parent_matrix = parent_matrix;
-                                }
-                            }
+                          (query_changed, query_changed_2d, query_reparented, query_missing)| {
+                        let dirty: HashSet<Entity> = query_changed
+                            .iter(world)
+                            .chain(query_changed_2d.iter(world))
+                            .chain(query_reparented.iter(world))
+                            .chain(query_missing.iter(world))
+                            .copied()
+                            .collect();
+                        if dirty.is_empty() {
+                            return;
                         }
 
-                        // Update global transform for entities that are children of some entity
-                        for (entity, transform) in query_children.iter_mut(world) {
-                            transform.global_matrix = transform.parent_matrix * transform.matrix();
-                            debug_assert!(
-                                transform.is_finite(),
-                                "Entity {:?} had a non-finite `Transform` {:?}",
-                                entity,
-                                transform
-                            );
+                        // Only the topmost dirty entities are updated, their descendants are
+                        // updated along with them.
+                        let roots: Vec<Entity> = dirty
+                            .iter()
+                            .copied()
+                            .filter(|entity| {
+                                !ancestors(world, *entity).any(|ancestor| dirty.contains(&ancestor))
+                            })
+                            .collect();
+                        for root in roots {
+                            let parent_matrix = parent_of(world, root)
+                                .and_then(|parent| global_matrix(world, parent))
+                                .unwrap_or_else(na::one);
+                            update_hierarchy(commands, world, root, parent_matrix);
                         }
                     },
                 ),
@@ -88,12 +86,96 @@ impl System for TransformSystem {
     }
 }
 
+/// The parent of `entity`, if it has one.
+fn parent_of(world: &SubWorld<'_>, entity: Entity) -> Option<Entity> {
+    world
+        .entry_ref(entity)
+        .ok()?
+        .get_component::<Parent>()
+        .ok()
+        .map(|parent| parent.0)
+}
+
+/// The ancestors of `entity`, from its parent to the root of its hierarchy.
+fn ancestors<'a>(world: &'a SubWorld<'_>, entity: Entity) -> impl Iterator<Item = Entity> + 'a {
+    std::iter::successors(parent_of(world, entity), move |parent| {
+        parent_of(world, *parent)
+    })
+}
+
+fn global_matrix(world: &SubWorld<'_>, entity: Entity) -> Option<Matrix4<f32>> {
+    world
+        .entry_ref(entity)
+        .ok()?
+        .get_component::<GlobalTransform>()
+        .ok()
+        .map(|global| global.0)
+}
+
+/// The local matrix of an entity, from its `Transform`, or else from its `Transform2D`.
+pub(super) fn local_matrix(entry: &EntryRef<'_>) -> Option<Matrix4<f32>> {
+    match entry.get_component::<Transform>() {
+        Ok(transform) => Some(transform.matrix()),
+        Err(_) => {
+            entry
+                .get_component::<Transform2D>()
+                .ok()
+                .map(Transform2D::matrix)
+        }
+    }
+}
+
+/// Updates the `GlobalTransform` of `root` and of its descendants.
+fn update_hierarchy(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld<'_>,
+    root: Entity,
+    parent_matrix: Matrix4<f32>,
+) {
+    let mut stack = vec![(root, parent_matrix)];
+    while let Some((entity, parent_matrix)) = stack.pop() {
+        let (global, children) = {
+            let entry = match world.entry_ref(entity) {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+            // Children without a local transform are not part of the transform hierarchy.
+            let local = match local_matrix(&entry) {
+                Some(local) => local,
+                None => continue,
+            };
+            let global = GlobalTransform(parent_matrix * local);
+            debug_assert!(
+                global.is_finite(),
+                "Entity {:?} had a non-finite local transform {:?}",
+                entity,
+                local
+            );
+            let children: SmallVec<[Entity; 8]> = entry
+                .get_component::<Children>()
+                .map(|children| children.0.clone())
+                .unwrap_or_default();
+            (global, children)
+        };
+
+        match world
+            .entry_mut(entity)
+            .ok()
+            .and_then(|entry| entry.into_component_mut::<GlobalTransform>().ok())
+        {
+            Some(current) => *current = global,
+            None => commands.add_component(entity, global),
+        }
+        stack.extend(children.into_iter().map(|child| (child, global.0)));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         ecs::*,
-        math::{Matrix4, Quaternion, Unit, Vector3},
-        transform::{Parent, Transform, TransformBundle},
+        math::{Matrix4, Quaternion, Unit, Vector2, Vector3},
+        transform::{Children, GlobalTransform, Parent, Transform, Transform2D, TransformBundle},
     };
 
     // If this works, then all other tests should work.
@@ -141,13 +223,10 @@ mod tests {
         let transform = world
             .entry(e1)
             .unwrap()
-            .into_component::<Transform>()
+            .into_component::<GlobalTransform>()
             .unwrap();
 
-        assert_eq!(
-            transform.global_matrix(),
-            Transform::default().global_matrix()
-        );
+        assert_eq!(*transform, GlobalTransform::default());
     }
 
     // Basic sanity check for Transform's local matrix -> global matrix, no parent relationships
@@ -168,9 +247,9 @@ mod tests {
         let transform = world
             .entry(e1)
             .unwrap()
-            .into_component::<Transform>()
+            .into_component::<GlobalTransform>()
             .unwrap();
-        let a1 = transform.global_matrix();
+        let a1 = transform.matrix();
         let a2 = local.matrix();
         assert_eq!(*a1, a2);
     }
@@ -198,34 +277,32 @@ mod tests {
 
         let e3 = world.push((local3, Parent(e2)));
 
-        // Current transform system is inefficient and needs multiple passed for deep hierarchy
-        dispatcher.execute(&mut world, &mut res);
         dispatcher.execute(&mut world, &mut res);
 
-        let e1_transform = *world
+        let e1_global = *world
             .entry(e1)
             .unwrap()
-            .into_component::<Transform>()
+            .into_component::<GlobalTransform>()
             .unwrap();
-        let a1 = e1_transform.global_matrix();
+        let a1 = e1_global.matrix();
         let a2 = local1.matrix();
         assert_eq!(*a1, a2);
 
-        let e2_transform = *world
+        let e2_global = *world
             .entry(e2)
             .unwrap()
-            .into_component::<Transform>()
+            .into_component::<GlobalTransform>()
             .unwrap();
-        let a3 = e2_transform.global_matrix();
+        let a3 = e2_global.matrix();
         let a4 = together(*a1, local2.matrix());
         assert_eq!(*a3, a4);
 
-        let e3_transform = *world
+        let e3_global = *world
             .entry(e3)
             .unwrap()
-            .into_component::<Transform>()
+            .into_component::<GlobalTransform>()
             .unwrap();
-        let a5 = e3_transform.global_matrix();
+        let a5 = e3_global.matrix();
         let a6 = together(*a3, local3.matrix());
         assert_eq!(*a5, a6);
     }
@@ -258,50 +335,175 @@ mod tests {
         world.entry(e2).unwrap().add_component(Parent(e1));
         world.entry(e3).unwrap().add_component(Parent(e2));
 
-        // Current transform system is inefficient and needs multiple passed for deep hierarchy
-        dispatcher.execute(&mut world, &mut res);
         dispatcher.execute(&mut world, &mut res);
 
         let global_matrix1 = {
-            let e1_transform = world
+            let e1_global = world
                 .entry(e1)
                 .unwrap()
-                .into_component::<Transform>()
+                .into_component::<GlobalTransform>()
                 .unwrap();
 
             // First entity (top level parent)
-            let a1 = *e1_transform.global_matrix();
+            let a1 = *e1_global.matrix();
             let a2 = local1.matrix();
             assert_eq!(a1, a2);
             a1
         };
 
         let global_matrix2 = {
-            let e2_transform = world
+            let e2_global = world
                 .entry(e2)
                 .unwrap()
-                .into_component::<Transform>()
+                .into_component::<GlobalTransform>()
                 .unwrap();
 
-            let a1 = *e2_transform.global_matrix();
+            let a1 = *e2_global.matrix();
             let a2 = together(global_matrix1, local2.matrix());
             assert_eq!(a1, a2);
             a1
         };
 
         {
-            let e3_transform = world
+            let e3_global = world
                 .entry(e3)
                 .unwrap()
-                .into_component::<Transform>()
+                .into_component::<GlobalTransform>()
                 .unwrap();
 
-            let a1 = e3_transform.global_matrix();
+            let a1 = e3_global.matrix();
             let a2 = together(global_matrix2, local3.matrix());
             assert_eq!(*a1, a2);
         };
     }
 
+    fn global(world: &mut World, entity: Entity) -> Matrix4<f32> {
+        world
+            .entry(entity)
+            .unwrap()
+            .into_component::<GlobalTransform>()
+            .unwrap()
+            .0
+    }
+
+    // Unchanged hierarchies are not recomputed, changes propagate to the descendants.
+    #[test]
+    fn recomputes_changed_hierarchies_only() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+
+        let parent = world.push((Transform::default(),));
+        let mut local = Transform::default();
+        local.set_translation_xyz(0.0, 1.0, 0.0);
+        let child = world.push((local, Parent(parent)));
+        let static_entity = world.push((Transform::default(),));
+
+        // Settle the structural changes made by the transform systems.
+        for _ in 0..3 {
+            dispatcher.execute(&mut world, &mut res);
+        }
+        for entity in &[child, static_entity] {
+            world
+                .entry(*entity)
+                .unwrap()
+                .get_component_mut::<GlobalTransform>()
+                .unwrap()
+                .0 = Matrix4::zeros();
+        }
+
+        dispatcher.execute(&mut world, &mut res);
+        assert_eq!(global(&mut world, child), Matrix4::zeros());
+
+        let mut moved = Transform::default();
+        moved.set_translation_xyz(2.0, 0.0, 0.0);
+        *world
+            .entry(parent)
+            .unwrap()
+            .get_component_mut::<Transform>()
+            .unwrap() = moved;
+        dispatcher.execute(&mut world, &mut res);
+
+        assert_eq!(global(&mut world, parent), moved.matrix());
+        assert_eq!(
+            global(&mut world, child),
+            together(moved.matrix(), local.matrix())
+        );
+        assert_eq!(global(&mut world, static_entity), Matrix4::zeros());
+    }
+
+    // Reparenting and detaching update the global matrix in the same frame.
+    #[test]
+    fn follows_hierarchy_changes() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+
+        let mut local1 = Transform::default();
+        local1.set_translation_xyz(1.0, 0.0, 0.0);
+        let e1 = world.push((local1,));
+        let mut local2 = Transform::default();
+        local2.set_translation_xyz(0.0, 2.0, 0.0);
+        let e2 = world.push((local2,));
+        let mut local3 = Transform::default();
+        local3.set_translation_xyz(0.0, 0.0, 3.0);
+        let e3 = world.push((local3, Parent(e1)));
+        dispatcher.execute(&mut world, &mut res);
+        assert_eq!(
+            global(&mut world, e3),
+            together(local1.matrix(), local3.matrix())
+        );
+
+        world.entry(e3).unwrap().add_component(Parent(e2));
+        dispatcher.execute(&mut world, &mut res);
+        assert_eq!(
+            global(&mut world, e3),
+            together(local2.matrix(), local3.matrix())
+        );
+
+        world.entry(e3).unwrap().remove_component::<Parent>();
+        dispatcher.execute(&mut world, &mut res);
+        assert_eq!(global(&mut world, e3), local3.matrix());
+        assert!(world
+            .entry(e2)
+            .unwrap()
+            .get_component::<Children>()
+            .unwrap()
+            .0
+            .is_empty());
+    }
+
+    // 2D and 3D entities share a hierarchy, and changes of a `Transform2D` propagate.
+    #[test]
+    fn mixed_2d_and_3d_hierarchy() {
+        let (mut res, mut world, mut dispatcher) = transform_world();
+
+        let mut local1 = Transform2D::from(Vector2::new(1.0, 2.0));
+        local1.set_rotation(0.5).set_layer(3.0);
+        let e1 = world.push((local1,));
+        let mut local2 = Transform::default();
+        local2.set_translation_xyz(0.0, 1.0, 0.0);
+        let e2 = world.push((local2, Parent(e1)));
+        let mut local3 = Transform2D::default();
+        local3.set_scale(Vector2::new(2.0, 2.0));
+        let e3 = world.push((local3, Parent(e2)));
+        dispatcher.execute(&mut world, &mut res);
+
+        assert_eq!(global(&mut world, e1), local1.matrix());
+        assert_eq!(
+            global(&mut world, e3),
+            together(together(local1.matrix(), local2.matrix()), local3.matrix())
+        );
+
+        local1.rotate(0.5);
+        *world
+            .entry(e1)
+            .unwrap()
+            .get_component_mut::<Transform2D>()
+            .unwrap() = local1;
+        dispatcher.execute(&mut world, &mut res);
+        assert_eq!(
+            global(&mut world, e3),
+            together(together(local1.matrix(), local2.matrix()), local3.matrix())
+        );
+    }
+
     #[test]
     #[should_panic]
     #[cfg(debug_assertions)]
@@ -9,7 +9,7 @@ use amethyst_assets::{
     distill_importer::{Error, ImportOp, ImportedAsset, Importer, ImporterValue},
     make_handle,
     prefab::{legion_prefab, Prefab},
-    AssetUuid,
+    run_build_steps, AssetUuid,
 };
 use amethyst_core::{
     ecs::{Entity, World},
@@ -70,7 +70,7 @@ pub struct GltfImporter;
 
 impl Importer for GltfImporter {
     fn version_static() -> u32 {
-        1
+        2
     }
 
     fn version(&self) -> u32 {
@@ -123,7 +123,7 @@ impl Importer for GltfImporter {
         let mut skin_map = HashMap::new();
         let mut node_map = HashMap::new();
 
-        scene.nodes().into_iter().for_each(|node| {
+        for node in scene.nodes() {
             let mut node_assets = load_node(
                 &node,
                 &mut world,
@@ -135,9 +135,9 @@ impl Importer for GltfImporter {
                 &mut node_map,
                 &mut skin_map,
                 None,
-            );
+            )?;
             asset_accumulator.append(&mut node_assets);
-        });
+        }
 
         // load skins
         for (entity, skin_info) in skin_map {
@@ -161,7 +161,8 @@ impl Importer for GltfImporter {
         }
 
         let legion_prefab = legion_prefab::Prefab::new(world);
-        let scene_prefab = Prefab::new(legion_prefab);
+        let scene_prefab = run_build_steps(Prefab::new(legion_prefab), &options.build_steps)
+            .map_err(|e| distill_importer::Error::Boxed(e.into_error()))?;
 
         asset_accumulator.push(ImportedAsset {
             id: state
@@ -195,7 +196,7 @@ fn load_node(
     node_map: &mut HashMap<usize, Entity>,
     skin_map: &mut HashMap<Entity, SkinInfo>,
     parent_bounding_box: Option<&mut GltfNodeExtent>,
-) -> Vec<ImportedAsset> {
+) -> Result<Vec<ImportedAsset>, Error> {
     let current_node_entity = world.push(());
     node_map.insert(node.index(), current_node_entity);
     let mut imported_assets = Vec::new();
@@ -270,7 +271,7 @@ fn load_node(
                         .entry(format!("{}_{}", name, 0))
                         .or_insert_with(|| op.new_asset_uuid());
 
-                    let mesh_data: MeshData = mesh.into();
+                    let mesh_data = build_mesh(mesh.into(), options)?;
                     imported_assets.push(ImportedAsset {
                         id: mesh_asset_id,
                         search_tags: vec![],
@@ -322,7 +323,7 @@ fn load_node(
                         .entry(format!("{}_{}", name, primitive_index))
                         .or_insert_with(|| op.new_asset_uuid());
 
-                    let mesh_data: MeshData = mesh.into();
+                    let mesh_data = build_mesh(mesh.into(), options)?;
                     imported_assets.push(ImportedAsset {
                         id: mesh_asset_id,
                         search_tags: vec![],
@@ -371,7 +372,7 @@ fn load_node(
             node_map,
             skin_map,
             Some(&mut bounding_box),
-        );
+        )?;
         imported_assets.append(&mut child_assets);
     }
 
@@ -392,7 +393,12 @@ fn load_node(
         skin_map.insert(current_node_entity, skin);
     }
 
-    imported_assets
+    Ok(imported_assets)
+}
+
+fn build_mesh(mesh_data: MeshData, options: &GltfSceneOptions) -> Result<MeshData, Error> {
+    run_build_steps(mesh_data, &options.mesh_build_steps)
+        .map_err(|e| distill_importer::Error::Boxed(e.into_error()))
 }
 
 fn load_light(node: &Node<'_>) -> Option<Light> {
@@ -21,6 +21,7 @@
 use amethyst_animation::{Animation, Joint};
 use amethyst_assets::{
     inventory, prefab::register_component_type, register_asset_type, AssetProcessorSystem,
+    BuildStepConfig,
 };
 use amethyst_core::Transform;
 use derivative::Derivative;
@@ -80,4 +81,8 @@ pub struct GltfSceneOptions {
     /// Load the given scene index, if not supplied will either load the default scene (if set),
     /// or the first scene (only if there is only one scene, otherwise an `Error` will be returned).
     pub scene_index: Option<usize>,
+    /// The build steps to run on the `MeshData` of each mesh primitive, in order.
+    pub mesh_build_steps: Vec<BuildStepConfig>,
+    /// The build steps to run on the `Prefab` of the scene, in order.
+    pub build_steps: Vec<BuildStepConfig>,
 }
@@ -1,22 +1,23 @@
 use amethyst_core::{
     math::{convert, Matrix4, Translation3, UnitQuaternion},
-    transform::Transform,
+    transform::{GlobalTransform, Transform},
 };
 use criterion::{criterion_group, criterion_main, Criterion};
 
 // Our world-space is +Y Up, +X Right and -Z Away
 // Current render target is +Y Down, +X Right and +Z Away
-fn setup() -> Transform {
+fn setup() -> GlobalTransform {
     // Setup common inputs for most of the tests.
     //
     // Sets up a test camera is positioned at (0,0,3) in world space.
     // A camera without rotation is pointing in the (0,0,-1) direction.
-    Transform::new(
+    let transform = Transform::new(
         Translation3::new(0.0, 0.0, 3.0),
         // Apply _no_ rotation
         UnitQuaternion::identity(),
         [1.0, 1.0, 1.0].into(),
-    )
+    );
+    GlobalTransform::from(transform.matrix())
 }
 
 pub fn transform_global_view_matrix_1000(b: &mut Criterion) {
@@ -26,7 +27,7 @@ pub fn transform_global_view_matrix_1000(b: &mut Criterion) {
         b.iter(|| {
             for _ in 0..1000 {
                 let _: [[f32; 4]; 4] =
-                    convert::<_, Matrix4<f32>>(transform.global_view_matrix()).into();
+                    convert::<_, Matrix4<f32>>(transform.view_matrix()).into();
             }
         });
     });
@@ -40,7 +41,7 @@ pub fn manual_inverse_global_matrix_1000(b: &mut Criterion) {
             for _ in 0..1000 {
                 let _: [[f32; 4]; 4] = convert::<_, Matrix4<f32>>(
                     transform
-                        .global_matrix()
+                        .matrix()
                         .try_inverse()
                         .expect("Unable to get inverse of camera transform"),
                 )
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 
 use amethyst_assets::{register_asset_type, AssetProcessorSystem, AssetStorage};
-use amethyst_core::ecs::{DispatcherBuilder, Resources, SystemBundle, World};
+use amethyst_core::ecs::{DispatcherBuilder, Resources, SystemBundle, SystemOrder, World};
 use amethyst_error::{format_err, Error};
 use rendy::init::Rendy;
 
@@ -27,6 +27,9 @@ use crate::{
     types::{Backend, DefaultBackend, Mesh, Texture},
 };
 
+/// Label of the thread local function rendering the frame, added by [`RenderingBundle`].
+pub const RENDER_SYSTEM: &str = "render";
+
 /// A bundle of systems used for rendering using `Rendy` render graph.
 ///
 /// Provides a mechanism for registering rendering plugins.
@@ -102,7 +105,10 @@ impl<B: Backend> SystemBundle for RenderingBundle<B> {
             },
         });
 
-        builder.add_thread_local_fn(render::<B, PluggableRenderGraphCreator<B>>);
+        builder.add_thread_local_fn_ordered(
+            render::<B, PluggableRenderGraphCreator<B>>,
+            SystemOrder::new().label(RENDER_SYSTEM),
+        );
 
         Ok(())
     }
@@ -10,9 +10,9 @@ use amethyst_assets::{
 };
 use amethyst_core::{
     ecs::Entity,
-    geometry::Ray,
+    geometry::{Frustum, Ray},
     math::{Matrix4, Point2, Point3, Vector2},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use serde::{de, de::SeqAccess, ser::SerializeSeq};
 use type_uuid::TypeUuid;
@@ -220,12 +220,12 @@ impl Camera {
         &self,
         screen_position: Point2<f32>,
         screen_diagonal: Vector2<f32>,
-        camera_transform: &Transform,
+        camera_transform: &GlobalTransform,
     ) -> Ray<f32> {
         let screen_x = 2.0 * screen_position.x / screen_diagonal.x - 1.0;
         let screen_y = 2.0 * screen_position.y / screen_diagonal.y - 1.0;
 
-        let matrix = camera_transform.global_matrix() * self.inverse;
+        let matrix = camera_transform.matrix() * self.inverse;
 
         let near = Point3::new(screen_x, screen_y, 1.0);
         // The constraint on far is: 0.0 < far < 1.0. We arbitrarily chose 0.5 - maybe there is a better value?
@@ -240,6 +240,12 @@ impl Camera {
         }
     }
 
+    /// Returns the `Frustum` seen by the camera in world space, to cull what it can't see.
+    #[must_use]
+    pub fn frustum(&self, camera_transform: &GlobalTransform) -> Frustum {
+        Frustum::new(self.matrix * camera_transform.view_matrix())
+    }
+
     /// Transforms the provided (X, Y, Z) screen coordinate into world coordinates.
     /// This method fires a ray from the camera in its view direction, and returns the Point at `screen_position.z`
     /// world space distance from the camera origin.
@@ -248,7 +254,7 @@ impl Camera {
         &self,
         screen_position: Point3<f32>,
         screen_diagonal: Vector2<f32>,
-        camera_transform: &Transform,
+        camera_transform: &GlobalTransform,
     ) -> Point3<f32> {
         self.screen_ray(screen_position.xy(), screen_diagonal, camera_transform)
             .at_distance(screen_position.z)
@@ -263,9 +269,9 @@ impl Camera {
         &self,
         world_position: Point3<f32>,
         screen_diagonal: Vector2<f32>,
-        camera_transform: &Transform,
+        camera_transform: &GlobalTransform,
     ) -> Point2<f32> {
-        let transformation_matrix = camera_transform.global_matrix().try_inverse().unwrap();
+        let transformation_matrix = camera_transform.matrix().try_inverse().unwrap();
         let screen_pos = (self.matrix * transformation_matrix).transform_point(&world_position);
 
         Point2::new(
@@ -299,6 +305,7 @@ mod tests {
     //! Current render target is +Y Down, +X Right, +Z Away
 
     use amethyst_core::{
+        geometry::{Intersects, Sphere},
         math::{convert, Isometry3, Matrix4, Point3, Translation3, UnitQuaternion, Vector3},
         transform::Transform,
     };
@@ -313,7 +320,7 @@ mod tests {
         let diagonal = Vector2::new(1024.0, 768.0);
 
         let camera = Camera::standard_3d(diagonal.x, diagonal.y);
-        let mut transform = Transform::default();
+        let transform = GlobalTransform::default();
 
         let center_screen = Point3::new(diagonal.x / 2.0, diagonal.y / 2.0, 0.0);
         let top_left = Point3::new(0.0, 0.0, 0.0);
@@ -336,9 +343,8 @@ mod tests {
             Point3::new(0.096_225_046, -0.072_168_78, -0.125)
         );
 
-        transform.set_translation_x(100.0);
-        transform.set_translation_y(100.0);
-        transform.copy_local_to_global();
+        let transform =
+            GlobalTransform::from(Matrix4::new_translation(&Vector3::new(100.0, 100.0, 0.0)));
         assert_ulps_eq!(
             camera.screen_to_world_point(center_screen, diagonal, &transform),
             Point3::new(100.0, 100.0, -0.125)
@@ -350,7 +356,7 @@ mod tests {
         let diagonal = Vector2::new(1024.0, 768.0);
 
         let camera = Camera::standard_2d(diagonal.x, diagonal.y);
-        let mut transform = Transform::default();
+        let transform = GlobalTransform::default();
 
         let center_screen = Point3::new(diagonal.x / 2.0, diagonal.y / 2.0, 0.0);
         let top_left = Point3::new(0.0, 0.0, 0.0);
@@ -371,9 +377,8 @@ mod tests {
             Point3::new(diagonal.x / 2.0, -diagonal.y / 2.0, -0.125)
         );
 
-        transform.set_translation_x(100.0);
-        transform.set_translation_y(100.0);
-        transform.copy_local_to_global();
+        let transform =
+            GlobalTransform::from(Matrix4::new_translation(&Vector3::new(100.0, 100.0, 0.0)));
         assert_ulps_eq!(
             camera.screen_to_world_point(center_screen, diagonal, &transform),
             Point3::new(100.0, 100.0, -0.125)
@@ -385,7 +390,7 @@ mod tests {
         let diagonal = Vector2::new(1024.0, 768.0);
 
         let ortho = Camera::standard_2d(diagonal.x, diagonal.y);
-        let transform = Transform::default();
+        let transform = GlobalTransform::default();
 
         let center_screen = Point2::new(diagonal.x / 2.0, diagonal.y / 2.0);
         let top_left = Point2::new(0.0, 0.0);
@@ -643,12 +648,12 @@ mod tests {
         let znear = 0.125;
         let camera = Camera::perspective(aspect, fov, znear);
 
-        let mut camera_transform: Transform = Transform::new(
+        let camera_transform: Transform = Transform::new(
             Translation3::new(0.0, 0.0, 3.0),
             UnitQuaternion::identity(),
             [1.0, 1.0, 1.0].into(),
         );
-        camera_transform.copy_local_to_global();
+        let camera_transform = GlobalTransform::from(camera_transform.matrix());
 
         let cursor_pos = Point2::new(width, height);
         let screen_diag = Vector2::new(width, height);
@@ -664,4 +669,23 @@ mod tests {
         assert_ulps_eq!(ray.origin, expected_ray.origin);
         assert_ulps_eq!(ray.direction, expected_ray.direction);
     }
+
+    #[test]
+    fn frustum_and_picking() {
+        let diagonal = Vector2::new(1280.0, 720.0);
+        let camera =
+            Camera::perspective(diagonal.x / diagonal.y, std::f32::consts::FRAC_PI_3, 0.125);
+        let camera_transform =
+            GlobalTransform::from(Matrix4::new_translation(&Vector3::new(0.0, 0.0, 3.0)));
+
+        let frustum = camera.frustum(&camera_transform);
+        assert!(frustum.contains_point(&Point3::origin()));
+        assert!(!frustum.contains_point(&Point3::new(0.0, 0.0, 5.0)));
+        assert!(!frustum.contains_point(&Point3::new(10.0, 0.0, 0.0)));
+
+        let sphere = Sphere::new(Point3::origin(), 1.0);
+        assert!(frustum.intersects(&sphere));
+        let ray = camera.screen_ray(Point2::from(diagonal / 2.0), diagonal, &camera_transform);
+        assert_abs_diff_eq!(sphere.intersect_ray(&ray).unwrap(), 1.875, epsilon = 1.0e-5);
+    }
 }
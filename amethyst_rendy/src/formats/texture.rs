@@ -1,5 +1,5 @@
 //! Texture formats implementation.
-use amethyst_assets::Format;
+use amethyst_assets::{BuildStep, Format};
 use amethyst_error::Error;
 use rendy::{
     hal::{
@@ -9,7 +9,7 @@ use rendy::{
     texture::{
         image::{load_from_image, ImageTextureConfig},
         pixel::{AsPixel, Rgba8Srgb},
-        TextureBuilder,
+        MipLevels, TextureBuilder,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -136,6 +136,33 @@ impl Format<TextureData> for ImageFormat {
     }
 }
 
+/// Build step giving textures their full chain of mip levels, down to 1x1.
+///
+/// The levels are generated from the first one when the texture is uploaded to the GPU, so this
+/// only changes how the texture is built, not the size of the stored asset. Enable it in the
+/// `.meta` file of an image:
+///
+/// ```ron
+/// importer_options: (
+///     format: (/* ... */),
+///     build_steps: [(step: "generate_mipmaps")],
+/// ),
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GenerateMipmaps;
+
+impl BuildStep for GenerateMipmaps {
+    const NAME: &'static str = "generate_mipmaps";
+    type Data = TextureData;
+    type Settings = ();
+
+    fn build(&self, data: TextureData, _: &()) -> Result<TextureData, Error> {
+        Ok(data.0.with_mip_levels(MipLevels::GenerateAuto).into())
+    }
+}
+
+amethyst_assets::register_build_step!(GenerateMipmaps);
+
 /// Provides enum variant typecasting of texture data.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum TextureGenerator {
@@ -192,3 +219,34 @@ impl TextureGenerator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use amethyst_assets::{run_build_steps, BuildStepConfig};
+
+    use super::*;
+
+    /// The mip levels of the texture, read back from its serialized builder which doesn't expose
+    /// them.
+    fn mip_levels(data: &TextureData) -> MipLevels {
+        #[derive(Deserialize)]
+        struct TextureBuilder {
+            mip_levels: MipLevels,
+        }
+        let text = ron::to_string(&data.0).unwrap();
+        ron::from_str::<TextureBuilder>(&text).unwrap().mip_levels
+    }
+
+    #[test]
+    fn generate_mipmaps_step() {
+        let steps = vec![BuildStepConfig {
+            step: "generate_mipmaps".to_string(),
+            settings: None,
+        }];
+        let data = TextureGenerator::Srgba(1.0, 0.0, 0.0, 1.0).data();
+        assert!(!matches!(mip_levels(&data), MipLevels::GenerateAuto));
+
+        let built = run_build_steps(data, &steps).unwrap();
+        assert!(matches!(mip_levels(&built), MipLevels::GenerateAuto));
+    }
+}
@@ -107,7 +107,7 @@ pub use rendy::{
 pub use crate::{
     bundle::{RenderPlugin, RenderingBundle},
     camera::{ActiveCamera, Camera},
-    formats::texture::ImageFormat,
+    formats::texture::{GenerateMipmaps, ImageFormat},
     mtl::{Material, MaterialDefaults},
     plugins::*,
     sprite::{Sprite, SpriteRender, SpriteSheet},
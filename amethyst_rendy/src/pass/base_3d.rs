@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use amethyst_assets::{AssetHandle, AssetStorage, Handle, LoadHandle};
-use amethyst_core::{ecs::IntoQuery, transform::Transform};
+use amethyst_core::{ecs::IntoQuery, transform::GlobalTransform};
 use derivative::Derivative;
 use rendy::{
     command::{QueueId, RenderPassEncoder},
@@ -216,7 +216,7 @@ impl<B: Backend, T: Base3DPassDef> RenderGroup<B, GraphAuxData> for DrawBase3D<B
         {
             profile_scope_impl!("prepare");
             let mut query =
-                <(&Handle<Material>, &Handle<Mesh>, &Transform, Option<&Tint>)>::query();
+                <(&Handle<Material>, &Handle<Mesh>, &GlobalTransform, Option<&Tint>)>::query();
 
             visibility
                 .visible_unordered
@@ -254,7 +254,7 @@ impl<B: Backend, T: Base3DPassDef> RenderGroup<B, GraphAuxData> for DrawBase3D<B
             let mut query = <(
                 &Handle<Material>,
                 &Handle<Mesh>,
-                &Transform,
+                &GlobalTransform,
                 Option<&Tint>,
                 &JointTransforms,
             )>::query();
@@ -569,7 +569,7 @@ impl<B: Backend, T: Base3DPassDef> RenderGroup<B, GraphAuxData> for DrawBase3DTr
             profile_scope_impl!("prepare");
 
             let mut query =
-                <(&Handle<Material>, &Handle<Mesh>, &Transform, Option<&Tint>)>::query();
+                <(&Handle<Material>, &Handle<Mesh>, &GlobalTransform, Option<&Tint>)>::query();
 
             visibility
                 .visible_ordered
@@ -608,7 +608,7 @@ impl<B: Backend, T: Base3DPassDef> RenderGroup<B, GraphAuxData> for DrawBase3DTr
             let mut query = <(
                 &Handle<Material>,
                 &Handle<Mesh>,
-                &Transform,
+                &GlobalTransform,
                 Option<&Tint>,
                 &JointTransforms,
             )>::query();
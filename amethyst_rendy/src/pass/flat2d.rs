@@ -1,7 +1,7 @@
 use amethyst_assets::AssetStorage;
 use amethyst_core::{
     ecs::{systems::ResourceSet, IntoQuery, Read},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use derivative::Derivative;
 use rendy::{
@@ -130,7 +130,7 @@ impl<B: Backend> RenderGroup<B, GraphAuxData> for DrawFlat2D<B> {
             #[cfg(feature = "profiler")]
             profile_scope!("gather_visibility");
 
-            let mut query = <(&SpriteRender, &Transform, Option<&Tint>)>::query();
+            let mut query = <(&SpriteRender, &GlobalTransform, Option<&Tint>)>::query();
 
             visibility
                 .visible_unordered
@@ -308,7 +308,7 @@ impl<B: Backend> RenderGroup<B, GraphAuxData> for DrawFlat2DTransparent<B> {
             #[cfg(feature = "profiler")]
             profile_scope!("gather_visibility");
 
-            let mut query = <(&SpriteRender, &Transform, Option<&Tint>)>::query();
+            let mut query = <(&SpriteRender, &GlobalTransform, Option<&Tint>)>::query();
 
             visibility
                 .visible_ordered
@@ -2,7 +2,7 @@
 
 use amethyst_core::{
     math::{convert, Matrix4, Vector4},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use glsl_layout::{float, int, mat4, vec2, vec3, vec4, Uniform};
 use rendy::{
@@ -89,12 +89,12 @@ pub struct VertexArgs {
 }
 
 impl VertexArgs {
-    /// Populates a `VertexArgs` instance-rate structure with the information from a `Transform`
-    /// and `TintComponent` components.
+    /// Populates a `VertexArgs` instance-rate structure with the information from a
+    /// `GlobalTransform` and `TintComponent` components.
     #[inline]
     #[must_use]
-    pub fn from_object_data(transform: &Transform, tint: Option<&TintComponent>) -> Self {
-        let model: [[f32; 4]; 4] = convert::<_, Matrix4<f32>>(*transform.global_matrix()).into();
+    pub fn from_object_data(transform: &GlobalTransform, tint: Option<&TintComponent>) -> Self {
+        let model: [[f32; 4]; 4] = convert::<_, Matrix4<f32>>(*transform.matrix()).into();
         VertexArgs {
             model: model.into(),
             tint: tint.map_or([1.0; 4].into(), |t| {
@@ -152,15 +152,15 @@ impl AsVertex for SkinnedVertexArgs {
 }
 
 impl SkinnedVertexArgs {
-    /// Populate `SkinnedVertexArgs` from the supplied `Transform` and `TintComponent`
+    /// Populate `SkinnedVertexArgs` from the supplied `GlobalTransform` and `TintComponent`
     #[inline]
     #[must_use]
     pub fn from_object_data(
-        transform: &Transform,
+        transform: &GlobalTransform,
         tint: Option<&TintComponent>,
         joints_offset: u32,
     ) -> Self {
-        let model: [[f32; 4]; 4] = convert::<_, Matrix4<f32>>(*transform.global_matrix()).into();
+        let model: [[f32; 4]; 4] = convert::<_, Matrix4<f32>>(*transform.matrix()).into();
         SkinnedVertexArgs {
             model: model.into(),
             tint: tint.map_or([1.0; 4].into(), |t| {
@@ -340,14 +340,14 @@ impl SpriteArgs {
     /// * `tex_storage` - `Texture` Storage
     /// * `sprite_storage` - `SpriteSheet` Storage
     /// * `sprite_render` - `SpriteRender` component reference
-    /// * `transform` - 'Transform' component reference
+    /// * `transform` - 'GlobalTransform' component reference
     #[must_use]
     pub fn from_data<'a>(
         sprite: &'a Sprite,
-        transform: &Transform,
+        transform: &GlobalTransform,
         tint: Option<&TintComponent>,
     ) -> Self {
-        let transform = convert::<_, Matrix4<f32>>(*transform.global_matrix());
+        let transform = convert::<_, Matrix4<f32>>(*transform.matrix());
         let dir_x = transform.column(0) * sprite.width;
         let dir_y = transform.column(1) * -sprite.height;
         let pos = transform * Vector4::new(-sprite.offsets[0], -sprite.offsets[1], 0.0, 1.0);
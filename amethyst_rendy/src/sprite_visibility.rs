@@ -4,7 +4,7 @@ use std::cmp::Ordering;
 use amethyst_core::{
     ecs::{component, Entity, IntoQuery, ParallelRunnable, System, SystemBuilder},
     math::{Point3, Vector3},
-    transform::Transform,
+    transform::GlobalTransform,
     Hidden, HiddenPropagate,
 };
 #[cfg(feature = "profiler")]
@@ -40,7 +40,7 @@ struct Internals {
 /// The sprite render pass should draw all sprites without semi-transparent pixels, then draw the
 /// sprites with semi-transparent pixels from far to near.
 ///
-/// Note that this should run after `Transform` has been updated for the current frame, and
+/// Note that this should run after `GlobalTransform` has been updated for the current frame, and
 /// before rendering occurs.
 #[derive(Debug)]
 pub struct SpriteVisibilitySortingSystem;
@@ -53,13 +53,13 @@ impl System for SpriteVisibilitySortingSystem {
             SystemBuilder::<()>::new("SpriteVisibilitySortingSystem")
                 .read_resource::<ActiveCamera>()
                 .write_resource::<SpriteVisibility>()
-                .with_query(<(&Camera, &Transform)>::query())
-                .with_query(<(Entity, &Camera, &Transform)>::query())
+                .with_query(<(&Camera, &GlobalTransform)>::query())
+                .with_query(<(Entity, &Camera, &GlobalTransform)>::query())
                 .with_query(
-                    <(Entity, &Transform, &SpriteRender, &Transparent)>::query()
+                    <(Entity, &GlobalTransform, &SpriteRender, &Transparent)>::query()
                         .filter(!component::<Hidden>() & !component::<HiddenPropagate>()),
                 )
-                .with_query(<(Entity, &Transform, &SpriteRender)>::query().filter(
+                .with_query(<(Entity, &GlobalTransform, &SpriteRender)>::query().filter(
                     !component::<Transparent>()
                         & !component::<Hidden>()
                         & !component::<HiddenPropagate>(),
@@ -98,15 +98,15 @@ impl System for SpriteVisibilitySortingSystem {
                             None => return,
                         };
 
-                        let camera_backward = camera_transform.global_matrix().column(2).xyz();
+                        let camera_backward = camera_transform.matrix().column(2).xyz();
                         let camera_centroid =
-                            camera_transform.global_matrix().transform_point(&origin);
+                            camera_transform.matrix().transform_point(&origin);
 
                         transparent_centroids.extend(
                             transparent_query
                                 .iter(world)
                                 .map(|(e, t, _, _)| {
-                                    (*e, t.global_matrix().transform_point(&origin))
+                                    (*e, t.matrix().transform_point(&origin))
                                 })
                                 // filter entities behind the camera
                                 .filter(|(_, c)| (c - camera_centroid).dot(&camera_backward) < 0.0)
@@ -133,7 +133,7 @@ impl System for SpriteVisibilitySortingSystem {
                         visibility.visible_unordered.extend(
                             non_transparent_query
                                 .iter(world)
-                                .map(|(e, t, _)| (e, t.global_matrix().transform_point(&origin)))
+                                .map(|(e, t, _)| (e, t.matrix().transform_point(&origin)))
                                 // filter entities behind the camera
                                 .filter(|(_, c)| (c - camera_centroid).dot(&camera_backward) < 0.0)
                                 .map(|(entity, _)| entity),
@@ -3,7 +3,7 @@
 use amethyst_core::{
     ecs::{IntoQuery, Read, Resources, World},
     math::{convert, Vector3},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use glsl_layout::Uniform;
 #[cfg(feature = "profiler")]
@@ -226,7 +226,7 @@ impl<B: Backend> PerImageEnvironmentSub<B> {
             }
             .std140();
 
-            let mut point_lights_query = <(Read<Light>, Read<Transform>)>::query();
+            let mut point_lights_query = <(Read<Light>, Read<GlobalTransform>)>::query();
             let point_lights = point_lights_query
                 .iter(world)
                 .filter_map(|(light, transform)| {
@@ -235,7 +235,7 @@ impl<B: Backend> PerImageEnvironmentSub<B> {
                             Some(
                                 pod::PointLight {
                                     position: convert::<_, Vector3<f32>>(
-                                        transform.global_matrix().column(3).xyz(),
+                                        transform.translation(),
                                     )
                                     .into_pod(),
                                     color: light.color.into_pod(),
@@ -269,7 +269,7 @@ impl<B: Backend> PerImageEnvironmentSub<B> {
                 })
                 .take(MAX_DIR_LIGHTS);
 
-            let mut spot_lights_query = <(Read<Light>, Read<Transform>)>::query();
+            let mut spot_lights_query = <(Read<Light>, Read<GlobalTransform>)>::query();
             let spot_lights = spot_lights_query
                 .iter(world)
                 .filter_map(|(light, transform)| {
@@ -278,7 +278,7 @@ impl<B: Backend> PerImageEnvironmentSub<B> {
                             Some(
                                 pod::SpotLight {
                                     position: convert::<_, Vector3<f32>>(
-                                        transform.global_matrix().column(3).xyz(),
+                                        transform.translation(),
                                     )
                                     .into_pod(),
                                     color: light.color.into_pod(),
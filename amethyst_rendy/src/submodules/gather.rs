@@ -2,7 +2,7 @@
 use amethyst_core::{
     ecs::{Entity, EntityStore, IntoQuery, Read, Resources, World},
     math::{convert, Matrix4, Vector3},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use glsl_layout::{vec3, Uniform};
 #[cfg(feature = "profiler")]
@@ -60,14 +60,14 @@ impl CameraGatherer {
     /// the appropriate camera to use for projection, and returns the camera position and extracted
     /// projection matrix.
     ///
-    /// The matrix returned is the camera's `Projection` matrix and the camera `Transform::global_view_matrix`
+    /// The matrix returned is the camera's `Projection` matrix and the camera `GlobalTransform::view_matrix`
     #[must_use]
     pub fn gather(world: &World, resources: &Resources) -> Self {
         #[cfg(feature = "profiler")]
         profile_scope!("gather_cameras");
 
         let defcam = Camera::standard_2d(1.0, 1.0);
-        let identity = Transform::default();
+        let identity = GlobalTransform::default();
 
         let camera_entity = Self::gather_camera_entity(world, resources);
 
@@ -79,16 +79,15 @@ impl CameraGatherer {
             world
                 .entry_ref(e)
                 .unwrap()
-                .into_component::<Transform>()
+                .into_component::<GlobalTransform>()
                 .ok()
         });
         let transform = transform.unwrap_or(&identity);
 
-        let camera_position =
-            convert::<_, Vector3<f32>>(transform.global_matrix().column(3).xyz()).into_pod();
+        let camera_position = convert::<_, Vector3<f32>>(transform.translation()).into_pod();
 
         let proj = &camera.matrix;
-        let view = transform.global_view_matrix();
+        let view = transform.view_matrix();
 
         let proj_view: [[f32; 4]; 4] = ((*proj) * view).into();
         let proj: [[f32; 4]; 4] = (*proj).into();
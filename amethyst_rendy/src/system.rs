@@ -1,7 +1,12 @@
 //! Renderer system
 
-use amethyst_assets::{AssetStorage, DefaultLoader, Loader, ProcessingQueue, ProcessingState};
-use amethyst_core::ecs::{ParallelRunnable, Resources, System, SystemBuilder, World};
+use amethyst_assets::{
+    AssetEvent, AssetStorage, DefaultLoader, Loader, ProcessingQueue, ProcessingState,
+};
+use amethyst_core::{
+    ecs::{ParallelRunnable, Resources, System, SystemBuilder, World},
+    shrev::EventChannel,
+};
 use derivative::Derivative;
 use palette::{LinSrgba, Srgba};
 use rendy::{
@@ -149,6 +154,7 @@ impl<B: Backend> System for MeshProcessorSystem<B> {
                 .write_resource::<AssetStorage<Mesh>>()
                 .read_resource::<QueueId>()
                 .read_resource::<Factory<B>>()
+                .write_resource::<EventChannel<AssetEvent<Mesh>>>()
                 .build(
                     move |commands,
                           world,
@@ -157,22 +163,28 @@ impl<B: Backend> System for MeshProcessorSystem<B> {
                         mesh_storage,
                         queue_id,
                         /* time, pool, */ factory,
+                        events,
                     ),
                           _| {
                         #[cfg(feature = "profiler")]
                         profile_scope!("mesh_processor");
-                        processing_queue.process(mesh_storage, |b, _, _| {
+                        processing_queue.process(mesh_storage, |b, storage, handle| {
                             log::trace!("Processing Mesh: {:?}", b);
 
                             #[cfg(feature = "profiler")]
                             profile_scope!("process_mesh");
 
+                            let size_in_bytes = b.size_in_bytes();
                             b.0.build(**queue_id, factory)
-                                .map(B::wrap_mesh)
+                                .map(|mesh| {
+                                    storage.set_size_in_bytes(*handle, size_in_bytes);
+                                    B::wrap_mesh(mesh)
+                                })
                                 .map(ProcessingState::Loaded)
                                 .map_err(|e| e.into())
                         });
                         mesh_storage.process_custom_drop(|_| {});
+                        events.iter_write(mesh_storage.drain_events());
                     },
                 ),
         )
@@ -194,6 +206,7 @@ impl<B: Backend> System for TextureProcessorSystem<B> {
                 .write_resource::<AssetStorage<Texture>>()
                 .read_resource::<QueueId>()
                 .write_resource::<Factory<B>>()
+                .write_resource::<EventChannel<AssetEvent<Texture>>>()
                 .build(
                     move |commands,
                           world,
@@ -202,6 +215,7 @@ impl<B: Backend> System for TextureProcessorSystem<B> {
                         texture_storage,
                         queue_id,
                         /* time, pool, */ factory,
+                        events,
                     ),
                           _| {
                         #[cfg(feature = "profiler")]
@@ -228,6 +242,7 @@ impl<B: Backend> System for TextureProcessorSystem<B> {
                             .map_err(|e| e.into())
                         });
                         texture_storage.process_custom_drop(|_| {});
+                        events.iter_write(texture_storage.drain_events());
                     },
                 ),
         )
@@ -12,7 +12,10 @@ use amethyst_assets::{
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
 
-use crate::system::{MeshProcessorSystem, TextureProcessorSystem};
+use crate::{
+    rendy::hal::{format::Format, image::Kind},
+    system::{MeshProcessorSystem, TextureProcessorSystem},
+};
 
 /// Extension of the rendy Backend trait.
 pub trait Backend: rendy::hal::Backend {
@@ -232,6 +235,35 @@ impl Asset for Texture {
         "Texture"
     }
     type Data = TextureData;
+
+    fn size_in_bytes(&self) -> Option<usize> {
+        let info = match self {
+            #[cfg(target_os = "macos")]
+            Texture::Metal(texture) => texture.image().info(),
+            #[cfg(all(not(target_os = "macos"), not(feature = "empty")))]
+            Texture::Vulkan(texture) => texture.image().info(),
+            #[cfg(feature = "empty")]
+            Texture::Empty(texture) => texture.image().info(),
+        };
+        Some(image_size_in_bytes(info.kind, info.levels, info.format))
+    }
+}
+
+/// The memory used by all the layers and mip levels of an image.
+fn image_size_in_bytes(kind: Kind, levels: u8, format: Format) -> usize {
+    let desc = format.surface_desc();
+    let (block_width, block_height) = (u32::from(desc.dim.0), u32::from(desc.dim.1));
+    let block_bytes = usize::from(desc.bits / 8);
+    let layers = usize::from(kind.num_layers());
+    (0..levels)
+        .map(|level| {
+            let extent = kind.level_extent(level);
+            // Compressed formats store blocks of texels, partial blocks use a full block.
+            let blocks_wide = (extent.width + block_width - 1) / block_width;
+            let blocks_high = (extent.height + block_height - 1) / block_height;
+            (blocks_wide * blocks_high * extent.depth) as usize * block_bytes * layers
+        })
+        .sum()
 }
 
 /// Newtype for `MeshBuilder` prefab usage.
@@ -246,6 +278,19 @@ pub struct MeshData(
 #[uuid = "25063afd-6cc0-487e-982f-a63fed7d7393"]
 pub struct TextureData(pub rendy::texture::TextureBuilder<'static>);
 
+impl MeshData {
+    /// The size of the vertex and index buffers built from this data.
+    ///
+    /// The `MeshProcessorSystem` records it in the `AssetStorage<Mesh>`, as the built `Mesh`
+    /// doesn't tell the size of its buffers.
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        let vertices: usize = self.0.vertices.iter().map(|v| v.vertices.len()).sum();
+        let indices = self.0.indices.as_ref().map_or(0, |i| i.indices.len());
+        vertices + indices
+    }
+}
+
 impl From<rendy::mesh::MeshBuilder<'static>> for MeshData {
     fn from(builder: rendy::mesh::MeshBuilder<'static>) -> Self {
         Self(builder)
@@ -265,3 +310,127 @@ where
     log::debug!("deserialize_data");
     Ok(rendy::mesh::MeshBuilder::deserialize(deserializer)?.into_owned())
 }
+
+#[cfg(test)]
+mod tests {
+    use amethyst_assets::{
+        AssetBudget, AssetBudgetSystem, AssetHandle, AssetPressureEvent, AssetStorage,
+        DefaultLoader, Handle, Loader, LoaderBundle, ProcessingQueue,
+    };
+    use amethyst_core::{
+        ecs::{DispatcherBuilder, Resources, World},
+        shrev::EventChannel,
+    };
+    use palette::Srgba;
+    use rendy::{
+        command::QueueId,
+        init::Rendy,
+        mesh::{MeshBuilder, Position},
+        texture::palette::load_from_srgba,
+    };
+
+    use super::*;
+
+    #[test]
+    fn image_size_counts_levels_layers_and_blocks() {
+        let square = Kind::D2(256, 256, 1, 1);
+        assert_eq!(image_size_in_bytes(square, 1, Format::Rgba8Srgb), 262_144);
+        // The full mip chain, down to 1x1.
+        assert_eq!(image_size_in_bytes(square, 9, Format::Rgba8Srgb), 349_524);
+        // The 6 faces of a cube map.
+        let cube = Kind::D2(16, 16, 6, 1);
+        assert_eq!(image_size_in_bytes(cube, 1, Format::Rgba8Unorm), 6144);
+        // 8 bytes per block of 4x4 texels, a 6x6 image takes 2x2 blocks.
+        let compressed = Kind::D2(6, 6, 1, 1);
+        assert_eq!(image_size_in_bytes(compressed, 1, Format::Bc1RgbUnorm), 32);
+    }
+
+    #[test]
+    fn mesh_size_counts_vertices_and_indices() {
+        let builder = MeshBuilder::new()
+            .with_vertices(vec![Position([0.0, 0.0, 0.0]); 3])
+            .with_indices(vec![0_u16, 1, 2]);
+        // 3 positions of 12 bytes and 3 indices of 2 bytes.
+        assert_eq!(MeshData(builder).size_in_bytes(), 42);
+    }
+
+    #[test]
+    #[ignore] // CI can't run tests requiring actual backend
+    fn texture_budget_evicts_textures() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let rendy: Rendy<DefaultBackend> = Rendy::init(&Default::default()).unwrap();
+        resources.insert(QueueId {
+            family: rendy.families.family_by_index(0).id(),
+            index: 0,
+        });
+        resources.insert(rendy.factory);
+        // Room for 2 of the 3 textures of 1x1 RGBA texels.
+        resources.insert(AssetBudget::<Texture>::new(8));
+
+        let mut dispatcher = DispatcherBuilder::default()
+            .add_bundle(LoaderBundle)
+            .add_system(AssetBudgetSystem::<Texture>::default())
+            .build(&mut world, &mut resources)
+            .unwrap();
+        let mut reader = resources
+            .get_mut::<EventChannel<AssetPressureEvent>>()
+            .unwrap()
+            .register_reader();
+
+        let mut handles: Vec<Handle<Texture>> = {
+            let loader = resources.get::<DefaultLoader>().unwrap();
+            let queue = resources.get::<ProcessingQueue<TextureData>>().unwrap();
+            (0..3)
+                .map(|_| {
+                    let texture = load_from_srgba(Srgba::new(1.0, 1.0, 1.0, 1.0));
+                    loader.load_from_data(texture.into(), (), &queue)
+                })
+                .collect()
+        };
+        dispatcher.execute(&mut world, &mut resources);
+        {
+            let storage = resources.get::<AssetStorage<Texture>>().unwrap();
+            assert_eq!(storage.memory_usage(), 12);
+            let mut budget = resources.get_mut::<AssetBudget<Texture>>().unwrap();
+            for handle in &handles {
+                budget.cache(handle);
+            }
+        }
+
+        // Nothing can be released while the game holds every handle.
+        dispatcher.execute(&mut world, &mut resources);
+        let event = resources
+            .get::<EventChannel<AssetPressureEvent>>()
+            .unwrap()
+            .read(&mut reader)
+            .last()
+            .cloned()
+            .expect("the budget was exceeded");
+        assert_eq!(event.used_bytes, 12);
+        assert_eq!((event.evicted, event.evicted_bytes), (0, 0));
+        assert!(event.is_over_budget());
+
+        let dropped = handles.remove(0);
+        let dropped_handle = dropped.load_handle();
+        drop(dropped);
+        // The first frame processes the dropped handle, the second one evicts its texture.
+        dispatcher.execute(&mut world, &mut resources);
+        dispatcher.execute(&mut world, &mut resources);
+
+        let event = resources
+            .get::<EventChannel<AssetPressureEvent>>()
+            .unwrap()
+            .read(&mut reader)
+            .find(|event| event.evicted > 0)
+            .cloned()
+            .expect("the dropped texture was evicted");
+        assert_eq!((event.evicted, event.evicted_bytes), (1, 4));
+        assert!(!event.is_over_budget());
+        let storage = resources.get::<AssetStorage<Texture>>().unwrap();
+        assert!(!storage.contains(dropped_handle));
+        assert_eq!(storage.memory_usage(), 8);
+        let budget = resources.get::<AssetBudget<Texture>>().unwrap();
+        assert_eq!(budget.num_cached(), 2);
+    }
+}
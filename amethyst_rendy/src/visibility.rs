@@ -1,10 +1,12 @@
 //! Transparency, visibility sorting and camera centroid culling for 3D Meshes.
 use std::cmp::Ordering;
 
+pub use amethyst_core::geometry::Frustum;
 use amethyst_core::{
     ecs::{component, systems::ParallelRunnable, Entity, IntoQuery, System, SystemBuilder},
-    math::{convert, distance_squared, Matrix4, Point3, Vector4},
-    transform::Transform,
+    geometry::{Intersects, Sphere},
+    math::{distance_squared, Point3},
+    transform::GlobalTransform,
     Hidden, HiddenPropagate,
 };
 use indexmap::IndexSet;
@@ -60,6 +62,12 @@ impl BoundingSphere {
             radius,
         }
     }
+
+    /// Returns the bounding sphere as a `Sphere`, in the local space of its entity.
+    #[must_use]
+    pub fn sphere(&self) -> Sphere {
+        Sphere::new(self.center, self.radius)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -73,7 +81,7 @@ struct Internals {
 /// Determine what entities are visible to the camera, and which are not. Will also sort transparent
 /// entities back to front based on distance from camera.
 ///
-/// Note that this should run after `Transform` has been updated for the current frame, and
+/// Note that this should run after `GlobalTransform` has been updated for the current frame, and
 /// before rendering occurs.
 #[derive(Default, Debug)]
 pub struct VisibilitySortingSystem {
@@ -87,12 +95,12 @@ impl System for VisibilitySortingSystem {
             SystemBuilder::new("VisibilitySortingSystem")
                 .read_resource::<ActiveCamera>()
                 .write_resource::<Visibility>()
-                .with_query(<(&Camera, &Transform)>::query())
-                .with_query(<(Entity, &Camera, &Transform)>::query())
+                .with_query(<(&Camera, &GlobalTransform)>::query())
+                .with_query(<(Entity, &Camera, &GlobalTransform)>::query())
                 .with_query(
                     <(
                         Entity,
-                        &Transform,
+                        &GlobalTransform,
                         Option<&Transparent>,
                         Option<&BoundingSphere>,
                     )>::query()
@@ -129,38 +137,29 @@ impl System for VisibilitySortingSystem {
                         };
 
                         let camera_centroid =
-                            camera_transform.global_matrix().transform_point(&origin);
-                        let frustum = Frustum::new(
-                            convert::<_, Matrix4<f32>>(camera.matrix)
-                                * camera_transform.global_matrix().try_inverse().unwrap(),
-                        );
+                            camera_transform.matrix().transform_point(&origin);
+                        let frustum = camera.frustum(camera_transform);
 
                         self.centroids.extend(
                             entity_query
                                 .iter(world)
                                 .map(|(entity, transform, transparent, sphere)| {
-                                    let pos = sphere.map_or(origin, |s| s.center);
-                                    let matrix = transform.global_matrix();
-                                    (
-                                        *entity,
-                                        transparent.is_some(),
-                                        matrix.transform_point(&pos),
-                                        sphere.map_or(1.0, |s| s.radius)
-                                            * matrix[(0, 0)]
-                                                .max(matrix[(1, 1)])
-                                                .max(matrix[(2, 2)]),
-                                    )
-                                })
-                                .filter(|(_, _, centroid, radius)| {
-                                    frustum.check_sphere(centroid, *radius)
+                                    let sphere = sphere
+                                        .map_or_else(
+                                            || Sphere::new(origin, 1.0),
+                                            BoundingSphere::sphere,
+                                        )
+                                        .transformed(transform.matrix());
+                                    (*entity, transparent.is_some(), sphere)
                                 })
-                                .map(|(entity, transparent, centroid, _)| {
+                                .filter(|(_, _, sphere)| frustum.intersects(sphere))
+                                .map(|(entity, transparent, sphere)| {
                                     Internals {
                                         entity,
                                         transparent,
-                                        centroid,
+                                        centroid: sphere.center,
                                         camera_distance: distance_squared(
-                                            &centroid,
+                                            &sphere.center,
                                             &camera_centroid,
                                         ),
                                     }
@@ -191,46 +190,3 @@ impl System for VisibilitySortingSystem {
         )
     }
 }
-
-/// Simple view Frustum implementation
-#[derive(Debug)]
-pub struct Frustum {
-    /// The planes of the frustum
-    pub planes: [Vector4<f32>; 6],
-}
-
-impl Frustum {
-    /// Create a new simple frustum from the provided matrix.
-    #[must_use]
-    pub fn new(matrix: Matrix4<f32>) -> Self {
-        let planes = [
-            (matrix.row(3) + matrix.row(0)).transpose(),
-            (matrix.row(3) - matrix.row(0)).transpose(),
-            (matrix.row(3) - matrix.row(1)).transpose(),
-            (matrix.row(3) + matrix.row(1)).transpose(),
-            (matrix.row(3) + matrix.row(2)).transpose(),
-            (matrix.row(3) - matrix.row(2)).transpose(),
-        ];
-        Self {
-            planes: [
-                planes[0] * (1.0 / planes[0].xyz().magnitude()),
-                planes[1] * (1.0 / planes[1].xyz().magnitude()),
-                planes[2] * (1.0 / planes[2].xyz().magnitude()),
-                planes[3] * (1.0 / planes[3].xyz().magnitude()),
-                planes[4] * (1.0 / planes[4].xyz().magnitude()),
-                planes[5] * (1.0 / planes[5].xyz().magnitude()),
-            ],
-        }
-    }
-
-    /// Check if the given sphere is within the Frustum
-    #[must_use]
-    pub fn check_sphere(&self, center: &Point3<f32>, radius: f32) -> bool {
-        for plane in &self.planes {
-            if plane.xyz().dot(&center.coords) + plane.w <= -radius {
-                return false;
-            }
-        }
-        true
-    }
-}
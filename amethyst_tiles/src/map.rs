@@ -3,8 +3,9 @@
 use amethyst_assets::{Asset, Handle};
 use amethyst_core::{
     ecs::{Resources, World},
+    geometry::Aabb,
     math::{Matrix4, Point3, Vector3},
-    transform::Transform,
+    transform::GlobalTransform,
 };
 use amethyst_rendy::{palette::Srgba, SpriteSheet};
 
@@ -50,7 +51,11 @@ pub trait Map {
     /// This performs an inverse matrix transformation of the world coordinate, scaling and translating using this
     /// maps `origin` and `tile_dimensions` respectively. If the tile map entity has a transform component, then
     /// it also translates the point using the it's transform.
-    fn to_world(&self, coord: &Point3<u32>, map_transform: Option<&Transform>) -> Vector3<f32>;
+    fn to_world(
+        &self,
+        coord: &Point3<u32>,
+        map_transform: Option<&GlobalTransform>,
+    ) -> Vector3<f32>;
 
     /// Convert an amethyst world-coordinate space coordinate `Vector3<f32>` to a tile coordinate `Point3<u32>`
     /// This performs an inverse matrix transformation of the world coordinate, scaling and translating using this
@@ -63,7 +68,7 @@ pub trait Map {
     fn to_tile(
         &self,
         coord: &Vector3<f32>,
-        map_transform: Option<&Transform>,
+        map_transform: Option<&GlobalTransform>,
     ) -> Result<Point3<u32>, TileOutOfBoundsError>;
 
     /// Returns the `Matrix4` transform which was created for transforming between world and tile coordinate spaces.
@@ -169,6 +174,26 @@ impl<T: Tile, E: CoordinateEncoder> TileMap<T, E> {
             version: 1,
         }
     }
+
+    /// The world space box covering every tile of the map, moved by the `GlobalTransform` of the
+    /// map entity if it has one.
+    #[must_use]
+    pub fn world_bounds(&self, map_transform: Option<&GlobalTransform>) -> Aabb {
+        // Tiles are centered on their coordinates, and the Y axis of tile space points down.
+        let dimensions = &self.dimensions;
+        let tiles = Aabb::new(
+            Point3::new(-0.5, -(dimensions.y as f32) + 0.5, 0.0),
+            Point3::new(
+                dimensions.x as f32 - 0.5,
+                0.5,
+                dimensions.z.saturating_sub(1) as f32,
+            ),
+        );
+        let transform = map_transform.map_or(self.transform, |map_transform| {
+            map_transform.matrix() * self.transform
+        });
+        tiles.transformed(&transform)
+    }
 }
 
 impl<T: Tile, E: CoordinateEncoder> Map for TileMap<T, E> {
@@ -193,7 +218,11 @@ impl<T: Tile, E: CoordinateEncoder> Map for TileMap<T, E> {
     }
 
     #[inline]
-    fn to_world(&self, coord: &Point3<u32>, map_transform: Option<&Transform>) -> Vector3<f32> {
+    fn to_world(
+        &self,
+        coord: &Point3<u32>,
+        map_transform: Option<&GlobalTransform>,
+    ) -> Vector3<f32> {
         to_world(&self.transform, coord, map_transform)
     }
 
@@ -202,7 +231,7 @@ impl<T: Tile, E: CoordinateEncoder> Map for TileMap<T, E> {
     fn to_tile(
         &self,
         coord: &Vector3<f32>,
-        map_transform: Option<&Transform>,
+        map_transform: Option<&GlobalTransform>,
     ) -> Result<Point3<u32>, TileOutOfBoundsError> {
         to_tile(&self.transform, coord, self.dimensions(), map_transform)
     }
@@ -294,12 +323,12 @@ fn create_transform(map_dimensions: &Vector3<u32>, tile_dimensions: &Vector3<u32
 fn to_world(
     transform: &Matrix4<f32>,
     coord: &Point3<u32>,
-    map_transform: Option<&Transform>,
+    map_transform: Option<&GlobalTransform>,
 ) -> Vector3<f32> {
     let coord_f = Point3::new(coord.x as f32, -1.0 * coord.y as f32, coord.z as f32);
     let point = transform.transform_point(&coord_f);
     map_transform.map_or(point.coords, |map_trans| {
-        map_trans.global_matrix().transform_point(&point).coords
+        map_trans.transform_point(&point).coords
     })
 }
 
@@ -308,11 +337,11 @@ fn to_tile(
     transform: &Matrix4<f32>,
     coord: &Vector3<f32>,
     max_dimensions: &Vector3<u32>,
-    map_transform: Option<&Transform>,
+    map_transform: Option<&GlobalTransform>,
 ) -> Result<Point3<u32>, TileOutOfBoundsError> {
     let point = Point3::from(*coord);
     let point = map_transform.map_or(point, |map_trans| {
-        map_trans.global_view_matrix().transform_point(&point)
+        map_trans.view_matrix().transform_point(&point)
     });
 
     let mut inverse = transform
@@ -486,7 +515,7 @@ mod tests {
         transform: &Matrix4<f32>,
         tile: Point3<u32>,
         world: Point3<f32>,
-        map_transform: &Transform,
+        map_transform: &GlobalTransform,
     ) {
         let world_result = to_world(transform, &tile, Some(map_transform));
         assert_eq!(world_result, world.coords);
@@ -516,7 +545,7 @@ mod tests {
         let transform = create_transform(&Vector3::new(64, 64, 64), &Vector3::new(10, 10, 1));
         let mut map_transform = Transform::default();
         map_transform.set_translation_xyz(-10.0, 10.0, 0.0);
-        map_transform.copy_local_to_global();
+        let map_transform = GlobalTransform::from(map_transform.matrix());
 
         test_coord_with_map_transform(
             &transform,
@@ -544,6 +573,25 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn world_bounds_cover_every_tile() {
+        let map = TileMap::<TestTile, FlatEncoder>::new(
+            Vector3::new(4, 3, 1),
+            Vector3::new(10, 10, 1),
+            None,
+        );
+        let bounds = map.world_bounds(None);
+        assert_eq!(bounds.min, Point3::new(-20.0, -15.0, 0.0));
+        assert_eq!(bounds.max, Point3::new(20.0, 15.0, 0.0));
+
+        let mut map_transform = Transform::default();
+        map_transform.set_translation_xyz(100.0, 0.0, 0.0);
+        let map_transform = GlobalTransform::from(map_transform.matrix());
+        let bounds = map.world_bounds(Some(&map_transform));
+        assert_eq!(bounds.min, Point3::new(80.0, -15.0, 0.0));
+        assert_eq!(bounds.max, Point3::new(120.0, 15.0, 0.0));
+    }
+
     #[test]
     pub fn tilemap_transform_positioning() {
         let transform = create_transform(&Vector3::new(1, 2, 3), &Vector3::new(10, 10, 1));
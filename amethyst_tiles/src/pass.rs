@@ -7,9 +7,9 @@ use amethyst_assets::{AssetHandle, AssetStorage, Handle};
 use amethyst_core::{
     dispatcher::{System, ThreadLocalSystem},
     ecs::{component, world::World, EntityStore, IntoQuery, Resources, TryRead},
-    geometry::{Plane, Ray},
+    geometry::{Frustum, Intersects, Plane, Ray},
     math::{self, clamp, convert, Matrix4, Point2, Point3, Vector2, Vector3, Vector4},
-    transform::Transform,
+    transform::GlobalTransform,
     Hidden,
 };
 use amethyst_rendy::{
@@ -81,7 +81,7 @@ pub trait DrawTiles2DBounds: 'static + std::fmt::Debug + Send + Sync {
     /// Returns the region to render the tiles
     fn bounds<T: Tile, E: CoordinateEncoder>(
         map: &TileMap<T, E>,
-        map_transform: Option<&Transform>,
+        map_transform: Option<&GlobalTransform>,
         aux: &GraphAuxData,
     ) -> Region;
 }
@@ -92,7 +92,7 @@ pub struct DrawTiles2DBoundsDefault;
 impl DrawTiles2DBounds for DrawTiles2DBoundsDefault {
     fn bounds<T: Tile, E: CoordinateEncoder>(
         map: &TileMap<T, E>,
-        map_transform: Option<&Transform>,
+        map_transform: Option<&GlobalTransform>,
         aux: &GraphAuxData,
     ) -> Region {
         Region::new(Point3::new(0, 0, 0), Point3::from(*map.dimensions()))
@@ -107,7 +107,7 @@ fn camera_ray_to_tile_coords<T: Tile, E: CoordinateEncoder>(
     ray: Ray<f32>,
     tile_plane: &Plane<f32>,
     map: &TileMap<T, E>,
-    map_transform: Option<&Transform>,
+    map_transform: Option<&GlobalTransform>,
 ) -> Point3<i64> {
     // Intersect rays with the tilemap, get intersecting tile coordinates
     let distance = ray.intersect_plane(tile_plane).unwrap_or(0.0);
@@ -128,7 +128,7 @@ fn camera_ray_to_tile_coords<T: Tile, E: CoordinateEncoder>(
 impl DrawTiles2DBounds for DrawTiles2DBoundsCameraCulling {
     fn bounds<T: Tile, E: CoordinateEncoder>(
         map: &TileMap<T, E>,
-        map_transform: Option<&Transform>,
+        map_transform: Option<&GlobalTransform>,
         aux: &GraphAuxData,
     ) -> Region {
         let active_camera = aux.resources.get::<ActiveCamera>();
@@ -144,13 +144,13 @@ impl DrawTiles2DBounds for DrawTiles2DBoundsCameraCulling {
         if let Ok(entry) = aux.world.entry_ref(active_camera.entity.unwrap()) {
             let tile_plane = Plane::from_point_normal(
                 &map_transform.map_or(Point3::new(0.0, 0.0, 0.0), |t| {
-                    Point3::from(*t.translation())
+                    Point3::from(t.translation())
                 }),
                 &map_transform.map_or(Vector3::new(0.0, 0.0, -1.0), |t| {
                     t.matrix().transform_vector(&Vector3::new(0.0, 0.0, -1.0))
                 }),
             );
-            let camera_transform = entry.get_component::<Transform>().unwrap();
+            let camera_transform = entry.get_component::<GlobalTransform>().unwrap();
             let camera = entry.get_component::<Camera>().unwrap();
             let dimensions = aux.resources.get::<ScreenDimensions>().unwrap();
             let w = dimensions.width();
@@ -328,9 +328,17 @@ impl<B: Backend, T: Tile, E: CoordinateEncoder, Z: DrawTiles2DBounds> RenderGrou
         let mut tilemap_args = vec![];
 
         let mut query =
-            <(&TileMap<T, E>, TryRead<Transform>)>::query().filter(!component::<Hidden>());
+            <(&TileMap<T, E>, TryRead<GlobalTransform>)>::query().filter(!component::<Hidden>());
+
+        // Skip the maps which the camera can't see at all.
+        let frustum = camera_frustum(aux);
+        let visible_maps = query.iter(aux.world).filter(|(tile_map, transform)| {
+            frustum.as_ref().map_or(true, |frustum| {
+                frustum.intersects(&tile_map.world_bounds(*transform))
+            })
+        });
 
-        for (tile_map, transform) in query.iter(aux.world) {
+        for (tile_map, transform) in visible_maps {
             if let Some(sheet) = tile_map
                 .sprite_sheet
                 .as_ref()
@@ -343,7 +351,7 @@ impl<B: Backend, T: Tile, E: CoordinateEncoder, Z: DrawTiles2DBounds> RenderGrou
                     let map_coordinate_transform: [[f32; 4]; 4] = (*tile_map.transform()).into();
                     let map_transform: [[f32; 4]; 4] = transform.map_or_else(
                         || Matrix4::identity().into(),
-                        |transform| (*transform.global_matrix()).into(),
+                        |transform| (*transform.matrix()).into(),
                     );
 
                     tilemap_args.push(TileMapArgs {
@@ -461,9 +469,18 @@ impl<B: Backend, T: Tile, E: CoordinateEncoder, Z: DrawTiles2DBounds> RenderGrou
     }
 }
 
+/// The frustum of the camera used to draw, or `None` if there is no camera.
+fn camera_frustum(aux: &GraphAuxData) -> Option<Frustum> {
+    let camera_entity = CameraGatherer::gather_camera_entity(aux.world, aux.resources)?;
+    let entry = aux.world.entry_ref(camera_entity).ok()?;
+    let camera = entry.get_component::<Camera>().ok()?;
+    let camera_transform = entry.get_component::<GlobalTransform>().ok()?;
+    Some(camera.frustum(camera_transform))
+}
+
 fn compute_region<T: Tile, E: CoordinateEncoder, Z: DrawTiles2DBounds>(
     tile_map: &TileMap<T, E>,
-    map_transform: Option<&Transform>,
+    map_transform: Option<&GlobalTransform>,
     aux: &GraphAuxData,
 ) -> Region {
     let mut region = Z::bounds(tile_map, map_transform, aux);
@@ -597,7 +614,7 @@ impl<B: Backend, T: Tile, E: CoordinateEncoder, Z: DrawTiles2DBounds> RenderPlug
 mod tests {
     // use amethyst_core::math::Point3;
     // use rayon::prelude::*;
-    use amethyst_core::ecs::world::WorldOptions;
+    use amethyst_core::{ecs::world::WorldOptions, transform::Transform};
     use amethyst_rendy::system::make_graph_aux_data;
 
     use super::*;
@@ -614,8 +631,7 @@ mod tests {
         dim_2d: Vector2<u32>,
         tile_size_2d: Vector2<u32>,
         cam_dim: Vector2<u32>,
-        cam_transform: Transform,
-        mut map_transform: Transform,
+        map_transform: Transform,
     ) -> Region {
         let map = TileMap::<TestTile, FlatEncoder>::new(
             Vector3::new(dim_2d.x, dim_2d.y, 1),
@@ -625,10 +641,10 @@ mod tests {
         let mut world = World::new(WorldOptions::default());
         #[allow(clippy::cast_precision_loss)]
         let camera = world.push((
-            cam_transform,
+            GlobalTransform::default(),
             Camera::standard_2d(cam_dim.x as f32, cam_dim.y as f32),
         ));
-        map_transform.copy_local_to_global();
+        let map_transform = GlobalTransform::from(map_transform.matrix());
         let map_entity = world.push((map, map_transform));
         let mut resources = Resources::default();
         resources.insert(ActiveCamera {
@@ -650,7 +666,6 @@ mod tests {
             Vector2::new(10, 10),                         // Number of tiles
             Vector2::new(50, 40),                         // Tile size
             Vector2::new(500, 400),                       // Camera size
-            Transform::from(Vector3::new(0.0, 0.0, 2.0)), // Camera transform
             Transform::from(Vector3::new(0.0, 0.0, 0.0)), // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(0, 0, 0));
@@ -661,7 +676,6 @@ mod tests {
             Vector2::new(10, 10),                               // Number of tiles
             Vector2::new(50, 40),                               // Tile size
             Vector2::new(500, 400),                             // Camera size
-            Transform::from(Vector3::new(0.0, 0.0, 2.0)),       // Camera transform
             Transform::from(Vector3::new(1000.0, -200.0, 0.0)), // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(0, 0, 0));
@@ -672,7 +686,6 @@ mod tests {
             Vector2::new(10, 10),                               // Number of tiles
             Vector2::new(50, 40),                               // Tile size
             Vector2::new(500, 400),                             // Camera size
-            Transform::from(Vector3::new(0.0, 0.0, 2.0)),       // Camera transform
             Transform::from(Vector3::new(-1000.0, 200.0, 0.0)), // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(10, 4, 0));
@@ -683,7 +696,6 @@ mod tests {
             Vector2::new(20, 15),                            // Number of tiles
             Vector2::new(50, 40),                            // Tile size
             Vector2::new(505, 405),                          // Camera size
-            Transform::from(Vector3::new(50.0, -20.0, 2.0)), // Camera transform
             Transform::from(Vector3::new(50.0, -20.0, 0.0)), // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(3, 1, 0));
@@ -693,11 +705,10 @@ mod tests {
         let mut map_transform = Transform::from(Vector3::new(50.0, -20.0, 0.0));
         map_transform.set_scale(Vector3::new(2.0, 0.5, 1.0));
         let region = bounds_camera_culling_region(
-            Vector2::new(10, 40),                            // Number of tiles
-            Vector2::new(50, 40),                            // Tile size
-            Vector2::new(505, 405),                          // Camera size
-            Transform::from(Vector3::new(50.0, -20.0, 2.0)), // Camera transform
-            map_transform,                                   // Tilemap transform
+            Vector2::new(10, 40),   // Number of tiles
+            Vector2::new(50, 40),   // Tile size
+            Vector2::new(505, 405), // Camera size
+            map_transform,          // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(1, 8, 0));
         assert_eq!(region.max, Point3::new(8, 30, 1));
@@ -706,11 +717,10 @@ mod tests {
         let mut map_transform = Transform::from(Vector3::new(50.0, -20.0, 0.0));
         map_transform.append_rotation_z_axis(std::f32::consts::PI / 4.0);
         let region = bounds_camera_culling_region(
-            Vector2::new(10, 40),                            // Number of tiles
-            Vector2::new(100, 25),                           // Tile size
-            Vector2::new(500, 250),                          // Camera size
-            Transform::from(Vector3::new(50.0, -20.0, 2.0)), // Camera transform
-            map_transform,                                   // Tilemap transform
+            Vector2::new(10, 40),   // Number of tiles
+            Vector2::new(100, 25),  // Tile size
+            Vector2::new(500, 250), // Camera size
+            map_transform,          // Tilemap transform
         );
         assert_eq!(region.min, Point3::new(1, 10, 0));
         assert_eq!(region.max, Point3::new(8, 32, 1));
@@ -6,6 +6,7 @@ use amethyst_assets::ProcessingQueue;
 use amethyst_core::{
     ecs::{DispatcherBuilder, Resources, SystemBundle, World},
     shrev::EventChannel,
+    NameIndexBundle,
 };
 use amethyst_error::Error;
 use amethyst_rendy::types::DefaultBackend;
@@ -25,7 +26,7 @@ use crate::{
     text::TextEditingMouseSystem,
     text_editing::TextEditingInputSystem,
     BlinkSystem, CachedSelectionOrderResource, UiButtonAction, UiEvent, UiLabel, UiPlaySoundAction,
-    WidgetId, Widgets,
+    UiTransform, WidgetId, Widgets,
 };
 
 /// UI bundle
@@ -110,7 +111,8 @@ where
             .add_system(TextEditingInputSystem::new(text_editing_input_reader))
             .add_system(ResizeSystem::new())
             .add_system(DragWidgetSystem::new(drag_widget_reader))
-            .add_system(BlinkSystem);
+            .add_system(BlinkSystem)
+            .add_bundle(NameIndexBundle::<UiTransform>::default());
 
         Ok(())
     }
@@ -1,7 +1,9 @@
 use amethyst_assets::prefab::{legion_prefab, register_component_type, serde_diff, SerdeDiff};
 use amethyst_core::{
     ecs::{Entity, EntityStore, IntoQuery},
+    entity_index::EntityName,
     transform::Parent,
+    NameIndex,
 };
 use amethyst_window::ScreenDimensions;
 use serde::{Deserialize, Serialize};
@@ -22,6 +24,13 @@ impl UiFinder {
             .map(|(e, _)| *e)
             .next()
     }
+
+    /// Find the `UiTransform` entity with the given id in the `NameIndex<UiTransform>` resource
+    /// added by the `UiBundle`, without going through all the UI entities.
+    #[must_use]
+    pub fn find_indexed(index: &NameIndex<UiTransform>, id: &str) -> Option<Entity> {
+        index.find(id)
+    }
 }
 
 /// The `UiTransform` represents the transformation of a ui element.
@@ -92,6 +101,12 @@ pub struct UiTransform {
 
 register_component_type!(UiTransform);
 
+impl EntityName for UiTransform {
+    fn entity_name(&self) -> &str {
+        &self.id
+    }
+}
+
 impl UiTransform {
     /// Creates a new `UiTransform`.
     /// By default, it is considered opaque.
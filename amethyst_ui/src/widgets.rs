@@ -8,8 +8,9 @@ use std::{
     ops::Index,
 };
 
+use amethyst_core::rng::with_global_rng;
 use derivative::Derivative;
-use rand::{self, distributions::Alphanumeric, Rng};
+use rand::{distributions::Alphanumeric, Rng};
 
 /// A widget is an object that keeps track of all components and entities
 /// that make up an element of the user interface. Using the `widget_components!`
@@ -49,12 +50,13 @@ impl WidgetId for u64 {
 
 impl WidgetId for String {
     fn generate(_: &Option<Self>) -> Self {
-        let mut rng = rand::thread_rng();
-        std::iter::repeat(())
-            .map(|()| rng.sample(Alphanumeric))
-            .map(char::from)
-            .take(16)
-            .collect()
+        with_global_rng(|rng| {
+            std::iter::repeat(())
+                .map(|()| rng.sample(Alphanumeric))
+                .map(char::from)
+                .take(16)
+                .collect()
+        })
     }
 }
 
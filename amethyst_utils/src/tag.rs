@@ -1,8 +1,15 @@
 //! Provides a small simple tag component for identifying entities.
 
-use std::marker::PhantomData;
+use std::{collections::HashSet, marker::PhantomData};
 
-use amethyst_core::ecs::{Entity, IntoQuery, Read, SubWorld};
+use amethyst_core::{
+    ecs::{
+        DispatcherBuilder, Entity, EntityStore, IntoQuery, ParallelRunnable, Read, Resources,
+        SubWorld, System, SystemBuilder, SystemBundle, World,
+    },
+    entity_index::ComponentEvents,
+};
+use amethyst_error::Error;
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 
@@ -48,3 +55,197 @@ where
             .next()
     }
 }
+
+/// Resource holding the entities tagged with `Tag<T>`.
+///
+/// It is maintained by the `TagIndexSystem<T>` added by the `TagIndexBundle<T>`, which only looks
+/// at the entities whose tag was added or removed, unlike the `TagFinder` querying all of them.
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""), Default(bound = ""))]
+pub struct TagIndex<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    entities: HashSet<Entity>,
+    #[derivative(Debug = "ignore")]
+    _m: PhantomData<T>,
+}
+
+impl<T> TagIndex<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Returns true if `entity` is tagged.
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// The tagged entities, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// The number of tagged entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns true if no entity is tagged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Updates the `TagIndex<T>` with the entities whose `Tag<T>` was added or removed.
+#[derive(Debug)]
+pub struct TagIndexSystem<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    events: ComponentEvents<Tag<T>>,
+}
+
+impl<T> TagIndexSystem<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Creates the system, indexing the entities reported by `events`.
+    #[must_use]
+    pub fn new(events: ComponentEvents<Tag<T>>) -> Self {
+        TagIndexSystem { events }
+    }
+}
+
+impl<T> System for TagIndexSystem<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn build(self) -> Box<dyn ParallelRunnable> {
+        let events = self.events;
+        Box::new(
+            SystemBuilder::new(format!("TagIndexSystem: {}", std::any::type_name::<T>()))
+                .write_resource::<TagIndex<T>>()
+                .read_component::<Tag<T>>()
+                .build(move |_, world, index, _| {
+                    for entity in events.drain() {
+                        let tagged = world
+                            .entry_ref(entity)
+                            .map_or(false, |entry| entry.get_component::<Tag<T>>().is_ok());
+                        if tagged {
+                            index.entities.insert(entity);
+                        } else {
+                            index.entities.remove(&entity);
+                        }
+                    }
+                }),
+        )
+    }
+}
+
+/// Adds the `TagIndex<T>` resource and the `TagIndexSystem<T>` maintaining it.
+///
+/// Entities tagged before the bundle is loaded are indexed when it is loaded.
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""), Default(bound = ""))]
+pub struct TagIndexBundle<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    #[derivative(Debug = "ignore")]
+    _m: PhantomData<T>,
+}
+
+impl<T> SystemBundle for TagIndexBundle<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn load(
+        &mut self,
+        world: &mut World,
+        resources: &mut Resources,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), Error> {
+        // The system only sees the tags added from now on, the ones already there are indexed here.
+        let mut index = TagIndex::<T>::default();
+        index.entities = <(Entity, Read<Tag<T>>)>::query()
+            .iter(world)
+            .map(|(entity, _)| *entity)
+            .collect();
+        resources.insert(index);
+        builder.add_system(TagIndexSystem::new(ComponentEvents::subscribe(world)));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use amethyst_core::ecs::Dispatcher;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Player;
+
+    #[test]
+    fn tag_index_follows_tags() {
+        let mut resources = Resources::default();
+        let mut world = World::default();
+        let mut dispatcher: Dispatcher = DispatcherBuilder::default()
+            .add_bundle(TagIndexBundle::<Player>::default())
+            .build(&mut world, &mut resources)
+            .unwrap();
+
+        let player = world.push((Tag::<Player>::default(),));
+        let other = world.push((Tag::<Player>::default(), 1_u32));
+        let untagged = world.push((2_u32,));
+        dispatcher.execute(&mut world, &mut resources);
+        {
+            let index = resources.get::<TagIndex<Player>>().unwrap();
+            assert!(index.contains(player) && index.contains(other));
+            assert!(!index.contains(untagged));
+        }
+
+        world
+            .entry(other)
+            .unwrap()
+            .remove_component::<Tag<Player>>();
+        world
+            .entry(untagged)
+            .unwrap()
+            .add_component(Tag::<Player>::default());
+        world.remove(player);
+        dispatcher.execute(&mut world, &mut resources);
+        let index = resources.get::<TagIndex<Player>>().unwrap();
+        assert_eq!(index.iter().collect::<Vec<_>>(), vec![untagged]);
+    }
+
+    #[test]
+    fn tag_index_includes_entities_tagged_before_load() {
+        let mut resources = Resources::default();
+        let mut world = World::default();
+        let player = world.push((Tag::<Player>::default(),));
+        let untagged = world.push((1_u32,));
+
+        let mut dispatcher: Dispatcher = DispatcherBuilder::default()
+            .add_bundle(TagIndexBundle::<Player>::default())
+            .build(&mut world, &mut resources)
+            .unwrap();
+        dispatcher.execute(&mut world, &mut resources);
+        {
+            let index = resources.get::<TagIndex<Player>>().unwrap();
+            assert_eq!(index.iter().collect::<Vec<_>>(), vec![player]);
+        }
+
+        world.remove(player);
+        world
+            .entry(untagged)
+            .unwrap()
+            .add_component(Tag::<Player>::default());
+        dispatcher.execute(&mut world, &mut resources);
+        let index = resources.get::<TagIndex<Player>>().unwrap();
+        assert_eq!(index.iter().collect::<Vec<_>>(), vec![untagged]);
+    }
+}
@@ -5,9 +5,9 @@ use amethyst::{
         AssetStorage, DefaultLoader, Handle, Loader, LoaderBundle, Progress, ProgressCounter,
     },
     core::{
-        geometry::Plane,
+        geometry::{Plane, Rect},
         math::{Point2, Vector2, Vector3},
-        transform::{Transform, TransformBundle},
+        transform::{GlobalTransform, Transform, TransformBundle},
         Named,
     },
     ecs::{
@@ -35,7 +35,7 @@ impl System for MouseRaycastSystem {
     fn build(self) -> Box<dyn ParallelRunnable> {
         Box::new(
             SystemBuilder::new("MouseRaycastSystem")
-                .with_query(<(&Camera, &Transform)>::query())
+                .with_query(<(&Camera, &GlobalTransform)>::query())
                 .with_query(<(&SpriteRender, &Transform, &Named)>::query())
                 .with_query(<(&UiTransform, &mut UiText)>::query())
                 .read_resource::<InputHandler>()
@@ -87,22 +87,14 @@ impl System for MouseRaycastSystem {
                                     let sprites =
                                         sprites_storage.get(&sprite_sheet.sprites).unwrap();
                                     let sprite = &sprites.build_sprites()[sprite.sprite_number];
-                                    let (min_x, max_x, min_y, max_y) = {
-                                        // Sprites are centered on a coordinate, so we build out a bbox for the sprite coordinate
-                                        // and dimensions
-                                        // Notice we ignore z-axis for this example.
-                                        (
-                                            transform.translation().x - (sprite.width * 0.5),
-                                            transform.translation().x + (sprite.width * 0.5),
-                                            transform.translation().y - (sprite.height * 0.5),
-                                            transform.translation().y + (sprite.height * 0.5),
-                                        )
-                                    };
-                                    if mouse_world_position.x > min_x
-                                        && mouse_world_position.x < max_x
-                                        && mouse_world_position.y > min_y
-                                        && mouse_world_position.y < max_y
-                                    {
+                                    // Sprites are centered on a coordinate, so we build out a bbox for the sprite coordinate
+                                    // and dimensions
+                                    // Notice we ignore z-axis for this example.
+                                    let bounds = Rect::from_center_half_extents(
+                                        Point2::from(transform.translation().xy()),
+                                        Vector2::new(sprite.width, sprite.height) * 0.5,
+                                    );
+                                    if bounds.contains_point(&mouse_world_position.xy()) {
                                         found_name = Some(&name.0);
                                     }
                                 }
@@ -2,7 +2,8 @@
 use amethyst::{
     assets::{DefaultLoader, Handle, Loader, LoaderBundle, ProcessingQueue},
     core::{
-        transform::{Transform, TransformBundle},
+        math::Vector2,
+        transform::{Transform, Transform2D, TransformBundle},
         Hidden,
     },
     ecs::{Entity, World},
@@ -243,14 +244,13 @@ impl Example {
         let sprite_offset_translation_x =
             (sprite_count * sprite_w) as f32 * SPRITE_SPACING_RATIO / 2.;
 
-        // This `Transform` moves the sprites to the middle of the window
-        let mut common_transform = Transform::default();
-        common_transform.set_translation_xyz(-sprite_offset_translation_x, 0.0, 0.0);
+        // This offset moves the sprites to the middle of the window
+        let common_translation = Vector2::new(-sprite_offset_translation_x, 0.0);
 
-        self.draw_sprites(world, &common_transform);
+        self.draw_sprites(world, common_translation);
     }
 
-    fn draw_sprites(&mut self, world: &mut World, common_transform: &Transform) {
+    fn draw_sprites(&mut self, world: &mut World, common_translation: Vector2<f32>) {
         let LoadedSpriteSheet {
             sprite_sheet_handle,
             sprite_count,
@@ -264,21 +264,18 @@ impl Example {
 
         // Create an entity per sprite.
         for i in 0..sprite_count {
-            let mut sprite_transform = Transform::default();
+            // 2D entities only need a `Transform2D`, its layer orders the sprites.
+            let mut sprite_transform = Transform2D::default();
 
             let z = if self.reverse {
                 (sprite_count - i - 1) as f32
             } else {
                 i as f32
             };
-            sprite_transform.set_translation_xyz(
-                (i * sprite_w) as f32 * SPRITE_SPACING_RATIO,
-                z,
-                -z,
-            );
-
-            // This combines multiple `Transform`ations.
-            sprite_transform.concat(common_transform);
+            sprite_transform
+                .set_translation_xy((i * sprite_w) as f32 * SPRITE_SPACING_RATIO, z)
+                .set_layer(-z)
+                .append_translation(common_translation);
 
             let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), i as usize);
 
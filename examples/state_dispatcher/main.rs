@@ -1,14 +1,6 @@
-//! An example showing how to create a dispatcher inside a State.
+//! An example showing how to restrict systems to a specific `State` with run conditions.
 
-use std::marker::PhantomData;
-
-use amethyst::{
-    ecs::{Dispatcher, DispatcherBuilder},
-    prelude::*,
-    shrev::EventChannel,
-    utils::application_root_dir,
-    Error,
-};
+use amethyst::{core::shrev::EventChannel, in_state, prelude::*, utils::application_root_dir};
 
 struct StateA;
 
@@ -16,47 +8,48 @@ impl SimpleState for StateA {
     fn update(&mut self, data: &mut StateData<'_, GameData>) -> SimpleTrans {
         println!("StateA::update()");
         // Shows how to push a `Trans` through the event queue.
-        // If you do use TransQueue, you will be forced to use the 'static lifetime on your states.
-        data.world
-            .write_resource::<EventChannel<TransEvent<GameData<'static, 'static>, StateEvent>>>()
-            .single_write(Box::new(|| Trans::Push(Box::new(StateB::default()))));
+        data.resources
+            .get_mut::<EventChannel<TransEvent<GameData, StateEvent>>>()
+            .unwrap()
+            .single_write(Box::new(|| Trans::Push(Box::new(StateB))));
 
         // You can also use normal Trans at the same time!
         // Those will be executed before the ones in the EventChannel
-        // Trans::Push(Box::new(StateB::default()))
+        // Trans::Push(Box::new(StateB))
 
         Trans::None
     }
 }
 
-/// StateB isn't Send + Sync
-struct StateB<'a> {
-    dispatcher: Dispatcher,
-    _phantom: &'a PhantomData<()>,
-}
+struct StateB;
 
-impl<'a> Default for StateB<'a> {
-    fn default() -> Self {
-        StateB {
-            dispatcher: DispatcherBuilder::new().build(),
-            _phantom: &PhantomData,
-        }
+impl SimpleState for StateB {
+    fn update(&mut self, _data: &mut StateData<'_, GameData>) -> SimpleTrans {
+        println!("StateB::update()");
+        Trans::Quit
     }
 }
 
-impl<'a> SimpleState for StateB<'a> {
-    fn update(&mut self, data: &mut StateData<'_, GameData>) -> SimpleTrans {
-        println!("StateB::update()");
-        self.dispatcher.dispatch(&data.world);
-        Trans::Quit
+/// Only runs while `StateB` is the active state.
+struct StateBSystem;
+
+impl System for StateBSystem {
+    fn build(self) -> Box<dyn ParallelRunnable> {
+        Box::new(SystemBuilder::new("StateBSystem").build(|_, _, _, _| {
+            println!("StateBSystem::run()");
+        }))
     }
 }
 
-fn main() -> Result<(), Error> {
+fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
     let app_root = application_root_dir()?;
     let assets_dir = app_root.join("assets");
-    let game = Application::build(assets_dir, StateA)?.build(DispatcherBuilder::default())?;
+
+    let mut dispatcher = DispatcherBuilder::default();
+    dispatcher.add_system_if(StateBSystem, in_state::<StateB>());
+
+    let game = Application::build(assets_dir, StateA)?.build(dispatcher)?;
     game.run();
     Ok(())
 }
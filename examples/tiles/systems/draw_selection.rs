@@ -1,7 +1,7 @@
 use amethyst::{
     core::{
         math::{Point3, Vector2},
-        transform::Transform,
+        transform::GlobalTransform,
     },
     ecs::{Entity, IntoQuery, ParallelRunnable, System, SystemBuilder},
     input::InputHandler,
@@ -21,7 +21,7 @@ impl System for DrawSelectionSystem {
                 .read_resource::<ScreenDimensions>()
                 .read_resource::<InputHandler>()
                 .with_query(<&mut DebugLinesComponent>::query())
-                .with_query(<(Entity, &Camera, &Transform)>::query())
+                .with_query(<(Entity, &Camera, &GlobalTransform)>::query())
                 .build(
                     move |_commands,
                           world,
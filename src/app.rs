@@ -2,15 +2,20 @@
 
 use std::{
     env,
+    fmt::Debug,
     marker::PhantomData,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
 use derivative::Derivative;
-use log::{debug, info, log_enabled, trace, Level};
+use log::{debug, error, info, log_enabled, trace, Level};
 use rayon::ThreadPoolBuilder;
+use serde::{de::DeserializeOwned, Serialize};
 #[cfg(feature = "profiler")]
 use thread_profiler::{profile_scope, register_thread_with_profiler, write_profile};
 use winit::event::{Event, WindowEvent};
@@ -18,15 +23,24 @@ use winit::event::{Event, WindowEvent};
 #[cfg(feature = "asset-daemon")]
 use crate::assets::AssetDaemon;
 use crate::{
-    assets::{DefaultLoader, Source},
+    assets::{Directory, LoaderMode, Source, Sources},
     core::{
         frame_limiter::{FrameLimiter, FrameRateLimitConfig, FrameRateLimitStrategy},
+        rng::{self, EngineRng},
         shrev::{EventChannel, ReaderId},
+        transform::{
+            interpolate_simulated_transforms, record_simulated_transforms,
+            restore_simulated_transforms,
+        },
         ArcThreadPool, EventReader, Stopwatch, Time,
     },
-    ecs::{Resource, Resources, World},
+    ecs::{Dispatcher, DispatcherBuilder, Resource, Resources, World},
     error::Error,
     game_data::{DataDispose, DataInit},
+    replay::{
+        EventRecorder, EventRecording, EventReplay, EventTap, RecordedStateEvent,
+        StateEventRecorder, StateEventReplay,
+    },
     state::{State, StateData, StateMachine, TransEvent},
     state_event::{StateEvent, StateEventReader},
 };
@@ -59,15 +73,22 @@ where
     #[derivative(Debug = "ignore")]
     events: Vec<E>,
     #[derivative(Debug = "ignore")]
-    event_reader_id: ReaderId<Event<'static, ()>>,
+    event_reader_id: Option<ReaderId<Event<'static, ()>>>,
     #[derivative(Debug = "ignore")]
     trans_reader_id: ReaderId<TransEvent<T, E>>,
     states: StateMachine<'a, T, E>,
     ignore_window_close: bool,
+    headless: bool,
+    shutdown_requested: Arc<AtomicBool>,
     data: T,
+    #[derivative(Debug = "ignore")]
+    fixed_dispatcher: Option<Dispatcher>,
+    frame_delta: Option<Duration>,
+    event_taps: Vec<Box<dyn EventTap>>,
+    started: bool,
     #[cfg(feature = "asset-daemon")]
     #[derivative(Debug = "ignore")]
-    asset_daemon: AssetDaemon,
+    asset_daemon: Option<AssetDaemon>,
 }
 
 /// An Application is the root object of the game engine. It binds the OS
@@ -221,6 +242,9 @@ where
     /// `Trans::Pop` on the last state in from the stack. See full
     /// documentation on this in [State](trait.State.html) documentation.
     ///
+    /// An application already advanced with [`step`](CoreApplication::step) continues from its
+    /// current frame, without starting its states again.
+    ///
     /// # Examples
     ///
     /// See the example supplied in the
@@ -232,9 +256,12 @@ where
             Some(guard)
         });
 
-        self.initialize();
-
-        self.resources.get_mut::<Stopwatch>().unwrap().start();
+        if !self.started {
+            self.initialize();
+        } else if !self.states.is_running() {
+            // `step` already ran the application to its end and shut it down.
+            return;
+        }
 
         while self.states.is_running() {
             self.advance_frame();
@@ -243,22 +270,96 @@ where
                 profile_scope!("frame_limiter wait");
                 self.resources.get_mut::<FrameLimiter>().unwrap().wait();
             }
-            {
-                let mut stopwatch = self.resources.get_mut::<Stopwatch>().unwrap();
-                let elapsed = stopwatch.elapsed();
-                let mut time = self.resources.get_mut::<Time>().unwrap();
-                time.advance_frame(elapsed);
-                stopwatch.stop();
-                stopwatch.restart();
-            }
+            self.advance_time();
         }
         self.shutdown();
     }
 
+    /// Advances the application by exactly one frame, starting it on the first call.
+    ///
+    /// Unlike [`run`](CoreApplication::run) this never waits for the frame limiter, so tests can
+    /// drive the application frame by frame. Together with
+    /// [`ApplicationBuilder::with_frame_delta`] and [`ApplicationBuilder::with_seed`] every call
+    /// advances the world in the same way on every run.
+    ///
+    /// Once the states stop running, the application is shut down and `step` keeps returning
+    /// `false`. A [`headless`](ApplicationBuilder::headless) application installs its termination
+    /// signal handler on the first call, so the next call after a SIGINT or SIGTERM shuts it down.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use amethyst::prelude::*;
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let mut game = Application::build("assets/", GameState)?
+    ///     .headless(60)
+    ///     .with_frame_delta(Duration::from_millis(16))
+    ///     .with_seed(42)
+    ///     .build(DispatcherBuilder::default())?;
+    ///
+    /// for _ in 0..10 {
+    ///     game.step();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn step(&mut self) -> bool {
+        if !self.started {
+            self.initialize();
+        }
+        if !self.states.is_running() {
+            return false;
+        }
+
+        self.advance_frame();
+        self.advance_time();
+
+        if self.states.is_running() {
+            true
+        } else {
+            self.shutdown();
+            false
+        }
+    }
+
+    /// The world of the application.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// The world of the application.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// The resources of the application.
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    /// The resources of the application.
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
     /// Sets up the application.
     fn initialize(&mut self) {
+        self.started = true;
+
+        if self.headless {
+            self.install_signal_handler();
+        }
+
         #[cfg(feature = "asset-daemon")]
-        self.asset_daemon.start_on_new_thread();
+        if let Some(daemon) = &mut self.asset_daemon {
+            daemon.start_on_new_thread();
+        }
 
         #[cfg(feature = "profiler")]
         profile_scope!("initialize");
@@ -269,14 +370,45 @@ where
                 &mut self.data,
             ))
             .expect("Tried to start state machine without any states present");
+
+        self.resources.get_mut::<Stopwatch>().unwrap().start();
     }
 
-    // React to window close events
+    // Advances `Time` by the fixed frame delta if there is one, by the time the frame took otherwise.
+    fn advance_time(&mut self) {
+        let mut stopwatch = self.resources.get_mut::<Stopwatch>().unwrap();
+        let elapsed = self.frame_delta.unwrap_or_else(|| stopwatch.elapsed());
+        let mut time = self.resources.get_mut::<Time>().unwrap();
+        time.advance_frame(elapsed);
+        stopwatch.stop();
+        stopwatch.restart();
+    }
+
+    // Stops the application on SIGINT or SIGTERM.
+    #[cfg(feature = "ctrlc")]
+    fn install_signal_handler(&self) {
+        let shutdown_requested = Arc::clone(&self.shutdown_requested);
+        let result = ctrlc::set_handler(move || {
+            info!("Received termination signal");
+            shutdown_requested.store(true, Ordering::SeqCst);
+        });
+        if let Err(err) = result {
+            log::warn!("Failed to install the termination signal handler: {}", err);
+        }
+    }
+
+    #[cfg(not(feature = "ctrlc"))]
+    fn install_signal_handler(&self) {
+        debug!("Termination signals are not handled, enable the `server` feature to handle them");
+    }
+
+    // React to window close events, or to termination signals in headless mode
     fn should_close(&mut self) -> bool {
-        if self.ignore_window_close {
+        if self.headless {
+            self.shutdown_requested.load(Ordering::SeqCst)
+        } else if self.ignore_window_close {
             false
-        } else {
-            let reader_id = &mut self.event_reader_id;
+        } else if let Some(reader_id) = &mut self.event_reader_id {
             self.resources
                 .get_mut::<EventChannel<Event<'_, ()>>>()
                 .unwrap()
@@ -300,6 +432,8 @@ where
                         )
                     }
                 })
+        } else {
+            false
         }
     }
 
@@ -334,6 +468,9 @@ where
             #[cfg(feature = "profiler")]
             profile_scope!("handle_event");
 
+            for tap in &mut self.event_taps {
+                tap.begin_frame(resources);
+            }
             self.reader.read(resources, &mut self.events);
 
             for e in self.events.drain(..) {
@@ -345,6 +482,7 @@ where
             #[cfg(feature = "profiler")]
             profile_scope!("fixed_update");
 
+            restore_simulated_transforms(&mut self.world);
             while self
                 .resources
                 .get_mut::<Time>()
@@ -356,11 +494,20 @@ where
                     &mut self.resources,
                     &mut self.data,
                 ));
+                if let Some(dispatcher) = &mut self.fixed_dispatcher {
+                    dispatcher.execute(&mut self.world, &mut self.resources);
+                }
+                record_simulated_transforms(&mut self.world);
             }
+            let alpha = self.resources.get::<Time>().unwrap().interpolation_alpha();
+            interpolate_simulated_transforms(&mut self.world, alpha);
         }
         {
             #[cfg(feature = "profiler")]
             profile_scope!("update");
+            for tap in &mut self.event_taps {
+                tap.before_update(&mut self.resources);
+            }
             self.states.update(StateData::new(
                 &mut self.world,
                 &mut self.resources,
@@ -377,10 +524,20 @@ where
     /// Cleans up after the quit signal is received.
     fn shutdown(&mut self) {
         #[cfg(feature = "asset-daemon")]
-        self.asset_daemon.stop_and_join();
+        if let Some(daemon) = &mut self.asset_daemon {
+            daemon.stop_and_join();
+        }
 
         info!("Engine is shutting down");
+        for tap in &mut self.event_taps {
+            tap.finish(&mut self.resources);
+        }
         self.data.dispose(&mut self.world, &mut self.resources);
+        if let Some(dispatcher) = self.fixed_dispatcher.take() {
+            if let Err(err) = dispatcher.unload(&mut self.world, &mut self.resources) {
+                error!("Failed to unload the fixed update dispatcher: {}", err);
+            }
+        }
     }
 }
 
@@ -411,6 +568,10 @@ pub struct ApplicationBuilder<S, T, E, R> {
     /// Used by bundles to initialize any resources in the world
     pub resources: Resources,
     ignore_window_close: bool,
+    headless: bool,
+    fixed_dispatcher: Option<DispatcherBuilder>,
+    frame_delta: Option<Duration>,
+    event_taps: Vec<Box<dyn EventTap>>,
     #[allow(dead_code)]
     asset_dirs: Vec<PathBuf>,
     phantom: PhantomData<(T, E, R)>,
@@ -535,6 +696,8 @@ where
         resources.insert(FrameLimiter::default());
         resources.insert(Stopwatch::default());
         resources.insert(Time::default());
+        resources.insert(EngineRng::default());
+        resources.insert(Sources::new(Directory::new(path.as_ref())));
 
         let asset_dirs = vec![path.as_ref().to_path_buf()];
 
@@ -543,6 +706,10 @@ where
             world,
             resources,
             ignore_window_close: false,
+            headless: false,
+            fixed_dispatcher: None,
+            frame_delta: None,
+            event_taps: Vec::new(),
             phantom: PhantomData,
             asset_dirs,
         })
@@ -604,6 +771,11 @@ where
     /// effect will be a replacement of the older store with the new one.
     /// No warning or panic will result from this action.
     ///
+    /// Stores are kept in the `Sources` resource, and assets are loaded from them by name with
+    /// `Loader::load_from` only. `Loader::load` and the other loads going through the asset
+    /// daemon don't use them, the daemon imports the asset directory the application was built
+    /// with.
+    ///
     /// # Parameters
     ///
     /// - `name`: A unique name or key to identify the asset storage location. `name`
@@ -623,15 +795,15 @@ where
     ///
     /// ```no_run
     /// use amethyst::{
-    ///     assets::{DefaultLoader, Directory, Handle, Loader},
+    ///     assets::{DefaultLoader, Directory, Handle, Loader, ProcessingQueue, Sources},
     ///     prelude::*,
-    ///     renderer::{formats::mesh::ObjFormat, Mesh},
+    ///     renderer::{formats::mesh::ObjFormat, types::MeshData, Mesh},
     /// };
     ///
     /// # fn main() -> amethyst::Result<()> {
     /// let assets_dir = "assets/";
     /// let game = Application::build(assets_dir, LoadingState)?
-    ///     // Register the directory "custom_directory" under the name "resources".
+    ///     // Register the directory "custom_directory" under the name "custom_store".
     ///     .with_source("custom_store", Directory::new("custom_directory"))
     ///     .build(DispatcherBuilder::default())?
     ///     .run();
@@ -642,26 +814,32 @@ where
     /// impl SimpleState for LoadingState {
     ///     fn on_start(&mut self, data: StateData<'_, GameData>) {
     ///         let loader = data.resources.get::<DefaultLoader>().unwrap();
+    ///         let sources = data.resources.get::<Sources>().unwrap();
+    ///         let queue = data.resources.get::<ProcessingQueue<MeshData>>().unwrap();
     ///         // Load a teapot mesh from the directory that registered above.
-    ///         let mesh: Handle<Mesh> = loader.load("teapot.obj");
+    ///         let mesh: Handle<Mesh> = loader
+    ///             .load_from("teapot.obj", ObjFormat, "custom_store", &sources, (), &queue)
+    ///             .expect("Failed to load teapot");
     ///     }
     /// }
     /// ```
-    pub fn with_source<I, O>(self, _name: I, _store: O) -> Self
+    pub fn with_source<I, O>(mut self, name: I, store: O) -> Self
     where
         I: Into<String>,
         O: Source,
     {
-        {
-            let _loader = self.resources.get_mut::<DefaultLoader>().unwrap();
-            // FIXME Update the source on the loader
-            // loader.add_source(name, store);
-        }
+        self.resources
+            .get_mut_or_default::<Sources>()
+            .insert(name, store);
         self
     }
 
     /// Registers the default asset store with the loader logic of the Application.
     ///
+    /// The default store is registered as `DEFAULT_SOURCE` in the `Sources` resource, replacing
+    /// the asset directory the application was built with for `Loader::load_from` only. The asset
+    /// daemon still imports the asset directory the application was built with.
+    ///
     /// # Parameters
     ///
     /// - `store`: The asset store being registered.
@@ -678,9 +856,12 @@ where
     ///
     /// ```no_run
     /// use amethyst::{
-    ///     assets::{DefaultLoader, Directory, Handle, Loader, LoaderBundle},
+    ///     assets::{
+    ///         DefaultLoader, Directory, Handle, Loader, LoaderBundle, ProcessingQueue, Sources,
+    ///         DEFAULT_SOURCE,
+    ///     },
     ///     prelude::*,
-    ///     renderer::Mesh,
+    ///     renderer::{formats::mesh::ObjFormat, types::MeshData, Mesh},
     /// };
     ///
     /// # fn main() -> amethyst::Result<()> {
@@ -700,20 +881,59 @@ where
     /// impl SimpleState for LoadingState {
     ///     fn on_start(&mut self, data: StateData<'_, GameData>) {
     ///         let loader = data.resources.get::<DefaultLoader>().unwrap();
+    ///         let sources = data.resources.get::<Sources>().unwrap();
+    ///         let queue = data.resources.get::<ProcessingQueue<MeshData>>().unwrap();
     ///         // Load a teapot mesh from the directory that registered above.
-    ///         let mesh: Handle<Mesh> = loader.load("teapot.obj");
+    ///         let mesh: Handle<Mesh> = loader
+    ///             .load_from("teapot.obj", ObjFormat, DEFAULT_SOURCE, &sources, (), &queue)
+    ///             .expect("Failed to load teapot");
     ///     }
     /// }
     /// ```
-    pub fn with_default_source<O>(self, _store: O) -> Self
+    pub fn with_default_source<O>(mut self, store: O) -> Self
     where
         O: Source,
     {
-        {
-            // let _loader = self.resources.get_mut::<DefaultLoader>().unwrap();
-            // FIXME Update the location on the loader
-            // loader.set_default_source(store);
-        }
+        self.resources
+            .get_mut_or_default::<Sources>()
+            .set_default(store);
+        self
+    }
+
+    /// Loads every asset from a packfile instead of the asset daemon, which is then not started.
+    ///
+    /// This is how an application is usually shipped: the packfile is built ahead of time with
+    /// `amethyst_assets::build_packfile`, or the `amethyst_packfile` binary of the
+    /// `asset-packfile` feature, and assets are no longer reloaded when they change.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the packfile.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `ApplicationBuilder` after it has modified it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use amethyst::{assets::LoaderBundle, prelude::*};
+    ///
+    /// # struct LoadingState;
+    /// # impl SimpleState for LoadingState {}
+    /// # fn main() -> amethyst::Result<()> {
+    /// let mut dispatcher = DispatcherBuilder::default();
+    /// dispatcher.add_bundle(LoaderBundle);
+    /// let game = Application::build("assets/", LoadingState)?
+    ///     .with_packfile("assets.pack")
+    ///     .build(dispatcher)?;
+    /// game.run();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_packfile<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.resources
+            .insert(LoaderMode::Packfile(path.as_ref().to_path_buf()));
         self
     }
 
@@ -757,6 +977,8 @@ where
     /// This function returns the `ApplicationBuilder` after modifying it.
     ///
     /// # Panics
+    ///
+    /// Panics if `duration` is zero.
     pub fn with_fixed_step_length(self, duration: Duration) -> Self {
         self.resources
             .get_mut::<Time>()
@@ -765,6 +987,79 @@ where
         self
     }
 
+    /// Sets the maximum number of fixed updates run during a single frame, defaults to 5.
+    ///
+    /// When a frame takes longer than this many fixed steps, the remaining steps are dropped
+    /// instead of making the next frame even longer.
+    ///
+    /// # Parameters
+    ///
+    /// `max_steps`: The maximum number of fixed updates per frame.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_steps` is zero.
+    pub fn with_max_fixed_steps_per_frame(self, max_steps: u32) -> Self {
+        self.resources
+            .get_mut::<Time>()
+            .unwrap()
+            .fixed_step_mut()
+            .set_max_steps_per_frame(max_steps);
+        self
+    }
+
+    /// Sets the dispatcher executed on every fixed update, right after
+    /// [`State::fixed_update`](crate::State::fixed_update).
+    ///
+    /// Systems of this dispatcher run zero or more times per frame, at the rate set by
+    /// [`with_fixed_step_length`](ApplicationBuilder::with_fixed_step_length) in the scaled time
+    /// of [`Time`], which makes it the place for gameplay and physics systems. Entities they move
+    /// can be rendered smoothly with a
+    /// [`TransformInterpolation`](crate::core::transform::TransformInterpolation): after the
+    /// fixed updates, the application moves them to their interpolated pose, whose global matrix
+    /// is then computed by the `TransformBundle` of the main dispatcher.
+    ///
+    /// # Parameters
+    ///
+    /// `dispatcher`: The builder of the fixed update dispatcher.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use amethyst::{core::transform::TransformBundle, prelude::*};
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let mut dispatcher = DispatcherBuilder::default();
+    /// dispatcher.add_bundle(TransformBundle);
+    ///
+    /// let mut fixed_dispatcher = DispatcherBuilder::default();
+    /// // fixed_dispatcher.add_system(PhysicsSystem);
+    ///
+    /// let game = Application::build("assets/", GameState)?
+    ///     .with_fixed_step_length(Duration::from_millis(20))
+    ///     .with_fixed_dispatcher(fixed_dispatcher)
+    ///     .build(dispatcher)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fixed_dispatcher(mut self, dispatcher: DispatcherBuilder) -> Self {
+        self.fixed_dispatcher = Some(dispatcher);
+        self
+    }
+
     /// Tells the resulting application window to ignore close events if ignore is true.
     /// This will make your game window unresponsive to operating system close commands.
     /// Use with caution.
@@ -781,6 +1076,280 @@ where
         self
     }
 
+    /// Runs the application without a window, for dedicated servers and tools.
+    ///
+    /// The application ticks `tick_rate` times per second, sleeping in between with the
+    /// [`FrameLimiter`], and never reads window events. It stops when a `State` returns
+    /// `Trans::Quit`, when a `Trans::Quit` is sent through the `TransEvent` channel, or, with the
+    /// `server` feature enabled, when the process receives SIGINT or SIGTERM.
+    ///
+    /// Don't add the window or rendering bundles to a headless application.
+    ///
+    /// # Parameters
+    ///
+    /// `tick_rate`: The number of frames per second. Calling
+    /// [`with_frame_limit`](ApplicationBuilder::with_frame_limit) afterwards overrides it.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use amethyst::prelude::*;
+    ///
+    /// struct ServerState;
+    /// impl SimpleState for ServerState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let game = Application::build("assets/", ServerState)?
+    ///     .headless(30)
+    ///     .build(DispatcherBuilder::default())?;
+    /// game.run();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn headless(mut self, tick_rate: u32) -> Self {
+        self.headless = true;
+        self.resources
+            .insert(FrameLimiter::new(FrameRateLimitStrategy::Sleep, tick_rate));
+        self
+    }
+
+    /// Advances `Time` by exactly `delta` every frame instead of the time the frame took.
+    ///
+    /// Use it with [`CoreApplication::step`] to make tests independent of the speed of the
+    /// machine running them. The frame limiter still applies when the application is
+    /// [`run`](CoreApplication::run).
+    ///
+    /// # Parameters
+    ///
+    /// `delta`: The duration of every frame.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    pub fn with_frame_delta(mut self, delta: Duration) -> Self {
+        self.frame_delta = Some(delta);
+        self
+    }
+
+    /// Seeds the [`EngineRng`] resource and the other random number generators of the engine.
+    ///
+    /// This reseeds the process-wide generator of [`rng::seed_global`] as well, which engine code
+    /// without access to the resources draws from, e.g. to generate widget ids. Without a seed,
+    /// they are seeded from entropy and every run is different.
+    ///
+    /// # Parameters
+    ///
+    /// `seed`: The seed of the random number generators.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        rng::seed_global(seed);
+        self.resources.insert(EngineRng::seed_from(seed));
+        self
+    }
+
+    /// Records the events written to the `EventChannel<V>` every frame, and saves them to `path`
+    /// when the application shuts down.
+    ///
+    /// The recording also stores the seed of the [`EngineRng`] and the frame delta, so
+    /// [`with_event_replay`](ApplicationBuilder::with_event_replay) can play the same run again.
+    /// Record every channel of custom events that drives the game. Record the input with
+    /// [`with_state_event_recording`](ApplicationBuilder::with_state_event_recording) instead, so
+    /// its replay updates the `InputHandler`.
+    ///
+    /// # Parameters
+    ///
+    /// `path`: The RON file the recording is saved to.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use amethyst::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Clone, Debug, Serialize, Deserialize)]
+    /// struct Jump;
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let game = Application::build("assets/", GameState)?
+    ///     .with_frame_delta(Duration::from_millis(16))
+    ///     .with_event_recording::<Jump, _>("jumps.ron")
+    ///     .build(DispatcherBuilder::default())?;
+    /// game.run();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_event_recording<V, P>(mut self, path: P) -> Self
+    where
+        V: Clone + Debug + Send + Sync + Serialize + DeserializeOwned + 'static,
+        P: AsRef<Path>,
+    {
+        self.event_taps
+            .push(Box::new(EventRecorder::<V>::new(path.as_ref())));
+        self
+    }
+
+    /// Writes the events of `recording` to the `EventChannel<V>`, in the frames they were
+    /// recorded in, and uses the seed and frame delta of the recorded run.
+    ///
+    /// Replaying the recordings of all channels in an application built with the same states and
+    /// systems reproduces the recorded world.
+    ///
+    /// # Parameters
+    ///
+    /// `recording`: The recording to replay, loaded with [`EventRecording::load`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use amethyst::{prelude::*, EventRecording};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Clone, Debug, Serialize, Deserialize)]
+    /// struct Jump;
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let recording = EventRecording::<Jump>::load("jumps.ron")?;
+    /// let mut game = Application::build("assets/", GameState)?
+    ///     .headless(60)
+    ///     .with_event_replay(recording)
+    ///     .build(DispatcherBuilder::default())?;
+    /// while game.step() {}
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_event_replay<V>(mut self, recording: EventRecording<V>) -> Self
+    where
+        V: Debug + Send + Sync + 'static,
+    {
+        self = self.with_recorded_run(&recording);
+        self.event_taps.push(Box::new(EventReplay::new(recording)));
+        self
+    }
+
+    /// Records the events the states handle every frame, and saves them to `path` when the
+    /// application shuts down.
+    ///
+    /// The recording holds the window events that change the input state and the `InputEvent`s,
+    /// together with the seed of the [`EngineRng`] and the frame delta. UI events aren't recorded,
+    /// replaying the input makes the UI send them again.
+    ///
+    /// # Parameters
+    ///
+    /// `path`: The RON file the recording is saved to.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use amethyst::{input::InputBundle, prelude::*};
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let mut dispatcher = DispatcherBuilder::default();
+    /// dispatcher.add_bundle(InputBundle::new());
+    /// let game = Application::build("assets/", GameState)?
+    ///     .with_frame_delta(Duration::from_millis(16))
+    ///     .with_seed(42)
+    ///     .with_state_event_recording("playtest.ron")
+    ///     .build(dispatcher)?;
+    /// game.run();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_state_event_recording<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.event_taps
+            .push(Box::new(StateEventRecorder::new(path.as_ref())));
+        self
+    }
+
+    /// Plays a recording made with
+    /// [`with_state_event_recording`](ApplicationBuilder::with_state_event_recording), using the
+    /// seed and frame delta of the recorded run.
+    ///
+    /// The recorded window input is written to the window event channel, so the `InputSystem`
+    /// rebuilds the state of the `InputHandler` and sends the same `InputEvent`s as in the
+    /// recorded run. Without an `InputHandler`, the recorded `InputEvent`s are written to their
+    /// channel directly.
+    ///
+    /// # Parameters
+    ///
+    /// `recording`: The recording to replay, loaded with [`EventRecording::load`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `ApplicationBuilder` after modifying it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use amethyst::{input::InputBundle, prelude::*, EventRecording, RecordedStateEvent};
+    ///
+    /// struct GameState;
+    /// impl SimpleState for GameState {}
+    ///
+    /// # fn main() -> amethyst::Result<()> {
+    /// let recording = EventRecording::<RecordedStateEvent>::load("playtest.ron")?;
+    /// let mut dispatcher = DispatcherBuilder::default();
+    /// dispatcher.add_bundle(InputBundle::new());
+    /// let mut game = Application::build("assets/", GameState)?
+    ///     .headless(60)
+    ///     .with_state_event_replay(recording)
+    ///     .build(dispatcher)?;
+    /// while game.step() {}
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_state_event_replay(
+        mut self,
+        recording: EventRecording<RecordedStateEvent>,
+    ) -> Self {
+        self = self.with_recorded_run(&recording);
+        self.event_taps
+            .push(Box::new(StateEventReplay::new(recording)));
+        self
+    }
+
+    // Uses the seed and frame delta of a recorded run.
+    fn with_recorded_run<V>(mut self, recording: &EventRecording<V>) -> Self {
+        if let Some(seed) = recording.seed() {
+            self = self.with_seed(seed);
+        }
+        if let Some(delta) = recording.frame_delta() {
+            self.frame_delta = Some(delta);
+        }
+        self
+    }
+
     /// Build an `Application` object using the `ApplicationBuilder` as configured.
     ///
     /// # Returns
@@ -818,12 +1387,21 @@ where
         profile_scope!("new");
 
         let data = init.build(&mut self.world, &mut self.resources)?;
+        let fixed_dispatcher = match self.fixed_dispatcher.as_mut() {
+            Some(builder) => Some(builder.build(&mut self.world, &mut self.resources)?),
+            None => None,
+        };
 
-        let event_reader_id = self
-            .resources
-            .get_mut::<EventChannel<Event<'static, ()>>>()
-            .unwrap()
-            .register_reader();
+        let event_reader_id = if self.headless {
+            None
+        } else {
+            Some(
+                self.resources
+                    .get_mut::<EventChannel<Event<'static, ()>>>()
+                    .unwrap()
+                    .register_reader(),
+            )
+        };
 
         let mut trans_event_channel = EventChannel::<TransEvent<T, E>>::with_capacity(2);
         let trans_reader_id = trans_event_channel.register_reader();
@@ -832,6 +1410,16 @@ where
         let mut reader = X::default();
         reader.setup(&mut self.resources);
 
+        for tap in &mut self.event_taps {
+            tap.setup(self.frame_delta, &mut self.resources);
+        }
+
+        #[cfg(feature = "asset-daemon")]
+        let asset_daemon = match self.resources.get::<LoaderMode>().as_deref() {
+            Some(LoaderMode::Packfile(_)) => None,
+            _ => Some(AssetDaemon::new(self.asset_dirs)),
+        };
+
         Ok(CoreApplication {
             world: self.world,
             resources: self.resources,
@@ -839,11 +1427,17 @@ where
             reader,
             events: Vec::new(),
             ignore_window_close: self.ignore_window_close,
+            headless: self.headless,
+            shutdown_requested: Arc::new(AtomicBool::new(false)),
             data,
+            fixed_dispatcher,
+            frame_delta: self.frame_delta,
+            event_taps: self.event_taps,
+            started: false,
             event_reader_id,
             trans_reader_id,
             #[cfg(feature = "asset-daemon")]
-            asset_daemon: AssetDaemon::new(self.asset_dirs),
+            asset_daemon,
         })
     }
 }
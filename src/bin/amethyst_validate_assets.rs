@@ -0,0 +1,10 @@
+//! Checks asset directories for files without importer, stale `.meta` files, duplicate UUIDs and
+//! broken references, exiting with a non-zero status when problems are found.
+//!
+//! The importers known are those of the engine crates enabled by the features of the build.
+
+use amethyst::{assets::run_asset_validation, Error};
+
+fn main() -> Result<(), Error> {
+    run_asset_validation()
+}
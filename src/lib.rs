@@ -113,9 +113,10 @@ pub use self::{
     },
     error::Error,
     game_data::{DataDispose, DataInit, GameData},
+    replay::{EventRecording, RecordedStateEvent, WindowInput},
     state::{
-        EmptyState, EmptyTrans, SimpleState, SimpleTrans, State, StateData, StateMachine, Trans,
-        TransEvent,
+        in_state, ActiveStates, EmptyState, EmptyTrans, SimpleState, SimpleTrans, State,
+        StateData, StateMachine, Trans, TransEvent,
     },
     state_event::{StateEvent, StateEventReader},
 };
@@ -126,6 +127,7 @@ pub mod prelude;
 
 mod app;
 mod game_data;
+mod replay;
 mod state;
 mod state_event;
 
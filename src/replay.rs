@@ -0,0 +1,923 @@
+//! Recording and replay of event streams, to reproduce a run of an application.
+
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use log::error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use winit::{
+    dpi::PhysicalPosition,
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        MouseScrollDelta, VirtualKeyCode, WindowEvent,
+    },
+    window::WindowId,
+};
+
+use crate::{
+    config::{Config, ConfigFormat},
+    core::{
+        rng::EngineRng,
+        shrev::{EventChannel, ReaderId},
+    },
+    ecs::Resources,
+    error::Error,
+    input::{InputEvent, InputHandler},
+};
+
+/// The events written to an `EventChannel` during a run, frame by frame.
+///
+/// Together with the seed of the [`EngineRng`] and the frame delta of the recorded run, this is
+/// enough to play the run again: replaying a recording in an application built with the same
+/// states and systems reproduces the same world.
+///
+/// Recordings are created with
+/// [`ApplicationBuilder::with_event_recording`](crate::ApplicationBuilder::with_event_recording)
+/// and played with
+/// [`ApplicationBuilder::with_event_replay`](crate::ApplicationBuilder::with_event_replay).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventRecording<V> {
+    seed: Option<u64>,
+    frame_delta: Option<Duration>,
+    frames: Vec<Vec<V>>,
+}
+
+impl<V> EventRecording<V> {
+    /// Creates an empty recording for a run with the given seed and frame delta.
+    #[must_use]
+    pub fn new(seed: Option<u64>, frame_delta: Option<Duration>) -> Self {
+        EventRecording {
+            seed,
+            frame_delta,
+            frames: Vec::new(),
+        }
+    }
+
+    /// The seed of the `EngineRng` of the recorded run.
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The duration of every frame of the recorded run, `None` if it used wall clock time.
+    #[must_use]
+    pub fn frame_delta(&self) -> Option<Duration> {
+        self.frame_delta
+    }
+
+    /// The recorded events, the events of each frame being those read by the states at its start.
+    #[must_use]
+    pub fn frames(&self) -> &[Vec<V>] {
+        &self.frames
+    }
+
+    /// Appends the events of the next frame.
+    pub fn push_frame(&mut self, events: Vec<V>) {
+        self.frames.push(events);
+    }
+}
+
+impl<V> EventRecording<V>
+where
+    V: Serialize + DeserializeOwned,
+{
+    /// Loads a recording from a RON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or doesn't contain a recording of `V` events.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Ok(<Self as Config>::load(path)?)
+    }
+
+    /// Saves this recording to a RON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(self.write_format(ConfigFormat::Ron, path)?)
+    }
+}
+
+/// A window event read by the `InputHandler`, in a form that can be saved to a recording.
+///
+/// Other window events, like resizes, don't change the input state and aren't recorded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WindowInput {
+    /// The keyboard modifiers changed.
+    ModifiersChanged {
+        /// Whether shift is held down.
+        shift: bool,
+        /// Whether control is held down.
+        ctrl: bool,
+        /// Whether alt is held down.
+        alt: bool,
+        /// Whether the logo key is held down.
+        logo: bool,
+    },
+    /// A character was typed.
+    ReceivedCharacter(char),
+    /// A key was pressed or released.
+    KeyboardInput {
+        /// Position of the key on the keyboard.
+        scancode: u32,
+        /// Whether the key was pressed or released.
+        state: ElementState,
+        /// Meaning of the key, if it has one.
+        virtual_keycode: Option<VirtualKeyCode>,
+    },
+    /// A mouse button was pressed or released.
+    MouseInput {
+        /// Whether the button was pressed or released.
+        state: ElementState,
+        /// The mouse button.
+        button: MouseButton,
+    },
+    /// The cursor moved to a new position in the window, in physical pixels.
+    CursorMoved {
+        /// Horizontal position of the cursor.
+        x: f64,
+        /// Vertical position of the cursor.
+        y: f64,
+    },
+    /// The window gained or lost focus.
+    Focused(bool),
+    /// The mouse device moved.
+    MouseMotion {
+        /// Horizontal movement of the mouse.
+        delta_x: f64,
+        /// Vertical movement of the mouse.
+        delta_y: f64,
+    },
+    /// The mouse wheel scrolled by a number of lines.
+    MouseWheelLines {
+        /// Lines scrolled horizontally.
+        delta_x: f32,
+        /// Lines scrolled vertically.
+        delta_y: f32,
+    },
+    /// The mouse wheel or touchpad scrolled by a number of pixels.
+    MouseWheelPixels {
+        /// Pixels scrolled horizontally.
+        delta_x: f64,
+        /// Pixels scrolled vertically.
+        delta_y: f64,
+    },
+}
+
+impl WindowInput {
+    /// The input of a window event, `None` if the event doesn't change the input state.
+    #[must_use]
+    pub fn from_event(event: &Event<'_, ()>) -> Option<Self> {
+        match event {
+            Event::WindowEvent { event, .. } => match *event {
+                WindowEvent::ModifiersChanged(modifiers) => Some(WindowInput::ModifiersChanged {
+                    shift: modifiers.shift(),
+                    ctrl: modifiers.ctrl(),
+                    alt: modifiers.alt(),
+                    logo: modifiers.logo(),
+                }),
+                WindowEvent::ReceivedCharacter(c) => Some(WindowInput::ReceivedCharacter(c)),
+                WindowEvent::KeyboardInput { input, .. } => Some(WindowInput::KeyboardInput {
+                    scancode: input.scancode,
+                    state: input.state,
+                    virtual_keycode: input.virtual_keycode,
+                }),
+                WindowEvent::MouseInput { state, button, .. } => {
+                    Some(WindowInput::MouseInput { state, button })
+                }
+                WindowEvent::CursorMoved { position, .. } => Some(WindowInput::CursorMoved {
+                    x: position.x,
+                    y: position.y,
+                }),
+                WindowEvent::Focused(focused) => Some(WindowInput::Focused(focused)),
+                _ => None,
+            },
+            Event::DeviceEvent { event, .. } => match *event {
+                DeviceEvent::MouseMotion {
+                    delta: (delta_x, delta_y),
+                } => Some(WindowInput::MouseMotion { delta_x, delta_y }),
+                DeviceEvent::MouseWheel {
+                    delta: MouseScrollDelta::LineDelta(delta_x, delta_y),
+                } => Some(WindowInput::MouseWheelLines { delta_x, delta_y }),
+                DeviceEvent::MouseWheel {
+                    delta: MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }),
+                } => Some(WindowInput::MouseWheelPixels {
+                    delta_x: x,
+                    delta_y: y,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The window event this input was recorded from.
+    ///
+    /// The event comes from no real window or device, its ids are the dummy ones of `winit`.
+    #[must_use]
+    #[allow(deprecated)] // The modifiers fields are deprecated but still mandatory.
+    pub fn to_event(&self) -> Event<'static, ()> {
+        let window_id = unsafe { WindowId::dummy() };
+        let device_id = unsafe { DeviceId::dummy() };
+        let window_event = |event| Event::WindowEvent { window_id, event };
+        match *self {
+            WindowInput::ModifiersChanged {
+                shift,
+                ctrl,
+                alt,
+                logo,
+            } => {
+                let mut modifiers = ModifiersState::empty();
+                modifiers.set(ModifiersState::SHIFT, shift);
+                modifiers.set(ModifiersState::CTRL, ctrl);
+                modifiers.set(ModifiersState::ALT, alt);
+                modifiers.set(ModifiersState::LOGO, logo);
+                window_event(WindowEvent::ModifiersChanged(modifiers))
+            }
+            WindowInput::ReceivedCharacter(c) => window_event(WindowEvent::ReceivedCharacter(c)),
+            WindowInput::KeyboardInput {
+                scancode,
+                state,
+                virtual_keycode,
+            } => window_event(WindowEvent::KeyboardInput {
+                device_id,
+                input: KeyboardInput {
+                    scancode,
+                    state,
+                    virtual_keycode,
+                    modifiers: ModifiersState::default(),
+                },
+                is_synthetic: false,
+            }),
+            WindowInput::MouseInput { state, button } => window_event(WindowEvent::MouseInput {
+                device_id,
+                state,
+                button,
+                modifiers: ModifiersState::default(),
+            }),
+            WindowInput::CursorMoved { x, y } => window_event(WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition { x, y },
+                modifiers: ModifiersState::default(),
+            }),
+            WindowInput::Focused(focused) => window_event(WindowEvent::Focused(focused)),
+            WindowInput::MouseMotion { delta_x, delta_y } => Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseMotion {
+                    delta: (delta_x, delta_y),
+                },
+            },
+            WindowInput::MouseWheelLines { delta_x, delta_y } => Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseWheel {
+                    delta: MouseScrollDelta::LineDelta(delta_x, delta_y),
+                },
+            },
+            WindowInput::MouseWheelPixels { delta_x, delta_y } => Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseWheel {
+                    delta: MouseScrollDelta::PixelDelta(PhysicalPosition {
+                        x: delta_x,
+                        y: delta_y,
+                    }),
+                },
+            },
+        }
+    }
+}
+
+/// A `StateEvent` handled by the states, in a form that can be saved to a recording.
+///
+/// UI events refer to entities and aren't recorded, the UI systems send them again when the
+/// recorded input is replayed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecordedStateEvent {
+    /// A `StateEvent::Window` changing the input state.
+    Window(WindowInput),
+    /// A `StateEvent::Input`.
+    Input(InputEvent),
+}
+
+/// Hooks into the frame of a `CoreApplication` to record or inject events.
+pub(crate) trait EventTap: Debug {
+    /// Runs when the application is built, after the bundles have set up the resources.
+    fn setup(&mut self, _frame_delta: Option<Duration>, _resources: &mut Resources) {}
+
+    /// Runs at the start of each frame, before the states handle the events.
+    fn begin_frame(&mut self, _resources: &mut Resources) {}
+
+    /// Runs right before the states update and the main dispatcher runs.
+    fn before_update(&mut self, _resources: &mut Resources) {}
+
+    /// Runs when the application shuts down.
+    fn finish(&mut self, _resources: &mut Resources) {}
+}
+
+/// Records the events of a channel and saves them when the application shuts down.
+#[derive(Debug)]
+pub(crate) struct EventRecorder<V: 'static> {
+    path: PathBuf,
+    reader: Option<ReaderId<V>>,
+    recording: EventRecording<V>,
+}
+
+impl<V> EventRecorder<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new(path: &Path) -> Self {
+        EventRecorder {
+            path: path.to_path_buf(),
+            reader: None,
+            recording: EventRecording::new(None, None),
+        }
+    }
+
+    fn record_frame(&mut self, resources: &Resources) {
+        let reader = self
+            .reader
+            .as_mut()
+            .expect("event recorder used before its setup");
+        let events = resources
+            .get::<EventChannel<V>>()
+            .expect("recorded event channel was removed")
+            .read(reader)
+            .cloned()
+            .collect();
+        self.recording.push_frame(events);
+    }
+}
+
+impl<V> EventTap for EventRecorder<V>
+where
+    V: Clone + Debug + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    fn setup(&mut self, frame_delta: Option<Duration>, resources: &mut Resources) {
+        let seed = resources.get::<EngineRng>().map(|rng| rng.seed());
+        self.recording = EventRecording::new(seed, frame_delta);
+        self.reader = Some(
+            resources
+                .get_mut_or_default::<EventChannel<V>>()
+                .register_reader(),
+        );
+    }
+
+    fn begin_frame(&mut self, resources: &mut Resources) {
+        self.record_frame(resources);
+    }
+
+    fn finish(&mut self, resources: &mut Resources) {
+        self.record_frame(resources);
+        if let Err(err) = self.recording.save(&self.path) {
+            error!(
+                "Failed to save the event recording to {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+/// Writes the events of a recording to their channel.
+///
+/// The events a frame starts with were written during the previous frame, so they are injected
+/// before the previous frame's update. Systems reading the channel then see them in the same frame
+/// as in the recorded run, assuming they ran after the systems writing them.
+#[derive(Debug)]
+pub(crate) struct EventReplay<V> {
+    frames: std::vec::IntoIter<Vec<V>>,
+    started: bool,
+}
+
+impl<V> EventReplay<V> {
+    pub(crate) fn new(recording: EventRecording<V>) -> Self {
+        EventReplay {
+            frames: recording.frames.into_iter(),
+            started: false,
+        }
+    }
+}
+
+impl<V> EventReplay<V>
+where
+    V: Send + Sync + 'static,
+{
+    fn inject_frame(&mut self, resources: &mut Resources) {
+        if let Some(events) = self.frames.next() {
+            resources
+                .get_mut_or_default::<EventChannel<V>>()
+                .iter_write(events);
+        }
+    }
+}
+
+impl<V> EventTap for EventReplay<V>
+where
+    V: Debug + Send + Sync + 'static,
+{
+    fn begin_frame(&mut self, resources: &mut Resources) {
+        if !self.started {
+            self.started = true;
+            self.inject_frame(resources);
+        }
+    }
+
+    fn before_update(&mut self, resources: &mut Resources) {
+        self.inject_frame(resources);
+    }
+}
+
+/// Records the window input and input events read by the states, and saves them when the
+/// application shuts down.
+#[derive(Debug)]
+pub(crate) struct StateEventRecorder {
+    path: PathBuf,
+    readers: Option<(ReaderId<Event<'static, ()>>, ReaderId<InputEvent>)>,
+    recording: EventRecording<RecordedStateEvent>,
+}
+
+impl StateEventRecorder {
+    pub(crate) fn new(path: &Path) -> Self {
+        StateEventRecorder {
+            path: path.to_path_buf(),
+            readers: None,
+            recording: EventRecording::new(None, None),
+        }
+    }
+
+    fn record_frame(&mut self, resources: &Resources) {
+        let (window_reader, input_reader) = self
+            .readers
+            .as_mut()
+            .expect("state event recorder used before its setup");
+        let window_events = resources
+            .get::<EventChannel<Event<'static, ()>>>()
+            .expect("window event channel was removed");
+        let input_events = resources
+            .get::<EventChannel<InputEvent>>()
+            .expect("input event channel was removed");
+        let events = window_events
+            .read(window_reader)
+            .filter_map(WindowInput::from_event)
+            .map(RecordedStateEvent::Window)
+            .chain(
+                input_events
+                    .read(input_reader)
+                    .cloned()
+                    .map(RecordedStateEvent::Input),
+            )
+            .collect();
+        self.recording.push_frame(events);
+    }
+}
+
+impl EventTap for StateEventRecorder {
+    fn setup(&mut self, frame_delta: Option<Duration>, resources: &mut Resources) {
+        let seed = resources.get::<EngineRng>().map(|rng| rng.seed());
+        self.recording = EventRecording::new(seed, frame_delta);
+        let window_reader = resources
+            .get_mut_or_default::<EventChannel<Event<'static, ()>>>()
+            .register_reader();
+        let input_reader = resources
+            .get_mut_or_default::<EventChannel<InputEvent>>()
+            .register_reader();
+        self.readers = Some((window_reader, input_reader));
+    }
+
+    fn begin_frame(&mut self, resources: &mut Resources) {
+        self.record_frame(resources);
+    }
+
+    fn finish(&mut self, resources: &mut Resources) {
+        self.record_frame(resources);
+        if let Err(err) = self.recording.save(&self.path) {
+            error!(
+                "Failed to save the state event recording to {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+/// Writes the window input of a recording to the window event channel, so the `InputSystem`
+/// rebuilds the `InputHandler` state and sends the recorded input events again.
+///
+/// Without an `InputHandler`, the recorded input events were written by the application itself
+/// and are written to their channel directly. Events are injected in the same frames as with
+/// [`EventReplay`].
+#[derive(Debug)]
+pub(crate) struct StateEventReplay {
+    frames: std::vec::IntoIter<Vec<RecordedStateEvent>>,
+    started: bool,
+    regenerates_input: bool,
+}
+
+impl StateEventReplay {
+    pub(crate) fn new(recording: EventRecording<RecordedStateEvent>) -> Self {
+        StateEventReplay {
+            frames: recording.frames.into_iter(),
+            started: false,
+            regenerates_input: false,
+        }
+    }
+
+    fn inject_frame(&mut self, resources: &mut Resources) {
+        if let Some(events) = self.frames.next() {
+            for event in events {
+                match event {
+                    RecordedStateEvent::Window(input) => {
+                        resources
+                            .get_mut_or_default::<EventChannel<Event<'static, ()>>>()
+                            .single_write(input.to_event());
+                    }
+                    RecordedStateEvent::Input(event) => {
+                        if !self.regenerates_input {
+                            resources
+                                .get_mut_or_default::<EventChannel<InputEvent>>()
+                                .single_write(event);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EventTap for StateEventReplay {
+    fn setup(&mut self, _frame_delta: Option<Duration>, resources: &mut Resources) {
+        self.regenerates_input = resources.contains::<InputHandler>();
+    }
+
+    fn begin_frame(&mut self, resources: &mut Resources) {
+        if !self.started {
+            self.started = true;
+            self.inject_frame(resources);
+        }
+    }
+
+    fn before_update(&mut self, resources: &mut Resources) {
+        self.inject_frame(resources);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        assets::LoaderMode,
+        ecs::{DispatcherBuilder, IntoQuery, SystemBuilder},
+        input::InputBundle,
+        prelude::*,
+        StateEventReader,
+    };
+
+    const SPACE_PRESS: WindowInput = WindowInput::KeyboardInput {
+        scancode: 57,
+        state: ElementState::Pressed,
+        virtual_keycode: Some(VirtualKeyCode::Space),
+    };
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Jump(u32);
+
+    #[derive(Clone, Copy, Debug)]
+    struct Height(f32);
+
+    // Writes a `Jump` every other frame when `jumps` is set, and quits after 6 frames.
+    struct JumpState {
+        frame: u32,
+        jumps: bool,
+    }
+
+    impl JumpState {
+        fn new(jumps: bool) -> Self {
+            JumpState { frame: 0, jumps }
+        }
+    }
+
+    impl SimpleState for JumpState {
+        fn update(&mut self, data: &mut StateData<'_, GameData>) -> SimpleTrans {
+            self.frame += 1;
+            if self.jumps && self.frame % 2 == 1 {
+                data.resources
+                    .get_mut::<EventChannel<Jump>>()
+                    .unwrap()
+                    .single_write(Jump(self.frame));
+            }
+            if self.frame < 6 {
+                Trans::None
+            } else {
+                Trans::Quit
+            }
+        }
+    }
+
+    // Runs the application to completion, every jump spawning an entity of random height.
+    fn jump_heights(
+        builder: ApplicationBuilder<JumpState, GameData, StateEvent, StateEventReader>,
+    ) -> Result<Vec<f32>, Error> {
+        // A packfile mode keeps the asset daemon from starting, no assets are loaded.
+        let mut builder = builder
+            .headless(60)
+            .with_resource(LoaderMode::Packfile(PathBuf::new()));
+        let mut reader = builder
+            .resources
+            .get_mut_or_default::<EventChannel<Jump>>()
+            .register_reader();
+
+        let mut dispatcher = DispatcherBuilder::default();
+        dispatcher.add_system(move || {
+            SystemBuilder::new("JumpSystem")
+                .read_resource::<EventChannel<Jump>>()
+                .write_resource::<EngineRng>()
+                .build(move |commands, _, (jumps, rng), _| {
+                    for _ in jumps.read(&mut reader) {
+                        commands.push((Height(rng.gen()),));
+                    }
+                })
+        });
+
+        let mut game = builder.build(dispatcher)?;
+        while game.step() {}
+        Ok(<&Height>::query()
+            .iter(game.world())
+            .map(|height| height.0)
+            .collect())
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    struct Pressed(u32);
+
+    // Presses space on the second frame when `presses` is set, and quits after 6 frames.
+    struct PressState {
+        frame: u32,
+        presses: bool,
+    }
+
+    impl SimpleState for PressState {
+        fn update(&mut self, data: &mut StateData<'_, GameData>) -> SimpleTrans {
+            self.frame += 1;
+            if self.presses && self.frame == 2 {
+                data.resources
+                    .get_mut::<EventChannel<Event<'static, ()>>>()
+                    .unwrap()
+                    .single_write(SPACE_PRESS.to_event());
+            }
+            if self.frame < 6 {
+                Trans::None
+            } else {
+                Trans::Quit
+            }
+        }
+    }
+
+    // Runs the application to completion, every frame with space held down spawning an entity.
+    // Returns the frames space was held down in, and whether it is still held down at the end.
+    fn space_frames(
+        builder: ApplicationBuilder<PressState, GameData, StateEvent, StateEventReader>,
+    ) -> Result<(Vec<u32>, bool), Error> {
+        let mut dispatcher = DispatcherBuilder::default();
+        dispatcher.add_bundle(InputBundle::new());
+        let mut frame = 0;
+        dispatcher.add_system(move || {
+            SystemBuilder::new("SpaceSystem")
+                .read_resource::<InputHandler>()
+                .build(move |commands, _, input, _| {
+                    frame += 1;
+                    if input.key_is_down(VirtualKeyCode::Space) {
+                        commands.push((Pressed(frame),));
+                    }
+                })
+        });
+
+        let mut game = builder
+            .headless(60)
+            .with_resource(LoaderMode::Packfile(PathBuf::new()))
+            .build(dispatcher)?;
+        while game.step() {}
+        let mut frames = <&Pressed>::query()
+            .iter(game.world())
+            .map(|pressed| pressed.0)
+            .collect::<Vec<_>>();
+        frames.sort_unstable();
+        let space_down = game
+            .resources()
+            .get::<InputHandler>()
+            .unwrap()
+            .key_is_down(VirtualKeyCode::Space);
+        Ok((frames, space_down))
+    }
+
+    fn read_all(resources: &Resources, reader: &mut ReaderId<Jump>) -> Vec<Jump> {
+        resources
+            .get::<EventChannel<Jump>>()
+            .unwrap()
+            .read(reader)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn records_events_per_frame() {
+        let mut resources = Resources::default();
+        resources.insert(EngineRng::seed_from(3));
+        let mut recorder = EventRecorder::<Jump>::new(Path::new("jumps.ron"));
+        recorder.setup(Some(Duration::from_millis(10)), &mut resources);
+
+        resources
+            .get_mut::<EventChannel<Jump>>()
+            .unwrap()
+            .single_write(Jump(1));
+        recorder.begin_frame(&mut resources);
+        recorder.begin_frame(&mut resources);
+        resources
+            .get_mut::<EventChannel<Jump>>()
+            .unwrap()
+            .iter_write(vec![Jump(2), Jump(3)]);
+        recorder.begin_frame(&mut resources);
+
+        assert_eq!(recorder.recording.seed(), Some(3));
+        assert_eq!(
+            recorder.recording.frames(),
+            &[vec![Jump(1)], vec![], vec![Jump(2), Jump(3)]][..]
+        );
+    }
+
+    #[test]
+    fn replay_injects_events_before_they_are_read() {
+        let mut recording = EventRecording::new(None, None);
+        recording.push_frame(vec![Jump(1)]);
+        recording.push_frame(vec![Jump(2)]);
+
+        let mut resources = Resources::default();
+        let mut reader = resources
+            .get_mut_or_default::<EventChannel<Jump>>()
+            .register_reader();
+        let mut replay = EventReplay::new(recording);
+
+        replay.begin_frame(&mut resources);
+        assert_eq!(read_all(&resources, &mut reader), vec![Jump(1)]);
+        replay.before_update(&mut resources);
+        assert_eq!(read_all(&resources, &mut reader), vec![Jump(2)]);
+        replay.begin_frame(&mut resources);
+        replay.before_update(&mut resources);
+        assert!(read_all(&resources, &mut reader).is_empty());
+    }
+
+    #[test]
+    fn recording_round_trip() {
+        let path = std::env::temp_dir().join("amethyst_recording_round_trip.ron");
+        let mut recording = EventRecording::new(Some(9), Some(Duration::from_millis(16)));
+        recording.push_frame(vec![Jump(4)]);
+        recording.push_frame(Vec::new());
+
+        recording.save(&path).unwrap();
+        let loaded = EventRecording::<Jump>::load(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded, recording);
+    }
+
+    #[test]
+    fn replay_reproduces_the_world() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("amethyst_replay_reproduces_the_world.ron");
+        let recorded = jump_heights(
+            Application::build("assets", JumpState::new(true))?
+                .with_frame_delta(Duration::from_millis(16))
+                .with_event_recording::<Jump, _>(&path),
+        )?;
+        let recording = EventRecording::<Jump>::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        let replayed = jump_heights(
+            Application::build("assets", JumpState::new(false))?.with_event_replay(recording),
+        )?;
+
+        assert_eq!(recorded.len(), 3);
+        assert_eq!(replayed, recorded);
+        Ok(())
+    }
+
+    #[test]
+    fn window_input_round_trip() {
+        let inputs = vec![
+            SPACE_PRESS,
+            WindowInput::ModifiersChanged {
+                shift: true,
+                ctrl: false,
+                alt: true,
+                logo: false,
+            },
+            WindowInput::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+            },
+            WindowInput::CursorMoved { x: 12.0, y: 34.5 },
+            WindowInput::MouseWheelLines {
+                delta_x: 0.0,
+                delta_y: -1.0,
+            },
+        ];
+        for input in inputs {
+            assert_eq!(WindowInput::from_event(&input.to_event()), Some(input));
+        }
+    }
+
+    #[test]
+    fn state_event_replay_rebuilds_the_input_handler() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("amethyst_state_event_replay.ron");
+        let recorded = space_frames(
+            Application::build(
+                "assets",
+                PressState {
+                    frame: 0,
+                    presses: true,
+                },
+            )?
+            .with_frame_delta(Duration::from_millis(16))
+            .with_state_event_recording(&path),
+        )?;
+        let recording = EventRecording::<RecordedStateEvent>::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        let events = recording.frames().concat();
+        assert!(events.contains(&RecordedStateEvent::Window(SPACE_PRESS)));
+        assert!(
+            events.contains(&RecordedStateEvent::Input(InputEvent::KeyPressed {
+                key_code: VirtualKeyCode::Space,
+                scancode: 57,
+            }))
+        );
+
+        let replayed = space_frames(
+            Application::build(
+                "assets",
+                PressState {
+                    frame: 0,
+                    presses: false,
+                },
+            )?
+            .with_state_event_replay(recording),
+        )?;
+
+        assert!(!recorded.0.is_empty());
+        assert!(replayed.1);
+        assert_eq!(replayed, recorded);
+        Ok(())
+    }
+
+    struct CountingState {
+        starts: Arc<AtomicUsize>,
+        frame: u32,
+    }
+
+    impl SimpleState for CountingState {
+        fn on_start(&mut self, _: StateData<'_, GameData>) {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn update(&mut self, _: &mut StateData<'_, GameData>) -> SimpleTrans {
+            self.frame += 1;
+            if self.frame < 3 {
+                Trans::None
+            } else {
+                Trans::Quit
+            }
+        }
+    }
+
+    #[test]
+    fn run_after_step_does_not_restart_the_states() -> Result<(), Error> {
+        let starts = Arc::new(AtomicUsize::new(0));
+        let mut game = Application::build(
+            "assets",
+            CountingState {
+                starts: Arc::clone(&starts),
+                frame: 0,
+            },
+        )?
+        .headless(60)
+        .with_frame_delta(Duration::from_millis(16))
+        .with_resource(LoaderMode::Packfile(PathBuf::new()))
+        .build(DispatcherBuilder::default())?;
+
+        assert!(game.step());
+        game.run();
+
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 //! Utilities for game state management.
 
-use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::{
+    any::type_name,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
 
+use amethyst_core::run_condition::RunCondition;
 use amethyst_input::is_close_requested;
 use derivative::Derivative;
 #[cfg(feature = "profiler")]
@@ -152,6 +156,12 @@ pub trait State<T, E: Send + Sync + 'static> {
     /// even when this is not the active state,
     /// as long as this state is on the [`StateMachine`](struct.StateMachine.html)'s state-stack.
     fn shadow_update(&mut self, _data: StateData<'_, T>) {}
+
+    /// Name of the state as listed in the [`ActiveStates`] resource. Defaults to the type name,
+    /// which is what [`in_state`] compares against.
+    fn state_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
 }
 
 /// An empty `State` trait. It contains no `StateData` or custom `StateEvent`.
@@ -202,6 +212,12 @@ pub trait EmptyState {
     /// even when this is not the active state,
     /// as long as this state is on the [`StateMachine`](struct.StateMachine.html)'s state-stack.
     fn shadow_update(&mut self, _data: StateData<'_, ()>) {}
+
+    /// Name of the state as listed in the [`ActiveStates`] resource. Defaults to the type name,
+    /// which is what [`in_state`] compares against.
+    fn state_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
 }
 
 impl<T: EmptyState> State<(), StateEvent> for T {
@@ -255,6 +271,11 @@ impl<T: EmptyState> State<(), StateEvent> for T {
     fn shadow_update(&mut self, data: StateData<'_, ()>) {
         self.shadow_update(data);
     }
+
+    /// Name of the state as listed in the [`ActiveStates`] resource.
+    fn state_name(&self) -> &'static str {
+        self.state_name()
+    }
 }
 
 /// A simple `State` trait. It contains `GameData` as its `StateData` and no custom `StateEvent`.
@@ -305,6 +326,12 @@ pub trait SimpleState {
     /// even when this is not the active state,
     /// as long as this state is on the [`StateMachine`](struct.StateMachine.html)'s state-stack.
     fn shadow_update(&mut self, _data: StateData<'_, GameData>) {}
+
+    /// Name of the state as listed in the [`ActiveStates`] resource. Defaults to the type name,
+    /// which is what [`in_state`] compares against.
+    fn state_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
 }
 
 impl<T: SimpleState> State<GameData, StateEvent> for T {
@@ -360,6 +387,74 @@ impl<T: SimpleState> State<GameData, StateEvent> for T {
     fn shadow_update(&mut self, data: StateData<'_, GameData>) {
         self.shadow_update(data);
     }
+
+    /// Name of the state as listed in the [`ActiveStates`] resource.
+    fn state_name(&self) -> &'static str {
+        self.state_name()
+    }
+}
+
+/// Resource listing the names of the states on the [`StateMachine`] stack, from the bottom of
+/// the stack to the active state. It is updated after every transition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActiveStates(Vec<&'static str>);
+
+impl ActiveStates {
+    /// Name of the active state, which is the one on top of the stack.
+    #[must_use]
+    pub fn active(&self) -> Option<&'static str> {
+        self.0.last().copied()
+    }
+
+    /// Names of all states on the stack, from the bottom of the stack to the active state.
+    #[must_use]
+    pub fn stack(&self) -> &[&'static str] {
+        &self.0
+    }
+
+    /// Checks whether `S` is the active state.
+    #[must_use]
+    pub fn is_active<S: ?Sized>(&self) -> bool {
+        self.active() == Some(type_name::<S>())
+    }
+}
+
+/// Run condition met while `S` is the active state, which allows systems to be restricted to
+/// a state without building a dispatcher per state.
+///
+/// States are identified by [`State::state_name`], so this condition never matches a state
+/// which overrides it.
+///
+/// # Examples
+///
+/// ```
+/// use amethyst::{in_state, prelude::*};
+///
+/// struct MenuState;
+/// impl SimpleState for MenuState {}
+///
+/// struct MenuBundle;
+/// impl SystemBundle for MenuBundle {
+///     fn load(
+///         &mut self,
+///         _world: &mut World,
+///         _resources: &mut Resources,
+///         _builder: &mut DispatcherBuilder,
+///     ) -> amethyst::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut dispatcher = DispatcherBuilder::default();
+/// dispatcher.add_bundle_if(MenuBundle, in_state::<MenuState>());
+/// ```
+pub fn in_state<S: ?Sized + 'static>() -> impl RunCondition {
+    let name = type_name::<S>();
+    move |_: &World, resources: &Resources| {
+        resources
+            .get::<ActiveStates>()
+            .map_or(false, |states| states.active() == Some(name))
+    }
 }
 
 /// A simple stack-based state machine (pushdown automaton).
@@ -392,16 +487,36 @@ impl<'a, T, E: Send + Sync + 'static> StateMachine<'a, T, E> {
     /// Will return a result `StateError` if startup failed.
     pub fn start(&mut self, data: StateData<'_, T>) -> Result<(), StateError> {
         if !self.running {
+            let StateData {
+                world,
+                resources,
+                data,
+            } = data;
             let state = self
                 .state_stack
                 .last_mut()
                 .ok_or(StateError::NoStatesPresent)?;
-            state.on_start(data);
+            state.on_start(StateData {
+                world,
+                resources,
+                data,
+            });
             self.running = true;
+            self.sync_active_states(resources);
         }
         Ok(())
     }
 
+    /// Updates the [`ActiveStates`] resource to reflect the state stack.
+    fn sync_active_states(&self, resources: &mut Resources) {
+        let names = self
+            .state_stack
+            .iter()
+            .map(|state| state.state_name())
+            .collect();
+        resources.insert(ActiveStates(names));
+    }
+
     /// Passes a single event to the active state to handle.
     pub fn handle_event(&mut self, data: StateData<'_, T>, event: E) {
         let StateData {
@@ -531,6 +646,17 @@ impl<'a, T, E: Send + Sync + 'static> StateMachine<'a, T, E> {
     /// sequentially in the order of insertion.
     pub fn transition(&mut self, request: Trans<T, E>, data: StateData<'_, T>) {
         if self.running {
+            let StateData {
+                world,
+                resources,
+                data,
+            } = data;
+            let changed = !matches!(request, Trans::None);
+            let data = StateData {
+                world,
+                resources,
+                data,
+            };
             match request {
                 Trans::None => (),
                 Trans::Pop => self.pop(data),
@@ -550,6 +676,9 @@ impl<'a, T, E: Send + Sync + 'static> StateMachine<'a, T, E> {
                 }
                 Trans::Quit => self.stop(data),
             }
+            if changed {
+                self.sync_active_states(resources);
+            }
         }
     }
 
@@ -867,4 +996,45 @@ mod tests {
         sm.update(StateData::new(&mut world, &mut resources, &mut ()));
         assert_eq!(sm.state_stack.len(), 1);
     }
+
+    #[test]
+    fn active_states() {
+        use crate::ecs::World;
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut in_state1 = in_state::<State1>();
+        let mut in_state2 = in_state::<State2>();
+
+        let mut sm = StateMachine::new(State1(1));
+        // Unwrap here is fine because start can only fail when there are no states in the machine.
+        sm.start(StateData::new(&mut world, &mut resources, &mut ()))
+            .unwrap();
+
+        assert!(
+            resources
+                .get::<ActiveStates>()
+                .unwrap()
+                .is_active::<State1>()
+        );
+        assert!(in_state1.evaluate(&world, &resources));
+        assert!(!in_state2.evaluate(&world, &resources));
+
+        sm.transition(
+            Trans::Push(Box::new(State2)),
+            StateData::new(&mut world, &mut resources, &mut ()),
+        );
+        assert_eq!(
+            resources.get::<ActiveStates>().unwrap().stack(),
+            &[type_name::<State1>(), type_name::<State2>()]
+        );
+        assert!(!in_state1.evaluate(&world, &resources));
+        assert!(in_state2.evaluate(&world, &resources));
+
+        sm.transition(
+            Trans::Quit,
+            StateData::new(&mut world, &mut resources, &mut ()),
+        );
+        assert_eq!(resources.get::<ActiveStates>().unwrap().active(), None);
+    }
 }
@@ -0,0 +1,76 @@
+//! Headless applications advanced frame by frame.
+
+use std::{path::PathBuf, time::Duration};
+
+use amethyst::{
+    assets::LoaderMode,
+    core::ecs::{DispatcherBuilder, SystemBuilder},
+    prelude::*,
+    shrev::EventChannel,
+    Error,
+};
+
+#[derive(Default)]
+struct Frames(u32);
+
+// Quits after `frames` updates.
+struct QuitState {
+    frames: u32,
+}
+
+impl SimpleState for QuitState {
+    fn update(&mut self, _: &mut StateData<'_, GameData>) -> SimpleTrans {
+        self.frames -= 1;
+        if self.frames > 0 {
+            Trans::None
+        } else {
+            Trans::Quit
+        }
+    }
+}
+
+// Builds a headless application counting its frames in the `Frames` resource.
+fn counting_application(frames: u32) -> Result<Application<'static, GameData>, Error> {
+    let mut dispatcher = DispatcherBuilder::default();
+    dispatcher.add_system(|| {
+        SystemBuilder::new("CountingSystem")
+            .write_resource::<Frames>()
+            .build(|_, _, frames, _| frames.0 += 1)
+    });
+
+    // A packfile mode keeps the asset daemon from starting, no assets are loaded.
+    Application::build("assets", QuitState { frames })?
+        .headless(60)
+        .with_frame_delta(Duration::from_millis(16))
+        .with_resource(Frames::default())
+        .with_resource(LoaderMode::Packfile(PathBuf::new()))
+        .build(dispatcher)
+}
+
+#[test]
+fn steps_until_a_state_quits() -> Result<(), Error> {
+    let mut game = counting_application(3)?;
+
+    assert!(game.step());
+    assert!(game.step());
+    assert!(!game.step());
+    assert!(!game.step());
+
+    assert_eq!(game.resources().get::<Frames>().unwrap().0, 3);
+    Ok(())
+}
+
+#[test]
+fn steps_until_a_quit_is_sent() -> Result<(), Error> {
+    let mut game = counting_application(u32::MAX)?;
+
+    assert!(game.step());
+    game.resources_mut()
+        .get_mut::<EventChannel<TransEvent<GameData, StateEvent>>>()
+        .unwrap()
+        .single_write(Box::new(|| Trans::Quit));
+    assert!(!game.step());
+
+    assert_eq!(game.resources().get::<Frames>().unwrap().0, 1);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Asset validation with the importers of the engine crates.
+#![cfg(feature = "renderer")]
+
+use std::fs;
+
+use amethyst::assets::{validate_assets, AssetProblem};
+
+#[test]
+fn engine_importers_are_known() {
+    let dir = std::env::temp_dir().join("amethyst_validate_engine_assets");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("logo.png"), "").unwrap();
+    #[cfg(feature = "audio")]
+    fs::write(dir.join("jump.wav"), "").unwrap();
+    fs::write(dir.join("notes.unknown"), "").unwrap();
+
+    let report = validate_assets(&[&dir]).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        report.checked_files(),
+        if cfg!(feature = "audio") { 3 } else { 2 }
+    );
+    assert_eq!(
+        report.problems(),
+        &[AssetProblem::NoImporter {
+            path: dir.join("notes.unknown"),
+        }]
+    );
+}